@@ -0,0 +1,361 @@
+//! Structured Beliefs - credences riding on the fiber.
+//!
+//! A perspective holds propositions with credences. A proposition may be
+//! linked to a fiber component. Moving that component is then evidence:
+//! transport shifts the proposition's log-odds by `sensitivity × Δψ_i`.
+//! Because log-odds add, a journey whose holonomy leaves the component
+//! unchanged also leaves the credence unchanged.
+//!
+//! Evidence found at world points is absorbed by Bayes' rule. When the
+//! observation itself is uncertain, Jeffrey's rule is used instead. Linked
+//! components move with the credence, so state and belief stay in step.
+//!
+//! Confidence follows how decisive the credences are. Valence follows
+//! what they mean: a proposition may carry a desirability, and a credence
+//! shift of Δp in it moves valence by `desirability × Δp`. Growing surer
+//! that the river is safe feels good; growing surer that it floods does not.
+
+use crate::bundle::{Perspective, WorldPoint};
+use crate::types::TopologyError;
+use nalgebra::DMatrix;
+
+/// Credence changes smaller than this are not reported
+pub const CREDENCE_TOLERANCE: f64 = 1e-6;
+
+/// A proposition held with some credence
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proposition {
+    /// What is believed
+    pub statement: String,
+    /// Probability assigned to the statement
+    pub credence: f64,
+    /// Fiber component the credence is linked to
+    pub component: Option<usize>,
+    /// Log-odds shift per unit of the linked component
+    pub sensitivity: f64,
+    /// How welcome the statement is, in [-1, 1] (0 is neutral)
+    pub desirability: f64,
+}
+
+impl Proposition {
+    /// An unlinked proposition (credence clamped to [0, 1])
+    pub fn new(statement: impl Into<String>, credence: f64) -> Self {
+        Self {
+            statement: statement.into(),
+            credence: credence.clamp(0.0, 1.0),
+            component: None,
+            sensitivity: 1.0,
+            desirability: 0.0,
+        }
+    }
+
+    /// Set how welcome the statement is (clamped to [-1, 1])
+    pub fn with_desirability(mut self, desirability: f64) -> Self {
+        self.desirability = desirability.clamp(-1.0, 1.0);
+        self
+    }
+
+    /// Link the credence to a fiber component
+    pub fn linked_to(mut self, component: usize, sensitivity: f64) -> Self {
+        self.component = Some(component);
+        self.sensitivity = sensitivity;
+        self
+    }
+
+    /// ln(p / (1 - p))
+    pub fn log_odds(&self) -> f64 {
+        let p = self.credence.clamp(1e-12, 1.0 - 1e-12);
+        (p / (1.0 - p)).ln()
+    }
+
+    /// Set the credence from log-odds
+    fn set_log_odds(&mut self, log_odds: f64) {
+        self.credence = 1.0 / (1.0 + (-log_odds).exp());
+    }
+}
+
+/// Evidence bearing on one proposition
+///
+/// `if_true` and `if_false` are the likelihoods P(E | H) and P(E | ¬H).
+/// With `certainty` < 1 the observation of E is itself uncertain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evidence {
+    /// Statement of the proposition the evidence bears on
+    pub proposition: String,
+    /// P(E | H)
+    pub if_true: f64,
+    /// P(E | ¬H)
+    pub if_false: f64,
+    /// Probability that E was really observed
+    pub certainty: f64,
+}
+
+impl Evidence {
+    /// Evidence with the given likelihoods, observed with certainty
+    pub fn new(proposition: impl Into<String>, if_true: f64, if_false: f64) -> Self {
+        Self {
+            proposition: proposition.into(),
+            if_true: if_true.clamp(0.0, 1.0),
+            if_false: if_false.clamp(0.0, 1.0),
+            certainty: 1.0,
+        }
+    }
+
+    /// Make the observation uncertain (Jeffrey conditioning)
+    pub fn with_certainty(mut self, certainty: f64) -> Self {
+        self.certainty = certainty.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Posterior credence in H starting from `prior`
+    ///
+    /// P'(H) = r P(H | E) + (1 - r) P(H | ¬E); r = 1 is Bayes' rule.
+    pub fn posterior(&self, prior: f64) -> f64 {
+        let bayes = |l_true: f64, l_false: f64| {
+            let evidence = l_true * prior + l_false * (1.0 - prior);
+            if evidence > 0.0 { l_true * prior / evidence } else { prior }
+        };
+        let given_e = bayes(self.if_true, self.if_false);
+        if self.certainty >= 1.0 {
+            return given_e;
+        }
+        let given_not_e = bayes(1.0 - self.if_true, 1.0 - self.if_false);
+        self.certainty * given_e + (1.0 - self.certainty) * given_not_e
+    }
+}
+
+/// A credence that moved
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeliefChange {
+    /// The proposition
+    pub statement: String,
+    /// Credence before
+    pub before: f64,
+    /// Credence after
+    pub after: f64,
+}
+
+impl BeliefChange {
+    /// Signed change in credence
+    pub fn shift(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+impl Perspective {
+    /// Add a proposition
+    pub fn with_proposition(mut self, proposition: Proposition) -> Self {
+        self.propositions.push(proposition);
+        self.refresh_confidence();
+        self
+    }
+
+    /// Credence in a statement, if held
+    pub fn credence(&self, statement: &str) -> Option<f64> {
+        self.propositions.iter().find(|p| p.statement == statement).map(|p| p.credence)
+    }
+
+    /// Carry the state through a transport matrix, updating linked credences
+    ///
+    /// The matrix must be square and act on a state of this perspective's size.
+    pub fn apply_transport(&mut self, transport: &DMatrix<f64>) -> Result<(), TopologyError> {
+        let n = self.state.len();
+        if transport.ncols() != n || transport.nrows() != n {
+            let found = if transport.ncols() != n { transport.ncols() } else { transport.nrows() };
+            return Err(TopologyError::DimensionMismatch { expected: n, found });
+        }
+        let before = self.state.clone();
+        self.state = transport * &self.state;
+        let mut felt = 0.0;
+        for proposition in &mut self.propositions {
+            if let Some(i) = proposition.component.filter(|&i| i < self.state.len()) {
+                let credence = proposition.credence;
+                let log_odds = proposition.log_odds() + proposition.sensitivity * (self.state[i] - before[i]);
+                proposition.set_log_odds(log_odds);
+                felt += proposition.desirability * (proposition.credence - credence);
+            }
+        }
+        self.refresh_confidence();
+        self.feel(felt);
+        Ok(())
+    }
+
+    /// Condition on one piece of evidence; false if the proposition is not held
+    pub fn observe(&mut self, evidence: &Evidence) -> bool {
+        let Some(proposition) = self.propositions.iter_mut().find(|p| p.statement == evidence.proposition) else {
+            return false;
+        };
+        let before = proposition.log_odds();
+        let credence = proposition.credence;
+        proposition.credence = evidence.posterior(proposition.credence);
+        let felt = proposition.desirability * (proposition.credence - credence);
+
+        // Keep the linked component in step with the credence
+        if let Some(i) = proposition.component.filter(|&i| i < self.state.len()) {
+            if proposition.sensitivity != 0.0 {
+                self.state[i] += (proposition.log_odds() - before) / proposition.sensitivity;
+            }
+        }
+        self.refresh_confidence();
+        self.feel(felt);
+        true
+    }
+
+    /// Condition on all evidence found at a world point; returns how many applied
+    pub fn observe_at(&mut self, world: &WorldPoint) -> usize {
+        world.evidence.iter().filter(|e| self.observe(e)).count()
+    }
+
+    /// Propositions whose credence differs from `earlier`
+    pub fn belief_changes(&self, earlier: &Perspective) -> Vec<BeliefChange> {
+        self.propositions.iter()
+            .filter_map(|now| {
+                let before = earlier.credence(&now.statement)?;
+                ((now.credence - before).abs() > CREDENCE_TOLERANCE).then(|| BeliefChange {
+                    statement: now.statement.clone(),
+                    before,
+                    after: now.credence,
+                })
+            })
+            .collect()
+    }
+
+    /// Valence moves with the desirability-weighted credence shift
+    fn feel(&mut self, shift: f64) {
+        self.valence = (self.valence + shift).clamp(-1.0, 1.0);
+    }
+
+    /// Confidence tracks how decisive the credences are
+    fn refresh_confidence(&mut self) {
+        if !self.propositions.is_empty() {
+            let decisiveness: f64 = self.propositions.iter().map(|p| (2.0 * p.credence - 1.0).abs()).sum();
+            self.confidence = decisiveness / self.propositions.len() as f64;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::{FiberBundle, PerspectivalSelf};
+    use crate::types::*;
+
+    fn believer() -> Perspective {
+        Perspective::new(2)
+            .with_state(vec![1.0, 0.0])
+            .with_proposition(Proposition::new("the river is safe", 0.5).linked_to(1, 2.0))
+            .with_proposition(Proposition::new("it will rain", 0.3))
+    }
+
+    fn square() -> Path {
+        Path::closed(vec![
+            Coordinate::new(vec![0.0, 0.0]),
+            Coordinate::new(vec![1.0, 0.0]),
+            Coordinate::new(vec![1.0, 1.0]),
+            Coordinate::new(vec![0.0, 1.0]),
+        ])
+    }
+
+    #[test]
+    fn test_bayes_and_jeffrey() {
+        let evidence = Evidence::new("it will rain", 0.8, 0.2);
+        assert!((evidence.posterior(0.5) - 0.8).abs() < 1e-12);
+
+        // Uncertain observation: a mix of conditioning on E and on ¬E
+        let uncertain = evidence.clone().with_certainty(0.75);
+        let expected = 0.75 * 0.8 + 0.25 * 0.2;
+        assert!((uncertain.posterior(0.5) - expected).abs() < 1e-12);
+
+        // Certainty equal to the prior chance of E teaches nothing
+        let p_e = 0.8 * 0.3 + 0.2 * 0.7;
+        assert!((evidence.with_certainty(p_e).posterior(0.3) - 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_holonomy_reports_changed_beliefs() {
+        let mut bundle = FiberBundle::curved(2, 2, 0.5);
+        bundle.set_section(WorldPoint::new(vec![0.0, 0.0], "home"), believer());
+
+        let result = bundle.holonomy(&square()).unwrap();
+        let changes = result.final_perspective.belief_changes(&result.initial_perspective);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].statement, "the river is safe");
+
+        // The linked component moved by sin(0.5); log-odds moved twice that
+        let after = Proposition { credence: changes[0].after, ..Proposition::new("", 0.0) };
+        assert!((after.log_odds() - 2.0 * 0.5f64.sin()).abs() < 1e-9);
+        assert!(result.describe().contains("the river is safe"));
+        assert!(!result.describe().contains("it will rain"));
+    }
+
+    #[test]
+    fn test_flat_loop_keeps_credences() {
+        let mut bundle = FiberBundle::trivial(2, 2);
+        bundle.set_section(WorldPoint::new(vec![0.0, 0.0], "home"), believer());
+        let result = bundle.holonomy(&square()).unwrap();
+        assert!(result.final_perspective.belief_changes(&result.initial_perspective).is_empty());
+        assert!(!result.describe().contains("Beliefs changed"));
+    }
+
+    #[test]
+    fn test_evidence_moves_linked_state() {
+        let mut perspective = believer();
+        let before = perspective.clone();
+        assert!(perspective.observe(&Evidence::new("the river is safe", 0.9, 0.1)));
+        assert!(!perspective.observe(&Evidence::new("unheld", 0.9, 0.1)));
+
+        // Log-odds rose by ln 9; the linked component by ln 9 / sensitivity
+        assert!((perspective.credence("the river is safe").unwrap() - 0.9).abs() < 1e-12);
+        assert!((perspective.state[1] - 9f64.ln() / 2.0).abs() < 1e-12);
+        assert!(perspective.confidence > before.confidence);
+        // Neutral propositions leave valence alone
+        assert_eq!(perspective.valence, before.valence);
+    }
+
+    #[test]
+    fn test_welcome_news_lifts_valence() {
+        let mut perspective = Perspective::new(2)
+            .with_proposition(Proposition::new("the river is safe", 0.5).with_desirability(1.0))
+            .with_proposition(Proposition::new("the bridge is out", 0.5).with_desirability(-0.5));
+
+        perspective.observe(&Evidence::new("the river is safe", 0.9, 0.1));
+        assert!((perspective.valence - 0.4).abs() < 1e-12);
+
+        // Growing surer of bad news costs half as much per unit of credence
+        perspective.observe(&Evidence::new("the bridge is out", 0.9, 0.1));
+        assert!((perspective.valence - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_holonomy_moves_valence() {
+        let mut bundle = FiberBundle::curved(2, 2, 0.5);
+        let hopeful = Perspective::new(2)
+            .with_state(vec![1.0, 0.0])
+            .with_proposition(Proposition::new("the river is safe", 0.5).linked_to(1, 2.0).with_desirability(1.0));
+        bundle.set_section(WorldPoint::new(vec![0.0, 0.0], "home"), hopeful);
+
+        let result = bundle.holonomy(&square()).unwrap();
+        let change = &result.final_perspective.belief_changes(&result.initial_perspective)[0];
+        let valence = result.final_perspective.valence - result.initial_perspective.valence;
+        assert!(valence.abs() > 0.05);
+        assert!((valence - change.shift()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_visits_gather_evidence() {
+        let mut traveller = PerspectivalSelf::new(2, 2, 0.0);
+        traveller.emerge_at(WorldPoint::new(vec![0.0, 0.0], "home"), believer());
+
+        let market = WorldPoint::new(vec![2.0, 0.0], "market")
+            .with_evidence(Evidence::new("it will rain", 0.9, 0.3))
+            .with_evidence(Evidence::new("the river is safe", 0.2, 0.6));
+        traveller.visit(market).unwrap();
+        assert_eq!(traveller.history().last().unwrap().0.name, "market");
+
+        let result = traveller.return_home().unwrap();
+        let changes = result.final_perspective.belief_changes(&result.initial_perspective);
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|c| c.shift().abs() > 0.05));
+        assert!(result.describe().contains("it will rain"));
+    }
+}
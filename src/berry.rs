@@ -0,0 +1,325 @@
+//! Berry Phase - holonomy of a τ-qubit dragged around a parameter loop.
+//!
+//! A τ-qubit from the FHP substrate is read as a two-level system in a
+//! field H = -(ω/2) n̂·σ, where ω is the qubit's frequency and n̂ a
+//! direction on the Bloch sphere. Turn n̂ slowly around a closed loop and
+//! the qubit follows the aligned eigenstate |n̂⟩. It comes home with two
+//! phases:
+//!
+//! - dynamic: ωT/2, which depends on how long the trip took
+//! - geometric: γ = -Ω/2, which depends only on the solid angle Ω the loop
+//!   encloses
+//!
+//! The geometric part is the holonomy of the Berry connection A = i⟨n|dn⟩.
+//! Its curvature is -½ per steradian.
+//!
+//! Legs of the loop are great-circle arcs. A field turning uniformly about a
+//! fixed axis has an exact rotating-frame solution, so evolution is exact
+//! and only departures from adiabaticity remain.
+
+use crate::bundle::{HolonomyResult, Perspective};
+use crate::fhp::TauQubit;
+use crate::types::TopologyError;
+use nalgebra::{Complex, ComplexField, DMatrix, Matrix2, Vector2, Vector3};
+use std::f64::consts::PI;
+
+type Spinor = Vector2<Complex<f64>>;
+
+/// Berry curvature of the aligned state, per steradian
+pub const BERRY_CURVATURE: f64 = -0.5;
+
+/// v·σ for a real 3-vector
+fn pauli_dot(v: &Vector3<f64>) -> Matrix2<Complex<f64>> {
+    Matrix2::new(
+        Complex::new(v.z, 0.0), Complex::new(v.x, -v.y),
+        Complex::new(v.x, v.y), Complex::new(-v.z, 0.0),
+    )
+}
+
+/// exp(-i θ/2 · a·σ) for a unit axis a
+fn spin_rotation(axis: &Vector3<f64>, theta: f64) -> Matrix2<Complex<f64>> {
+    let (c, s) = ((theta / 2.0).cos(), (theta / 2.0).sin());
+    Matrix2::identity() * Complex::new(c, 0.0) - pauli_dot(axis) * Complex::new(0.0, s)
+}
+
+/// Polar and azimuthal angles of a direction
+fn spherical(n: &Vector3<f64>) -> (f64, f64) {
+    let n = n.normalize();
+    (n.z.clamp(-1.0, 1.0).acos(), n.y.atan2(n.x))
+}
+
+/// Wrap an angle to (-π, π]
+fn wrap(angle: f64) -> f64 {
+    let wrapped = angle.rem_euclid(2.0 * PI);
+    if wrapped > PI { wrapped - 2.0 * PI } else { wrapped }
+}
+
+/// The state aligned with `n`, in the north-pole gauge (cos θ/2, e^{iφ} sin θ/2)
+pub fn aligned_state(n: &Vector3<f64>) -> Spinor {
+    let (theta, phi) = spherical(n);
+    Vector2::new(
+        Complex::new((theta / 2.0).cos(), 0.0),
+        Complex::new(phi.cos(), phi.sin()) * (theta / 2.0).sin(),
+    )
+}
+
+/// Berry connection of the aligned state along a step `dn` at `n`
+///
+/// North-pole gauge: A = -sin²(θ/2) dφ. Singular at the south pole.
+pub fn berry_connection(n: &Vector3<f64>, dn: &Vector3<f64>) -> f64 {
+    let (theta, phi) = spherical(n);
+    let (_, phi_next) = spherical(&(n + dn));
+    -(theta / 2.0).sin().powi(2) * wrap(phi_next - phi)
+}
+
+/// A closed loop of field directions joined by great-circle arcs
+#[derive(Debug, Clone)]
+pub struct ParameterLoop {
+    /// Unit directions, first not repeated
+    pub directions: Vec<Vector3<f64>>,
+}
+
+impl ParameterLoop {
+    /// A loop through the given directions (normalized)
+    ///
+    /// Zero directions have no point on the sphere, and antipodal neighbours
+    /// have no unique great circle between them, so both are rejected.
+    pub fn new(directions: Vec<Vector3<f64>>) -> Result<Self, TopologyError> {
+        if let Some(k) = directions.iter().position(|d| !(d.norm() > f64::EPSILON && d.norm().is_finite())) {
+            return Err(TopologyError::invalid_structure(format!("loop direction {} is zero or not finite", k)));
+        }
+        let parameter_loop = Self { directions: directions.into_iter().map(|d| d.normalize()).collect() };
+        let n = parameter_loop.directions.len();
+        if let Some(k) = parameter_loop.legs().position(|(a, b)| a.dot(b) < -1.0 + 1e-12) {
+            return Err(TopologyError::invalid_structure(format!(
+                "loop directions {} and {} are antipodal",
+                k,
+                (k + 1) % n
+            )));
+        }
+        Ok(parameter_loop)
+    }
+
+    /// A circle of constant polar angle, sampled at `points` directions
+    pub fn latitude(polar_angle: f64, points: usize) -> Self {
+        let directions = (0..points.max(3))
+            .map(|k| {
+                let phi = 2.0 * PI * k as f64 / points.max(3) as f64;
+                Vector3::new(polar_angle.sin() * phi.cos(), polar_angle.sin() * phi.sin(), polar_angle.cos())
+            })
+            .collect();
+        Self { directions }
+    }
+
+    /// The loop traversed backwards from the same start
+    pub fn reversed(&self) -> Self {
+        let mut directions = self.directions.clone();
+        if directions.len() < 2 {
+            return Self { directions };
+        }
+        directions[1..].reverse();
+        Self { directions }
+    }
+
+    /// Consecutive (from, to) pairs, closing the loop
+    fn legs(&self) -> impl Iterator<Item = (&Vector3<f64>, &Vector3<f64>)> {
+        let n = self.directions.len();
+        (0..n).map(move |k| (&self.directions[k], &self.directions[(k + 1) % n]))
+    }
+
+    /// Total arc length on the unit sphere
+    pub fn arc_length(&self) -> f64 {
+        self.legs().map(|(a, b)| a.dot(b).clamp(-1.0, 1.0).acos()).sum()
+    }
+
+    /// Signed solid angle enclosed (counter-clockwise seen from outside is positive)
+    pub fn solid_angle(&self) -> f64 {
+        let Some(origin) = self.directions.first() else { return 0.0 };
+        self.directions.windows(2).skip(1)
+            .map(|w| {
+                let (b, c) = (&w[0], &w[1]);
+                let numerator = origin.dot(&b.cross(c));
+                let denominator = 1.0 + origin.dot(b) + b.dot(c) + c.dot(origin);
+                2.0 * numerator.atan2(denominator)
+            })
+            .sum()
+    }
+
+    /// Gauge-invariant discrete Berry phase: -arg Π ⟨n_k | n_{k+1}⟩
+    pub fn pancharatnam_phase(&self) -> f64 {
+        let product = self.legs().fold(Complex::new(1.0, 0.0), |acc, (a, b)| {
+            acc * aligned_state(a).dotc(&aligned_state(b))
+        });
+        -product.argument()
+    }
+}
+
+/// Result of driving a τ-qubit around a parameter loop
+#[derive(Debug, Clone)]
+pub struct BerryPhase {
+    /// Geometric phase, wrapped to (-π, π]
+    pub geometric_phase: f64,
+    /// Dynamic phase ωT/2 (unwrapped)
+    pub dynamic_phase: f64,
+    /// Solid angle enclosed by the loop
+    pub solid_angle: f64,
+    /// |⟨n₀|ψ(T)⟩|²: 1 when the evolution was adiabatic
+    pub fidelity: f64,
+    /// The geometric phase as a U(1) holonomy, realified to a 2D rotation
+    pub holonomy: HolonomyResult,
+}
+
+impl BerryPhase {
+    /// Geometric phase predicted by the adiabatic theorem: -Ω/2
+    pub fn expected_phase(&self) -> f64 {
+        wrap(BERRY_CURVATURE * self.solid_angle)
+    }
+}
+
+impl TauQubit {
+    /// Drive the qubit around a field loop in time `duration`
+    ///
+    /// The qubit is prepared aligned with the first direction and the
+    /// field turns at constant angular speed. As with `evolve`, the total
+    /// phase picked up advances `phase` and is recorded in the phase memory.
+    pub fn adiabatic_loop(&mut self, parameter_loop: &ParameterLoop, duration: f64) -> BerryPhase {
+        let start = parameter_loop.directions.first().copied().unwrap_or_else(Vector3::z);
+        let initial = aligned_state(&start);
+        let arc = parameter_loop.arc_length();
+
+        let mut psi = initial;
+        for (from, to) in parameter_loop.legs() {
+            let angle = from.dot(to).clamp(-1.0, 1.0).acos();
+            if angle < 1e-15 || arc <= 0.0 {
+                continue;
+            }
+            let tau = duration * angle / arc;
+            let axis = from.cross(to).normalize();
+
+            // Rotating frame: constant H_eff = -(1/2)(ω n₀ + w a)·σ
+            let b = from * self.omega + axis * (angle / tau);
+            let rotating_frame = spin_rotation(&(-b.normalize()), b.norm() * tau);
+            psi = spin_rotation(&axis, angle) * rotating_frame * psi;
+        }
+
+        let overlap = initial.dotc(&psi);
+        let dynamic_phase = self.omega * duration / 2.0;
+        let geometric_phase = wrap(overlap.argument() - dynamic_phase);
+
+        self.phase = (self.phase + overlap.argument()).rem_euclid(2.0 * PI);
+        self.phase_memory.push(self.phase);
+
+        let rotation = DMatrix::from_row_slice(2, 2, &[
+            geometric_phase.cos(), -geometric_phase.sin(),
+            geometric_phase.sin(), geometric_phase.cos(),
+        ]);
+        BerryPhase {
+            geometric_phase,
+            dynamic_phase,
+            solid_angle: parameter_loop.solid_angle(),
+            fidelity: overlap.norm_sqr(),
+            holonomy: HolonomyResult::from_transport(Perspective::new(2).with_state(vec![1.0, 0.0]), rotation)
+                .expect("a 2×2 rotation acts on a two-state perspective"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fhp::TauK;
+
+    /// x → y → z: one octant, solid angle π/2
+    fn octant() -> ParameterLoop {
+        ParameterLoop::new(vec![Vector3::x(), Vector3::y(), Vector3::z()]).unwrap()
+    }
+
+    fn slow_qubit() -> TauQubit {
+        TauQubit::new(TauK::critical()).with_frequency(1.0e4)
+    }
+
+    #[test]
+    fn test_octant_berry_phase() {
+        let loop_ = octant();
+        assert!((loop_.solid_angle() - PI / 2.0).abs() < 1e-12);
+        assert!((loop_.pancharatnam_phase() + PI / 4.0).abs() < 1e-12);
+
+        let result = slow_qubit().adiabatic_loop(&loop_, 10.0);
+        assert!(result.fidelity > 1.0 - 1e-6);
+        assert!((result.geometric_phase - result.expected_phase()).abs() < 1e-3);
+        assert!((result.holonomy.rotation_angle - result.geometric_phase).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_reversed_loop_flips_phase() {
+        let forward = slow_qubit().adiabatic_loop(&octant(), 10.0);
+        let backward = slow_qubit().adiabatic_loop(&octant().reversed(), 10.0);
+        assert!((forward.geometric_phase + backward.geometric_phase).abs() < 2e-3);
+        assert!(!forward.holonomy.is_trivial);
+
+        assert!(ParameterLoop::new(vec![]).unwrap().reversed().directions.is_empty());
+        let point = ParameterLoop::new(vec![Vector3::z()]).unwrap();
+        assert_eq!(point.reversed().directions, point.directions);
+    }
+
+    #[test]
+    fn test_phase_is_independent_of_duration() {
+        // The dynamic phase grows with time; the geometric one does not
+        let loop_ = ParameterLoop::latitude(PI / 3.0, 24);
+        let short = slow_qubit().adiabatic_loop(&loop_, 5.0);
+        let long = slow_qubit().adiabatic_loop(&loop_, 20.0);
+        assert!((long.dynamic_phase - 4.0 * short.dynamic_phase).abs() < 1e-9);
+        assert!((short.geometric_phase - long.geometric_phase).abs() < 2e-3);
+        assert!((long.geometric_phase - loop_.pancharatnam_phase()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_connection_integrates_to_phase() {
+        // ∮ A around a latitude circle = -2π sin²(θ/2) = -Ω/2
+        let theta = 1.1;
+        let n = 2000;
+        let loop_ = ParameterLoop::latitude(theta, n);
+        let integral: f64 = loop_.legs().map(|(a, b)| berry_connection(a, &(b - a))).sum();
+        let cap = 2.0 * PI * (1.0 - theta.cos());
+        assert!((integral - BERRY_CURVATURE * cap).abs() < 1e-9);
+        assert!((loop_.solid_angle() - cap).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_fast_driving_breaks_adiabaticity() {
+        let mut qubit = TauQubit::new(TauK::critical()).with_frequency(1.0);
+        let result = qubit.adiabatic_loop(&octant(), 1.0);
+        assert!(result.fidelity < 0.99);
+
+        // The default τ-qubit frequency is adiabatic even for quick loops
+        let mut fast = TauQubit::new(TauK::critical());
+        let before = fast.phase;
+        let result = fast.adiabatic_loop(&octant(), 1e-4);
+        assert!(result.fidelity > 1.0 - 1e-6);
+        let total = wrap(result.dynamic_phase + result.geometric_phase);
+        assert!((wrap(fast.phase - before) - total).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rejects_zero_directions() {
+        let result = ParameterLoop::new(vec![Vector3::x(), Vector3::zeros(), Vector3::z()]);
+        assert!(matches!(result, Err(TopologyError::InvalidStructure { .. })));
+        assert!(ParameterLoop::new(vec![Vector3::new(f64::NAN, 0.0, 1.0)]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_antipodal_legs() {
+        let result = ParameterLoop::new(vec![Vector3::z(), -Vector3::z(), Vector3::x()]);
+        assert!(matches!(result, Err(TopologyError::InvalidStructure { .. })));
+
+        // The closing leg counts too
+        assert!(ParameterLoop::new(vec![Vector3::x(), Vector3::y(), -Vector3::x()]).is_err());
+
+        // Splitting the leg through an orthogonal midpoint gives a finite phase
+        let split = ParameterLoop::new(vec![Vector3::x(), Vector3::z(), Vector3::y(), -Vector3::z()]).unwrap();
+        assert!((split.solid_angle().abs() - PI).abs() < 1e-12);
+        let result = slow_qubit().adiabatic_loop(&split, 10.0);
+        assert!(result.geometric_phase.is_finite());
+        assert!((result.geometric_phase - result.expected_phase()).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,236 @@
+//! Symmetry Breaking - kernel strain as a descent through subgroups.
+//!
+//! A strained kernel keeps only part of its symmetry. A `StrainModel` is a
+//! chain of subgroups G = H₀ ⊃ H₁ ⊃ … ⊃ 1, and the strain level picks a
+//! link of the chain. The strained space is the orbit of one seed vertex,
+//! face and edge under what remains:
+//!
+//! - A₅: 12 pitches, 20 cells, 30 morphs
+//! - D₅ (the axis through the seed vertex): 2, 10, 10
+//! - Z₅ (the seed vertex itself): 1, 5, 5
+//! - 1: 1, 1, 1
+//!
+//! Pitch-class sets are sets of vertex indices. The kernel permutes them,
+//! so they have orbits and stabilizers too.
+
+use crate::group::Subgroup;
+use crate::kernel::{GenerativeKernel, GroupElement, TransformationSpace};
+use crate::types::*;
+use std::collections::BTreeSet;
+
+/// A chain of nested subgroups indexed by strain
+#[derive(Debug, Clone)]
+pub struct StrainModel {
+    /// Subgroups from the whole group (no strain) down (full strain)
+    pub chain: Vec<Subgroup>,
+}
+
+impl StrainModel {
+    /// A model from a descending chain; each link must contain the next
+    pub fn new(chain: Vec<Subgroup>) -> Result<Self, TopologyError> {
+        if chain.is_empty() {
+            return Err(TopologyError::group_structure("strain model has an empty subgroup chain"));
+        }
+        for (i, pair) in chain.windows(2).enumerate() {
+            if !pair[1].is_subgroup_of(&pair[0]) {
+                return Err(TopologyError::group_structure(format!(
+                    "strain model link {} ({}) is not inside link {} ({})",
+                    i + 1,
+                    pair[1].name,
+                    i,
+                    pair[0].name
+                )));
+            }
+        }
+        Ok(Self { chain })
+    }
+
+    /// The chain fixing ever more of vertex 0: G ⊃ axis ⊃ vertex ⊃ 1
+    pub fn stabilizer_chain(kernel: &GenerativeKernel) -> Result<Self, TopologyError> {
+        let group = kernel.group_structure()?;
+        let vertex = kernel.geometry.vertices[0];
+        let candidates = [
+            group.closure(&(0..group.order()).collect::<Vec<_>>()),
+            group.setwise_stabilizer(&[vertex, -vertex]),
+            group.stabilizer(&vertex),
+            group.closure(&[]),
+        ];
+
+        let mut chain: Vec<Subgroup> = Vec::new();
+        for subgroup in candidates {
+            if chain.last() != Some(&subgroup) {
+                chain.push(subgroup);
+            }
+        }
+        Self::new(chain)
+    }
+
+    /// Number of links
+    pub fn len(&self) -> usize {
+        self.chain.len()
+    }
+
+    /// Check if the chain is empty (never, once constructed)
+    pub fn is_empty(&self) -> bool {
+        self.chain.is_empty()
+    }
+
+    /// Link index for a strain in [0, 1], in equal bands
+    pub fn level(&self, strain: f64) -> usize {
+        let bands = self.chain.len();
+        ((strain.clamp(0.0, 1.0) * bands as f64).floor() as usize).min(bands - 1)
+    }
+
+    /// The residual symmetry at a strain
+    pub fn subgroup_at(&self, strain: f64) -> &Subgroup {
+        &self.chain[self.level(strain)]
+    }
+}
+
+impl GenerativeKernel {
+    /// How an element permutes the vertices, if it preserves them
+    pub fn vertex_permutation(&self, element: &GroupElement) -> Option<Vec<usize>> {
+        let vertices = &self.geometry.vertices;
+        vertices.iter()
+            .map(|v| {
+                let image = element.apply(v);
+                vertices.iter().position(|w| (w - image).norm() < 1e-9)
+            })
+            .collect()
+    }
+
+    /// All images of a pitch-class set (vertex indices) under the kernel
+    pub fn pitch_set_orbit(&self, set: &[usize]) -> Result<Vec<Vec<usize>>, TopologyError> {
+        self.check_pitch_set(set)?;
+        let orbit: BTreeSet<Vec<usize>> = self.elements.iter()
+            .filter_map(|element| self.vertex_permutation(element))
+            .map(|permutation| permute_set(&permutation, set))
+            .collect();
+        Ok(orbit.into_iter().collect())
+    }
+
+    /// Elements mapping a pitch-class set onto itself
+    pub fn pitch_set_stabilizer(&self, set: &[usize]) -> Result<Subgroup, TopologyError> {
+        self.check_pitch_set(set)?;
+        let group = self.group_structure()?;
+        let target = permute_set(&(0..self.geometry.vertices.len()).collect::<Vec<_>>(), set);
+        let members: Vec<usize> = (0..group.order())
+            .filter(|&i| {
+                self.vertex_permutation(&group.elements[i])
+                    .is_some_and(|permutation| permute_set(&permutation, set) == target)
+            })
+            .collect();
+        Ok(group.closure(&members))
+    }
+
+    /// Reject vertex indices the geometry does not have
+    fn check_pitch_set(&self, set: &[usize]) -> Result<(), TopologyError> {
+        let count = self.geometry.vertices.len();
+        match set.iter().find(|&&v| v >= count) {
+            Some(v) => Err(TopologyError::not_found(format!("vertex {} of a {}-vertex geometry", v, count))),
+            None => Ok(()),
+        }
+    }
+
+    /// The space generated under the symmetry left at the current strain
+    ///
+    /// Keeps the orbits of vertex 0, face 0 and edge 0 under the residual
+    /// subgroup; `symmetry_order` becomes the subgroup's order.
+    pub fn strained_space(&self, model: &StrainModel) -> Result<TransformationSpace, TopologyError> {
+        let group = self.group_structure()?;
+        let residual = model.subgroup_at(self.strain);
+        let permutations: Vec<Vec<usize>> = residual.members.iter()
+            .filter_map(|&m| self.vertex_permutation(&group.elements[m]))
+            .collect();
+
+        let orbit_of = |cell: &[usize]| -> BTreeSet<Vec<usize>> {
+            permutations.iter().map(|p| permute_set(p, cell)).collect()
+        };
+        let vertices = orbit_of(&[0]);
+        let faces = self.geometry.faces.first().map(|f| orbit_of(f)).unwrap_or_default();
+        let edges = self.geometry.edges.first().map(|e| orbit_of(e)).unwrap_or_default();
+
+        let mut space = self.generate_space();
+        space.pitch_classes.retain(|p| vertices.contains(&vec![p.index]));
+        space.rhythmic_cells.retain(|r| faces.contains(&sorted(&r.vertices)));
+        space.timbral_morphs.retain(|t| edges.contains(&sorted(&[t.source, t.target])));
+        space.symmetry_order = residual.order();
+        Ok(space)
+    }
+}
+
+/// Image of an index set under a permutation, sorted
+fn permute_set(permutation: &[usize], set: &[usize]) -> Vec<usize> {
+    sorted(&set.iter().map(|&i| permutation[i]).collect::<Vec<_>>())
+}
+
+fn sorted(set: &[usize]) -> Vec<usize> {
+    let mut set = set.to_vec();
+    set.sort_unstable();
+    set
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strain_descends_the_chain() {
+        let mut kernel = GenerativeKernel::icosahedral();
+        let model = StrainModel::stabilizer_chain(&kernel).unwrap();
+        let names: Vec<&str> = model.chain.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["A5", "D5", "Z5", "1"]);
+
+        let mut sizes = Vec::new();
+        for strain in [0.0, 0.3, 0.6, 1.0] {
+            kernel.apply_strain(strain);
+            let space = kernel.strained_space(&model).unwrap();
+            sizes.push((space.pitch_classes.len(), space.rhythmic_cells.len(), space.timbral_morphs.len(), space.symmetry_order));
+        }
+        assert_eq!(sizes, vec![(12, 20, 30, 60), (2, 10, 10, 10), (1, 5, 5, 5), (1, 1, 1, 1)]);
+    }
+
+    #[test]
+    fn test_pitch_set_orbits() {
+        let kernel = GenerativeKernel::icosahedral();
+
+        // Antipodal pair (vertices 0 and 3): 6 axes, stabilized by D5
+        assert_eq!(kernel.pitch_set_orbit(&[0, 3]).unwrap().len(), 6);
+        assert_eq!(kernel.pitch_set_stabilizer(&[0, 3]).unwrap().name, "D5");
+
+        // A face's trichord: 20 images, each fixed by a 3-fold rotation
+        let face = kernel.geometry.faces[0];
+        assert_eq!(kernel.pitch_set_orbit(&face).unwrap().len(), 20);
+        assert_eq!(kernel.pitch_set_stabilizer(&face).unwrap().name, "Z3");
+
+        // The whole set is invariant
+        let all: Vec<usize> = (0..12).collect();
+        assert_eq!(kernel.pitch_set_orbit(&all).unwrap(), vec![all.clone()]);
+        assert_eq!(kernel.pitch_set_stabilizer(&all).unwrap().order(), 60);
+
+        // There is no vertex 12
+        assert!(matches!(kernel.pitch_set_orbit(&[12]), Err(TopologyError::NotFound { .. })));
+        assert!(kernel.pitch_set_stabilizer(&[0, 12]).is_err());
+    }
+
+    #[test]
+    fn test_chains_must_nest() {
+        let group = GenerativeKernel::icosahedral().group_structure().unwrap();
+        let lattice = group.subgroup_lattice();
+        assert!(StrainModel::new(lattice.longest_chain()).is_ok());
+
+        let mut upside_down = lattice.longest_chain();
+        upside_down.reverse();
+        assert!(matches!(StrainModel::new(upside_down), Err(TopologyError::GroupStructure { .. })));
+        assert!(matches!(StrainModel::new(Vec::new()), Err(TopologyError::GroupStructure { .. })));
+    }
+
+    #[test]
+    fn test_smaller_kernels_break_too() {
+        let kernel = GenerativeKernel::tetrahedral();
+        let model = StrainModel::stabilizer_chain(&kernel).unwrap();
+        let names: Vec<&str> = model.chain.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["A4", "Z3", "1"]);
+        assert_eq!(model.level(0.5), 1);
+    }
+}
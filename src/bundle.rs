@@ -4,6 +4,7 @@
 //! Changing your mind is parallel transport.
 //! "You can never go home again" is a geometric fact (holonomy).
 
+use crate::belief::{Evidence, Proposition};
 use crate::types::*;
 use nalgebra::{DMatrix, DVector};
 use std::f64::consts::PI;
@@ -13,6 +14,8 @@ use std::f64::consts::PI;
 pub struct WorldPoint {
     pub coordinates: Coordinate,
     pub name: String,
+    /// Evidence available to anyone who visits
+    pub evidence: Vec<Evidence>,
 }
 
 impl WorldPoint {
@@ -20,8 +23,15 @@ impl WorldPoint {
         Self {
             coordinates: Coordinate::new(coords),
             name: name.into(),
+            evidence: Vec::new(),
         }
     }
+
+    /// Place evidence at this point
+    pub fn with_evidence(mut self, evidence: Evidence) -> Self {
+        self.evidence.push(evidence);
+        self
+    }
 }
 
 /// A point in the fiber (subjective experience at a world point)
@@ -31,10 +41,12 @@ pub struct Perspective {
     pub state: DVector<f64>,
     /// Beliefs about the world point
     pub beliefs: Vec<String>,
-    /// Emotional valence
+    /// Emotional valence in [-1, 1], moved by shifts in desirable credences
     pub valence: f64,
     /// Confidence level
     pub confidence: f64,
+    /// Structured beliefs with credences
+    pub propositions: Vec<Proposition>,
 }
 
 impl Perspective {
@@ -44,6 +56,7 @@ impl Perspective {
             beliefs: Vec::new(),
             valence: 0.0,
             confidence: 1.0,
+            propositions: Vec::new(),
         }
     }
 
@@ -78,48 +91,412 @@ impl Perspective {
     }
 }
 
+/// A matrix Lie algebra acting on the fiber
+///
+/// Connection components take values in the span of the generators.
+#[derive(Debug, Clone)]
+pub struct LieAlgebra {
+    /// Name, e.g. "so(3)"
+    pub name: String,
+    /// Dimension of the fiber the generators act on
+    pub fiber_dimension: usize,
+    /// Basis of generators (fiber_dimension × fiber_dimension)
+    pub generators: Vec<DMatrix<f64>>,
+}
+
+impl LieAlgebra {
+    /// so(n): rotations of n real belief components
+    ///
+    /// The generator for i < j turns e_i toward e_j.
+    pub fn so(n: usize) -> Self {
+        let mut generators = Vec::new();
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let mut g = DMatrix::zeros(n, n);
+                g[(j, i)] = 1.0;
+                g[(i, j)] = -1.0;
+                generators.push(g);
+            }
+        }
+        Self { name: format!("so({})", n), fiber_dimension: n, generators }
+    }
+
+    /// u(n) acting on ℂⁿ, realified as ℝ²ⁿ = (Re, Im)
+    pub fn u(n: usize) -> Self {
+        let realify = |re: &DMatrix<f64>, im: &DMatrix<f64>| {
+            let mut m = DMatrix::zeros(2 * n, 2 * n);
+            m.view_mut((0, 0), (n, n)).copy_from(re);
+            m.view_mut((0, n), (n, n)).copy_from(&-im);
+            m.view_mut((n, 0), (n, n)).copy_from(im);
+            m.view_mut((n, n), (n, n)).copy_from(re);
+            m
+        };
+        let zero = DMatrix::zeros(n, n);
+
+        let mut generators = Vec::new();
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let mut antisymmetric = DMatrix::zeros(n, n);
+                antisymmetric[(j, i)] = 1.0;
+                antisymmetric[(i, j)] = -1.0;
+                generators.push(realify(&antisymmetric, &zero));
+
+                let mut symmetric = DMatrix::zeros(n, n);
+                symmetric[(j, i)] = 1.0;
+                symmetric[(i, j)] = 1.0;
+                generators.push(realify(&zero, &symmetric));
+            }
+            let mut diagonal = DMatrix::zeros(n, n);
+            diagonal[(i, i)] = 1.0;
+            generators.push(realify(&zero, &diagonal));
+        }
+        Self { name: format!("u({})", n), fiber_dimension: 2 * n, generators }
+    }
+
+    /// User-supplied generators, checked to be square and closed under the bracket
+    pub fn custom(name: impl Into<String>, generators: Vec<DMatrix<f64>>) -> Result<Self, TopologyError> {
+        let fiber_dimension = generators.first().map_or(0, |g| g.nrows());
+        for g in &generators {
+            if g.nrows() != fiber_dimension || g.ncols() != fiber_dimension {
+                return Err(TopologyError::DimensionMismatch { expected: fiber_dimension, found: g.ncols() });
+            }
+        }
+
+        let algebra = Self { name: name.into(), fiber_dimension, generators };
+        for (i, a) in algebra.generators.iter().enumerate() {
+            for b in &algebra.generators[i + 1..] {
+                let (_, residual) = algebra.coordinates(&commutator(a, b));
+                if residual > 1e-8 {
+                    return Err(TopologyError::invalid_structure(format!(
+                        "generators of {} are not closed under the bracket",
+                        algebra.name
+                    )));
+                }
+            }
+        }
+        Ok(algebra)
+    }
+
+    /// Number of generators
+    pub fn dimension(&self) -> usize {
+        self.generators.len()
+    }
+
+    /// The element Σ cₐ Tₐ
+    pub fn element(&self, coefficients: &[f64]) -> DMatrix<f64> {
+        let mut m = DMatrix::zeros(self.fiber_dimension, self.fiber_dimension);
+        for (g, &c) in self.generators.iter().zip(coefficients) {
+            m += g * c;
+        }
+        m
+    }
+
+    /// Least-squares coordinates of a matrix, with the residual norm
+    pub fn coordinates(&self, m: &DMatrix<f64>) -> (Vec<f64>, f64) {
+        if self.generators.is_empty() {
+            return (Vec::new(), m.norm());
+        }
+        let columns: Vec<DVector<f64>> = self.generators.iter()
+            .map(|g| DVector::from_column_slice(g.as_slice()))
+            .collect();
+        let basis = DMatrix::from_columns(&columns);
+        let target = DVector::from_column_slice(m.as_slice());
+        let coefficients = basis.clone().svd(true, true)
+            .solve(&target, 1e-12)
+            .unwrap_or_else(|_| DVector::zeros(self.generators.len()));
+        let residual = (basis * &coefficients - target).norm();
+        (coefficients.as_slice().to_vec(), residual)
+    }
+}
+
+/// The Lie bracket [a, b] = ab - ba
+pub fn commutator(a: &DMatrix<f64>, b: &DMatrix<f64>) -> DMatrix<f64> {
+    a * b - b * a
+}
+
 /// Connection form - how perspectives transform along paths
+///
+/// A = Σ A_μ(x) dx^μ with each A_μ in a Lie algebra and linear in position:
+/// A_μ(x) = A_μ(0) + Σ_ν x^ν ∂_ν A_μ. Transport solves dψ/dt = -A(ẋ)ψ.
 #[derive(Debug, Clone)]
 pub struct ConnectionForm {
-    /// Connection coefficients (gauge field)
-    /// A_μ at each point defines how the fiber "twists"
-    pub gauge_field: DMatrix<f64>,
-    /// Curvature of the connection
-    pub curvature: f64,
+    /// Lie algebra the components take values in
+    pub algebra: LieAlgebra,
+    /// A_μ at the origin, one per base direction
+    pub components: Vec<DMatrix<f64>>,
+    /// Position dependence: gradients[μ][ν] = ∂_ν A_μ
+    pub gradients: Vec<Vec<DMatrix<f64>>>,
+}
+
+/// Longest straight step taken by one Magnus update
+const TRANSPORT_STEP: f64 = 0.05;
+
+/// Solve dψ = -A(x)ψ along the straight segment from `from` to `to`
+///
+/// `along(x, dx)` is the connection contracted with a displacement. Uses
+/// fourth-order Magnus steps of at most `TRANSPORT_STEP`.
+pub(crate) fn magnus_transport(
+    n: usize,
+    from: &DVector<f64>,
+    to: &DVector<f64>,
+    along: impl Fn(&DVector<f64>, &DVector<f64>) -> DMatrix<f64>,
+) -> DMatrix<f64> {
+    let delta = to - from;
+    let steps = ((delta.norm() / TRANSPORT_STEP).ceil() as usize).max(1);
+    let h = 1.0 / steps as f64;
+    let offset = 3f64.sqrt() / 6.0;
+
+    let mut u = DMatrix::identity(n, n);
+    for k in 0..steps {
+        let t = k as f64 * h;
+        let b1 = -along(&(from + &delta * (t + (0.5 - offset) * h)), &delta);
+        let b2 = -along(&(from + &delta * (t + (0.5 + offset) * h)), &delta);
+        let omega = (&b1 + &b2) * (h / 2.0) + commutator(&b2, &b1) * (3f64.sqrt() * h * h / 12.0);
+        u = omega.exp() * u;
+    }
+    u
 }
 
 impl ConnectionForm {
-    /// Flat connection (no curvature, parallel transport is trivial)
-    pub fn flat(dimension: usize) -> Self {
+    /// Zero connection with values in `algebra` over a base of the given dimension
+    pub fn new(algebra: LieAlgebra, base_dimension: usize) -> Self {
+        let n = algebra.fiber_dimension;
         Self {
-            gauge_field: DMatrix::zeros(dimension, dimension),
-            curvature: 0.0,
+            components: vec![DMatrix::zeros(n, n); base_dimension],
+            gradients: vec![vec![DMatrix::zeros(n, n); base_dimension]; base_dimension],
+            algebra,
         }
     }
 
+    /// Flat connection (no curvature, parallel transport is trivial)
+    pub fn flat(dimension: usize) -> Self {
+        Self::new(LieAlgebra::so(dimension), 0)
+    }
+
     /// Curved connection (non-trivial parallel transport)
+    ///
+    /// Uniform curvature in the (x⁰, x¹) base plane: a counter-clockwise loop
+    /// of area S turns fiber component 0 toward 1 by `curvature` × S.
     pub fn curved(dimension: usize, curvature: f64) -> Self {
-        let mut gauge_field = DMatrix::zeros(dimension, dimension);
-        // Set up a simple rotation generator
+        let mut connection = Self::new(LieAlgebra::so(dimension), 2);
         if dimension >= 2 {
-            gauge_field[(0, 1)] = curvature;
-            gauge_field[(1, 0)] = -curvature;
+            let half = curvature / 2.0;
+            connection = connection
+                .with_gradient(0, 1, &[half])
+                .with_gradient(1, 0, &[-half]);
         }
-        Self {
-            gauge_field,
-            curvature,
+        connection
+    }
+
+    /// Number of base directions with a component
+    pub fn base_dimension(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Grow the component tables to cover `base_dimension` directions
+    fn ensure_base(&mut self, base_dimension: usize) {
+        let n = self.algebra.fiber_dimension;
+        if base_dimension > self.components.len() {
+            self.components.resize(base_dimension, DMatrix::zeros(n, n));
+            self.gradients.resize(base_dimension, Vec::new());
+        }
+        for row in &mut self.gradients {
+            if row.len() < self.components.len() {
+                row.resize(self.components.len(), DMatrix::zeros(n, n));
+            }
         }
     }
 
-    /// Transport a perspective along an infinitesimal step
-    pub fn transport_step(&self, perspective: &mut Perspective, step: &DVector<f64>) {
-        // Parallel transport equation: dψ/dt = -A_μ (dx^μ/dt) ψ
-        let transport = &self.gauge_field * step;
-        let angle = transport.norm() * 0.1; // Scale factor
+    /// Set A_μ(0) from Lie-algebra coefficients
+    pub fn with_component(mut self, mu: usize, coefficients: &[f64]) -> Self {
+        self.ensure_base(mu + 1);
+        self.components[mu] = self.algebra.element(coefficients);
+        self
+    }
+
+    /// Set ∂_ν A_μ from Lie-algebra coefficients
+    pub fn with_gradient(mut self, mu: usize, nu: usize, coefficients: &[f64]) -> Self {
+        self.ensure_base(mu.max(nu) + 1);
+        self.gradients[mu][nu] = self.algebra.element(coefficients);
+        self
+    }
+
+    /// A_μ at a base point
+    pub fn component(&self, mu: usize, x: &DVector<f64>) -> DMatrix<f64> {
+        let n = self.algebra.fiber_dimension;
+        let Some(base) = self.components.get(mu) else {
+            return DMatrix::zeros(n, n);
+        };
+        let mut a = base.clone();
+        for (nu, gradient) in self.gradients[mu].iter().enumerate().take(x.len()) {
+            a += gradient * x[nu];
+        }
+        a
+    }
+
+    /// A(x) contracted with a base displacement: Σ_μ A_μ(x) dx^μ
+    pub fn along(&self, x: &DVector<f64>, dx: &DVector<f64>) -> DMatrix<f64> {
+        let n = self.algebra.fiber_dimension;
+        let mut a = DMatrix::zeros(n, n);
+        for mu in 0..self.components.len().min(dx.len()) {
+            a += self.component(mu, x) * dx[mu];
+        }
+        a
+    }
+
+    /// Transport matrix along the straight segment from `from` to `to`
+    ///
+    /// Fourth-order Magnus steps: exact for abelian connections, and
+    /// path-ordered for non-abelian ones.
+    pub fn segment_transport(&self, from: &DVector<f64>, to: &DVector<f64>) -> DMatrix<f64> {
+        magnus_transport(self.algebra.fiber_dimension, from, to, |x, dx| self.along(x, dx))
+    }
+
+    /// Path-ordered exponential along a piecewise-linear path
+    pub fn transport_matrix(&self, path: &Path) -> DMatrix<f64> {
+        let n = self.algebra.fiber_dimension;
+        path.points.windows(2).fold(DMatrix::identity(n, n), |u, w| {
+            self.segment_transport(&w[0].position, &w[1].position) * u
+        })
+    }
+
+    /// Wilson loop: trace of the transport around a loop (gauge invariant)
+    pub fn wilson_loop(&self, closed_path: &Path) -> f64 {
+        self.transport_matrix(closed_path).trace()
+    }
+
+    /// Transport a perspective along a short step starting at `at`
+    pub fn transport_step(&self, perspective: &mut Perspective, at: &DVector<f64>, step: &DVector<f64>) {
+        if perspective.state.len() == self.algebra.fiber_dimension {
+            perspective
+                .apply_transport(&self.segment_transport(at, &(at + step)))
+                .expect("the segment transport is fiber-sized");
+        }
+    }
+
+    /// ∂_ν A_μ (zero outside the stored directions)
+    pub(crate) fn gradient(&self, mu: usize, nu: usize) -> DMatrix<f64> {
+        let n = self.algebra.fiber_dimension;
+        self.gradients.get(mu)
+            .and_then(|row| row.get(nu))
+            .cloned()
+            .unwrap_or_else(|| DMatrix::zeros(n, n))
+    }
+
+    /// Curvature 2-form F = dA + A∧A at a base point
+    ///
+    /// F_μν = ∂_μ A_ν - ∂_ν A_μ + [A_μ, A_ν]
+    pub fn curvature_form(&self, x: &DVector<f64>) -> CurvatureForm {
+        let d = self.base_dimension();
+        let a: Vec<DMatrix<f64>> = (0..d).map(|mu| self.component(mu, x)).collect();
+        let components = (0..d)
+            .map(|mu| {
+                (0..d)
+                    .map(|nu| self.gradient(nu, mu) - self.gradient(mu, nu) + commutator(&a[mu], &a[nu]))
+                    .collect()
+            })
+            .collect();
+        CurvatureForm { at: x.clone(), components }
+    }
+
+    /// Check if the curvature vanishes everywhere
+    ///
+    /// F is at most quadratic in position, so it vanishes identically iff it
+    /// vanishes at 0, ±e_i and e_i + e_j.
+    pub fn is_flat(&self) -> bool {
+        let d = self.base_dimension();
+        let unit = |i: usize| {
+            let mut e = DVector::zeros(d);
+            e[i] = 1.0;
+            e
+        };
+        let mut points = vec![DVector::zeros(d)];
+        for i in 0..d {
+            points.push(unit(i));
+            points.push(-unit(i));
+            for j in (i + 1)..d {
+                points.push(unit(i) + unit(j));
+            }
+        }
+        points.iter().all(|x| self.curvature_form(x).magnitude() < 1e-10)
+    }
+}
+
+/// The curvature 2-form at a base point
+#[derive(Debug, Clone)]
+pub struct CurvatureForm {
+    /// Base point
+    pub at: DVector<f64>,
+    /// F_μν for every pair of base directions (antisymmetric)
+    pub components: Vec<Vec<DMatrix<f64>>>,
+}
+
+impl CurvatureForm {
+    /// F_μν (empty if the directions are out of range)
+    pub fn component(&self, mu: usize, nu: usize) -> DMatrix<f64> {
+        self.components.get(mu)
+            .and_then(|row| row.get(nu))
+            .cloned()
+            .unwrap_or_else(|| DMatrix::zeros(0, 0))
+    }
 
-        if perspective.state.len() >= 2 && angle.abs() > 1e-10 {
-            perspective.rotate(0, 1, angle);
+    /// Strongest rotation rate over all planes (largest singular value)
+    pub fn magnitude(&self) -> f64 {
+        self.components.iter()
+            .flatten()
+            .filter(|f| !f.is_empty())
+            .map(|f| f.singular_values().max())
+            .fold(0.0, f64::max)
+    }
+
+    /// Contract with oriented areas: Σ_{μ<ν} F_μν a_μν
+    pub fn contract(&self, areas: &[Vec<f64>]) -> DMatrix<f64> {
+        let n = self.components.first().and_then(|row| row.first()).map_or(0, |f| f.nrows());
+        let mut total = DMatrix::zeros(n, n);
+        for (mu, row) in self.components.iter().enumerate() {
+            for (nu, f) in row.iter().enumerate().skip(mu + 1) {
+                let a = areas.get(mu).and_then(|r| r.get(nu)).copied().unwrap_or(0.0);
+                total += f * a;
+            }
         }
+        total
+    }
+}
+
+/// One loop of a shrinking-loop curvature check
+#[derive(Debug, Clone)]
+pub struct LoopSample {
+    /// Side length of the square loop
+    pub side: f64,
+    /// Curvature estimated from the loop's holonomy: (I - U) / area
+    pub estimate: DMatrix<f64>,
+    /// Distance from the curvature 2-form at the center
+    pub error: f64,
+}
+
+/// Ambrose–Singer check: holonomy of shrinking loops recovers the curvature
+#[derive(Debug, Clone)]
+pub struct CurvatureDiagnostic {
+    /// F_μν at the center of the loops
+    pub expected: DMatrix<f64>,
+    /// Samples from largest to smallest loop
+    pub samples: Vec<LoopSample>,
+}
+
+impl CurvatureDiagnostic {
+    /// Errors shrink with the loops and the smallest is within `tolerance`
+    pub fn converges(&self, tolerance: f64) -> bool {
+        let shrinking = self.samples.windows(2).all(|w| w[1].error <= w[0].error + 1e-12);
+        shrinking && self.samples.last().is_some_and(|s| s.error <= tolerance)
+    }
+
+    /// Empirical order of convergence from the two smallest loops
+    pub fn order(&self) -> Option<f64> {
+        let [.., a, b] = self.samples.as_slice() else { return None };
+        if a.error <= 0.0 || b.error <= 0.0 {
+            return None;
+        }
+        Some((a.error / b.error).ln() / (a.side / b.side).ln())
     }
 }
 
@@ -139,22 +516,37 @@ pub struct FiberBundle {
 impl FiberBundle {
     /// Create a trivial bundle (product space, flat connection)
     pub fn trivial(base_dim: usize, fiber_dim: usize) -> Self {
-        Self {
-            base_dimension: base_dim,
-            fiber_dimension: fiber_dim,
-            connection: ConnectionForm::flat(fiber_dim),
-            section: None,
-        }
+        Self::with_connection(base_dim, ConnectionForm::flat(fiber_dim)).expect("a flat connection fits any base")
     }
 
     /// Create a bundle with curvature (non-trivial holonomy)
+    ///
+    /// The curvature lives in the (x⁰, x¹) plane, so a base of fewer than
+    /// two dimensions gets a flat connection.
     pub fn curved(base_dim: usize, fiber_dim: usize, curvature: f64) -> Self {
-        Self {
+        let connection = if base_dim >= 2 {
+            ConnectionForm::curved(fiber_dim, curvature)
+        } else {
+            ConnectionForm::flat(fiber_dim)
+        };
+        Self::with_connection(base_dim, connection).expect("the connection was built for this base")
+    }
+
+    /// Create a bundle from an explicit connection; the fiber is the algebra's
+    ///
+    /// A connection with fewer base directions than `base_dim` is extended
+    /// by zero; one with more is rejected.
+    pub fn with_connection(base_dim: usize, mut connection: ConnectionForm) -> Result<Self, TopologyError> {
+        if connection.base_dimension() > base_dim {
+            return Err(TopologyError::DimensionMismatch { expected: base_dim, found: connection.base_dimension() });
+        }
+        connection.ensure_base(base_dim);
+        Ok(Self {
             base_dimension: base_dim,
-            fiber_dimension: fiber_dim,
-            connection: ConnectionForm::curved(fiber_dim, curvature),
+            fiber_dimension: connection.algebra.fiber_dimension,
+            connection,
             section: None,
-        }
+        })
     }
 
     /// Set the current section (where the self "is" in the bundle)
@@ -175,80 +567,78 @@ impl FiberBundle {
     /// Parallel transport along a path
     /// "Changing your mind is a geometric operation"
     pub fn parallel_transport(&mut self, path: &Path) -> Result<Perspective, TopologyError> {
-        let (_, mut perspective) = self.section.take()
+        self.transport_section(path).map(|(perspective, _)| perspective)
+    }
+
+    /// Transport the section, also returning the transport matrix
+    fn transport_section(&mut self, path: &Path) -> Result<(Perspective, DMatrix<f64>), TopologyError> {
+        let (world, mut perspective) = self.section.take()
             .ok_or(TopologyError::FiberDisconnection)?;
 
-        // Transport along each segment
-        for window in path.points.windows(2) {
-            let step = &window[1].position - &window[0].position;
-            self.connection.transport_step(&mut perspective, &step);
+        if perspective.state.len() != self.fiber_dimension {
+            let found = perspective.state.len();
+            self.section = Some((world, perspective));
+            return Err(TopologyError::DimensionMismatch { expected: self.fiber_dimension, found });
         }
 
+        let transport = self.connection.transport_matrix(path);
+        perspective.apply_transport(&transport)?;
+
         // Update section to end of path
-        if let Some(end) = path.points.last() {
-            self.section = Some((
-                WorldPoint::new(end.position.as_slice().to_vec(), "transported"),
-                perspective.clone(),
-            ));
-        }
+        let world = match path.points.last() {
+            Some(end) => WorldPoint::new(end.position.as_slice().to_vec(), "transported"),
+            None => world,
+        };
+        self.section = Some((world, perspective.clone()));
 
-        Ok(perspective)
+        Ok((perspective, transport))
     }
 
     /// Compute holonomy around a closed loop
     /// "You can never go home again" - returning yields a different state
+    ///
+    /// The current perspective is carried around the loop; without a section
+    /// the first fiber direction is used.
     pub fn holonomy(&self, closed_path: &Path) -> Result<HolonomyResult, TopologyError> {
         if !closed_path.is_closed {
             return Err(TopologyError::FiberDisconnection);
         }
 
-        // Start with identity perspective
-        let initial = Perspective::new(self.fiber_dimension)
-            .with_state(vec![1.0, 0.0]); // Unit vector in first direction
+        let initial = match self.current_perspective() {
+            Some(p) if p.state.len() == self.fiber_dimension => p.clone(),
+            _ => {
+                let mut unit = Perspective::new(self.fiber_dimension);
+                if self.fiber_dimension > 0 {
+                    unit.state[0] = 1.0;
+                }
+                unit
+            }
+        };
 
-        let mut transported = initial.clone();
+        HolonomyResult::from_transport(initial, self.connection.transport_matrix(closed_path))
+    }
 
-        // Transport around the loop
-        for window in closed_path.points.windows(2) {
-            let step = &window[1].position - &window[0].position;
-            self.connection.transport_step(&mut transported, &step);
+    /// Wilson loop around a closed path: tr of the holonomy
+    pub fn wilson_loop(&self, closed_path: &Path) -> Result<f64, TopologyError> {
+        if !closed_path.is_closed {
+            return Err(TopologyError::FiberDisconnection);
         }
-
-        // Compute the rotation angle (holonomy)
-        let initial_vec = &initial.state;
-        let final_vec = &transported.state;
-
-        let dot = initial_vec.dot(final_vec);
-        let angle = dot.clamp(-1.0, 1.0).acos();
-
-        // Check orientation
-        let signed_angle = if self.fiber_dimension >= 2 {
-            let cross = initial_vec[0] * final_vec[1] - initial_vec[1] * final_vec[0];
-            if cross >= 0.0 { angle } else { -angle }
-        } else {
-            angle
-        };
-
-        Ok(HolonomyResult {
-            initial_perspective: initial,
-            final_perspective: transported,
-            rotation_angle: signed_angle,
-            is_trivial: signed_angle.abs() < 1e-10,
-        })
+        Ok(self.connection.wilson_loop(closed_path))
     }
 
     /// Check if the bundle is flat (zero curvature everywhere)
     pub fn is_flat(&self) -> bool {
-        self.connection.curvature.abs() < 1e-10
+        self.connection.is_flat()
     }
 
-    /// Compute the area enclosed by a path (for holonomy estimation)
-    fn enclosed_area(path: &Path) -> f64 {
+    /// Oriented area of a loop projected to the (μ, ν) plane
+    ///
+    /// Positive for counter-clockwise loops (shoelace formula).
+    pub fn oriented_area(path: &Path, mu: usize, nu: usize) -> f64 {
         if path.points.len() < 3 {
             return 0.0;
         }
 
-        // Shoelace formula for 2D
         let mut area = 0.0;
         let n = path.points.len();
 
@@ -257,13 +647,86 @@ impl FiberBundle {
             let pi = &path.points[i];
             let pj = &path.points[j];
 
-            if pi.dimension >= 2 && pj.dimension >= 2 {
-                area += pi.position[0] * pj.position[1];
-                area -= pj.position[0] * pi.position[1];
+            if pi.dimension > mu.max(nu) && pj.dimension > mu.max(nu) {
+                area += pi.position[mu] * pj.position[nu];
+                area -= pj.position[mu] * pi.position[nu];
+            }
+        }
+
+        area / 2.0
+    }
+
+    /// Compute the area enclosed by a path (for holonomy estimation)
+    pub fn enclosed_area(path: &Path) -> f64 {
+        Self::oriented_area(path, 0, 1).abs()
+    }
+
+    /// Small-loop holonomy estimate: exp(-Σ F_μν a_μν) at the loop's centroid
+    ///
+    /// Open or empty paths have no centroid and are rejected.
+    pub fn estimate_holonomy(&self, closed_path: &Path) -> Result<DMatrix<f64>, TopologyError> {
+        if !closed_path.is_closed || closed_path.points.is_empty() {
+            return Err(TopologyError::FiberDisconnection);
+        }
+
+        let d = self.connection.base_dimension();
+        let corners = &closed_path.points[..closed_path.points.len() - 1];
+        let mut centroid = DVector::zeros(d);
+        for point in corners {
+            for k in 0..d.min(point.dimension) {
+                centroid[k] += point.position[k] / corners.len() as f64;
             }
         }
 
-        (area / 2.0).abs()
+        let areas: Vec<Vec<f64>> = (0..d)
+            .map(|mu| (0..d).map(|nu| Self::oriented_area(closed_path, mu, nu)).collect())
+            .collect();
+        Ok((-self.connection.curvature_form(&centroid).contract(&areas)).exp())
+    }
+
+    /// Compare holonomy around shrinking squares in the (μ, ν) plane with F_μν
+    ///
+    /// Each square is centered on `center` and reached along a straight tail.
+    pub fn curvature_diagnostic(
+        &self,
+        center: &DVector<f64>,
+        mu: usize,
+        nu: usize,
+        sides: &[f64],
+    ) -> Result<CurvatureDiagnostic, TopologyError> {
+        let d = self.connection.base_dimension();
+        if center.len() != d || mu.max(nu) >= d || mu == nu {
+            return Err(TopologyError::DimensionMismatch { expected: d, found: center.len() });
+        }
+
+        let n = self.fiber_dimension;
+        let expected = self.connection.curvature_form(center).component(mu, nu);
+        let samples = sides.iter()
+            .map(|&side| {
+                let corner = |s: f64, t: f64| {
+                    let mut x = center.clone();
+                    x[mu] += s * side / 2.0;
+                    x[nu] += t * side / 2.0;
+                    Coordinate::new(x.as_slice().to_vec())
+                };
+                // Lasso based at the center, so the holonomy is not conjugated
+                let lasso = Path::new(vec![
+                    Coordinate::new(center.as_slice().to_vec()),
+                    corner(-1.0, -1.0),
+                    corner(1.0, -1.0),
+                    corner(1.0, 1.0),
+                    corner(-1.0, 1.0),
+                    corner(-1.0, -1.0),
+                    Coordinate::new(center.as_slice().to_vec()),
+                ]);
+                let u = self.connection.transport_matrix(&lasso);
+                let estimate = (DMatrix::identity(n, n) - u) / (side * side);
+                let error = (&estimate - &expected).norm();
+                LoopSample { side, estimate, error }
+            })
+            .collect();
+
+        Ok(CurvatureDiagnostic { expected, samples })
     }
 }
 
@@ -274,20 +737,40 @@ pub struct HolonomyResult {
     pub final_perspective: Perspective,
     pub rotation_angle: f64,
     pub is_trivial: bool,
+    /// The holonomy: transport matrix around the loop
+    pub holonomy: DMatrix<f64>,
 }
 
 impl HolonomyResult {
+    /// Build the result of carrying `initial` through a transport matrix
+    pub fn from_transport(initial: Perspective, holonomy: DMatrix<f64>) -> Result<Self, TopologyError> {
+        let mut final_perspective = initial.clone();
+        final_perspective.apply_transport(&holonomy)?;
+
+        let n = holonomy.nrows();
+        let is_trivial = (&holonomy - DMatrix::identity(n, n)).norm() < 1e-10;
+        Ok(Self {
+            rotation_angle: principal_angle(&holonomy),
+            initial_perspective: initial,
+            final_perspective,
+            is_trivial,
+            holonomy,
+        })
+    }
+
+    /// Wilson trace tr(U): unchanged by any gauge transformation
+    pub fn wilson_trace(&self) -> f64 {
+        self.holonomy.trace()
+    }
+
     /// Get the holonomy as a rotation matrix
     pub fn as_rotation_matrix(&self) -> DMatrix<f64> {
-        let c = self.rotation_angle.cos();
-        let s = self.rotation_angle.sin();
-
-        DMatrix::from_row_slice(2, 2, &[c, -s, s, c])
+        self.holonomy.clone()
     }
 
-    /// Describe the holonomy in human terms
+    /// Describe the holonomy in human terms, listing beliefs that changed
     pub fn describe(&self) -> String {
-        if self.is_trivial {
+        let mut description = if self.is_trivial {
             "Trivial holonomy: returning home leaves you unchanged.".to_string()
         } else {
             format!(
@@ -295,7 +778,37 @@ impl HolonomyResult {
                  'You can never go home again' is a geometric fact.",
                 self.rotation_angle * 180.0 / PI
             )
+        };
+
+        let changes = self.final_perspective.belief_changes(&self.initial_perspective);
+        if !changes.is_empty() {
+            description.push_str(" Beliefs changed:");
+            for change in changes {
+                description.push_str(&format!(
+                    " '{}' {:.2} → {:.2};",
+                    change.statement, change.before, change.after
+                ));
+            }
+            description.pop();
+            description.push('.');
         }
+        description
+    }
+}
+
+/// Rotation angle of a transport matrix
+///
+/// Signed (counter-clockwise in the 0-1 plane) for a 2-dimensional fiber;
+/// otherwise the largest eigenvalue phase.
+fn principal_angle(u: &DMatrix<f64>) -> f64 {
+    match u.nrows() {
+        0 => 0.0,
+        1 => 0.0f64.atan2(u[(0, 0)]),
+        2 => (u[(1, 0)] - u[(0, 1)]).atan2(u[(0, 0)] + u[(1, 1)]),
+        _ => u.complex_eigenvalues()
+            .iter()
+            .map(|lambda| lambda.im.atan2(lambda.re).abs())
+            .fold(0.0, f64::max),
     }
 }
 
@@ -306,6 +819,8 @@ pub struct PerspectivalSelf {
     history: Vec<(WorldPoint, Perspective)>,
     /// Accumulated holonomy from life experience
     total_holonomy: f64,
+    /// Transport since emerging
+    transport: DMatrix<f64>,
 }
 
 impl PerspectivalSelf {
@@ -314,6 +829,7 @@ impl PerspectivalSelf {
             bundle: FiberBundle::curved(base_dim, fiber_dim, curvature),
             history: Vec::new(),
             total_holonomy: 0.0,
+            transport: DMatrix::identity(fiber_dim, fiber_dim),
         }
     }
 
@@ -321,11 +837,14 @@ impl PerspectivalSelf {
     pub fn emerge_at(&mut self, world: WorldPoint, perspective: Perspective) {
         self.bundle.set_section(world.clone(), perspective.clone());
         self.history.push((world, perspective));
+        let n = self.bundle.fiber_dimension;
+        self.transport = DMatrix::identity(n, n);
     }
 
     /// Move through the world (parallel transport of perspective)
     pub fn journey(&mut self, path: &Path) -> Result<(), TopologyError> {
-        let new_perspective = self.bundle.parallel_transport(path)?;
+        let (new_perspective, transport) = self.bundle.transport_section(path)?;
+        self.transport = transport * &self.transport;
 
         if let Some(end) = path.points.last() {
             let world = WorldPoint::new(
@@ -338,6 +857,22 @@ impl PerspectivalSelf {
         Ok(())
     }
 
+    /// Travel straight to a world point and take in the evidence there
+    pub fn visit(&mut self, world: WorldPoint) -> Result<(), TopologyError> {
+        let current = self.bundle.current_world_point()
+            .ok_or(TopologyError::FiberDisconnection)?
+            .coordinates
+            .clone();
+        let path = Path::new(vec![current, world.coordinates.clone()]);
+        let (mut perspective, transport) = self.bundle.transport_section(&path)?;
+        self.transport = transport * &self.transport;
+
+        perspective.observe_at(&world);
+        self.bundle.set_section(world.clone(), perspective.clone());
+        self.history.push((world, perspective));
+        Ok(())
+    }
+
     /// Attempt to return home (reveals holonomy)
     pub fn return_home(&mut self) -> Result<HolonomyResult, TopologyError> {
         if self.history.len() < 2 {
@@ -354,21 +889,23 @@ impl PerspectivalSelf {
             origin.clone(),
         ]);
 
-        // Transport back
-        let returned = self.bundle.parallel_transport(&return_path)?;
+        // Transport back; the whole trip is the holonomy
+        let (_, transport) = self.bundle.transport_section(&return_path)?;
+        self.transport = transport * &self.transport;
 
-        // Compare with original perspective
-        let original = &self.history[0].1;
-        let angle = original.state.dot(&returned.state).clamp(-1.0, 1.0).acos();
+        // The final perspective is where the self really is, evidence included
+        let mut result = HolonomyResult::from_transport(self.history[0].1.clone(), self.transport.clone())?;
+        if let Some(current) = self.bundle.current_perspective() {
+            result.final_perspective = current.clone();
+        }
+        self.total_holonomy += result.rotation_angle.abs();
 
-        self.total_holonomy += angle;
+        Ok(result)
+    }
 
-        Ok(HolonomyResult {
-            initial_perspective: original.clone(),
-            final_perspective: returned,
-            rotation_angle: angle,
-            is_trivial: angle.abs() < 1e-10,
-        })
+    /// Recorded (world point, perspective) stops, oldest first
+    pub fn history(&self) -> &[(WorldPoint, Perspective)] {
+        &self.history
     }
 
     /// Get accumulated life holonomy
@@ -437,11 +974,191 @@ mod tests {
         ]);
 
         let result = bundle.holonomy(&path).unwrap();
-        // Curved bundle should have non-trivial holonomy
-        // (depends on implementation details of transport)
+        // Uniform curvature: the angle is curvature × enclosed area
+        assert!(!result.is_trivial);
+        assert!((result.rotation_angle - 0.5).abs() < 1e-9);
         println!("{}", result.describe());
     }
 
+    fn unit_square() -> Vec<Coordinate> {
+        vec![
+            Coordinate::new(vec![0.0, 0.0]),
+            Coordinate::new(vec![1.0, 0.0]),
+            Coordinate::new(vec![1.0, 1.0]),
+            Coordinate::new(vec![0.0, 1.0]),
+        ]
+    }
+
+    #[test]
+    fn test_lie_algebras() {
+        assert_eq!(LieAlgebra::so(3).dimension(), 3);
+        let u2 = LieAlgebra::u(2);
+        assert_eq!((u2.dimension(), u2.fiber_dimension), (4, 4));
+        assert!(LieAlgebra::custom("su(2)", LieAlgebra::so(3).generators).is_ok());
+
+        // Two rotations of so(3) bracket to the third: not closed on their own
+        let partial = LieAlgebra::so(3).generators[..2].to_vec();
+        assert!(matches!(
+            LieAlgebra::custom("partial", partial),
+            Err(TopologyError::InvalidStructure { .. })
+        ));
+    }
+
+    #[test]
+    fn test_connection_must_fit_the_base() {
+        let planar = ConnectionForm::new(LieAlgebra::so(2), 2).with_component(1, &[0.5]);
+        assert!(matches!(
+            FiberBundle::with_connection(1, planar.clone()),
+            Err(TopologyError::DimensionMismatch { expected: 1, found: 2 })
+        ));
+        let bundle = FiberBundle::with_connection(3, planar).unwrap();
+        assert_eq!(bundle.connection.base_dimension(), 3);
+        assert_eq!(FiberBundle::curved(1, 2, 0.5).connection.base_dimension(), 1);
+    }
+
+    #[test]
+    fn test_holonomy_orientation() {
+        let bundle = FiberBundle::curved(2, 2, 0.5);
+        let mut reversed = unit_square();
+        reversed[1..].reverse();
+
+        let forward = bundle.holonomy(&Path::closed(unit_square())).unwrap();
+        let backward = bundle.holonomy(&Path::closed(reversed)).unwrap();
+        assert!((forward.rotation_angle + backward.rotation_angle).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_transport_depends_on_direction() {
+        let connection = ConnectionForm::curved(2, 1.0);
+        let start = DVector::from_vec(vec![1.0, 1.0]);
+        let mut along_x = Perspective::new(2).with_state(vec![1.0, 0.0]);
+        let mut along_y = along_x.clone();
+
+        connection.transport_step(&mut along_x, &start, &DVector::from_vec(vec![0.1, 0.0]));
+        connection.transport_step(&mut along_y, &start, &DVector::from_vec(vec![0.0, 0.1]));
+        assert!(along_x.distance(&along_y) > 1e-3);
+        assert!((along_x.state.norm() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_non_abelian_holonomy() {
+        // Constant but non-commuting components still curve the bundle
+        let connection = ConnectionForm::new(LieAlgebra::so(3), 2)
+            .with_component(0, &[1.0, 0.0, 0.0])
+            .with_component(1, &[0.0, 1.0, 0.0]);
+        let bundle = FiberBundle::with_connection(2, connection).unwrap();
+        assert_eq!(bundle.fiber_dimension, 3);
+
+        let mut reversed = unit_square();
+        reversed[1..].reverse();
+        let forward = bundle.holonomy(&Path::closed(unit_square())).unwrap();
+        let backward = bundle.holonomy(&Path::closed(reversed)).unwrap();
+
+        let u = &forward.holonomy;
+        assert!(!forward.is_trivial);
+        assert!((u.transpose() * u - DMatrix::identity(3, 3)).norm() < 1e-9);
+        assert!((u.determinant() - 1.0).abs() < 1e-9);
+        assert!((u * &backward.holonomy - DMatrix::identity(3, 3)).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_curvature_form() {
+        let x = DVector::from_vec(vec![0.7, -1.3]);
+        let curved = ConnectionForm::curved(2, 0.5);
+        assert!((curved.curvature_form(&x).magnitude() - 0.5).abs() < 1e-12);
+        assert!(!curved.is_flat());
+        assert!(ConnectionForm::flat(3).is_flat());
+
+        // A constant abelian field is pure gauge: nonzero A, zero F
+        let pure_gauge = ConnectionForm::new(LieAlgebra::so(2), 2).with_component(0, &[1.0]);
+        assert!(pure_gauge.is_flat());
+
+        // Constant non-commuting components curve through A∧A alone
+        let non_abelian = ConnectionForm::new(LieAlgebra::so(3), 2)
+            .with_component(0, &[1.0, 0.0, 0.0])
+            .with_component(1, &[0.0, 1.0, 0.0]);
+        let f = non_abelian.curvature_form(&x);
+        assert!(!non_abelian.is_flat());
+        assert!((f.component(0, 1) + f.component(1, 0)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_oriented_area() {
+        let counter_clockwise = Path::closed(unit_square());
+        let mut reversed = unit_square();
+        reversed[1..].reverse();
+        let clockwise = Path::closed(reversed);
+
+        assert!((FiberBundle::oriented_area(&counter_clockwise, 0, 1) - 1.0).abs() < 1e-12);
+        assert!((FiberBundle::oriented_area(&clockwise, 0, 1) + 1.0).abs() < 1e-12);
+        assert!((FiberBundle::enclosed_area(&clockwise) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_small_loop_estimate() {
+        // Uniform abelian curvature: the estimate is exact for any loop
+        let bundle = FiberBundle::curved(2, 2, 0.5);
+        let square = Path::closed(unit_square());
+        let exact = bundle.holonomy(&square).unwrap().holonomy;
+        let estimate = bundle.estimate_holonomy(&square).unwrap();
+        assert!((exact - estimate).norm() < 1e-9);
+        assert!(matches!(bundle.estimate_holonomy(&Path::closed(vec![])), Err(TopologyError::FiberDisconnection)));
+    }
+
+    #[test]
+    fn test_ambrose_singer_convergence() {
+        let connection = ConnectionForm::new(LieAlgebra::so(3), 2)
+            .with_component(0, &[1.0, 0.0, 0.0])
+            .with_component(1, &[0.0, 0.5, 0.0])
+            .with_gradient(0, 1, &[0.0, 0.0, 0.8])
+            .with_gradient(1, 0, &[0.3, 0.0, 0.0]);
+        let bundle = FiberBundle::with_connection(2, connection).unwrap();
+        let center = DVector::from_vec(vec![0.3, -0.2]);
+
+        let diagnostic = bundle.curvature_diagnostic(&center, 0, 1, &[0.4, 0.2, 0.1, 0.05]).unwrap();
+        assert!(diagnostic.converges(1e-2));
+        assert!(diagnostic.order().unwrap() > 1.5);
+
+        // The small-loop estimator agrees with exact transport on a tiny loop
+        let tiny: Vec<Coordinate> = unit_square().iter()
+            .map(|c| Coordinate::new(vec![0.3 + 0.01 * c.position[0], -0.2 + 0.01 * c.position[1]]))
+            .collect();
+        let tiny = Path::closed(tiny);
+        let exact = bundle.holonomy(&tiny).unwrap().holonomy;
+        assert!((exact - bundle.estimate_holonomy(&tiny).unwrap()).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_higher_fiber_dimension() {
+        let mut bundle = FiberBundle::curved(2, 4, 0.5);
+        bundle.set_section(
+            WorldPoint::new(vec![0.0, 0.0], "origin"),
+            Perspective::new(4).with_state(vec![1.0, 0.0, 0.3, 0.4]),
+        );
+
+        let result = bundle.holonomy(&Path::closed(unit_square())).unwrap();
+        assert!((result.rotation_angle - 0.5).abs() < 1e-9);
+        assert!((result.final_perspective.state[2] - 0.3).abs() < 1e-12);
+
+        bundle.set_section(WorldPoint::new(vec![0.0, 0.0], "origin"), Perspective::new(2));
+        assert!(matches!(
+            bundle.parallel_transport(&Path::new(unit_square())),
+            Err(TopologyError::DimensionMismatch { expected: 4, found: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_transport_must_fit_the_fiber() {
+        assert!(matches!(
+            HolonomyResult::from_transport(Perspective::new(3), DMatrix::identity(2, 2)),
+            Err(TopologyError::DimensionMismatch { expected: 3, found: 2 })
+        ));
+
+        let mut perspective = Perspective::new(2).with_state(vec![1.0, 0.0]);
+        assert!(perspective.apply_transport(&DMatrix::identity(3, 2)).is_err());
+        assert_eq!(perspective.state, DVector::from_vec(vec![1.0, 0.0]));
+    }
+
     #[test]
     fn test_parallel_transport() {
         let mut bundle = FiberBundle::trivial(2, 2);
@@ -0,0 +1,409 @@
+//! Centrality - which ideas carry the structure of the complex.
+//!
+//! Random walks move between simplices of the same dimension, either
+//! through a shared coface (up) or a shared face (down). Their stationary
+//! behaviour gives simplicial PageRank; the principal eigenvector of the
+//! same adjacency gives eigenvector centrality.
+//!
+//! A bottleneck idea is one whose removal most changes β₀ or β₁. Results are
+//! keyed by Idea id so they can focus attention on the manifold.
+
+use crate::homology::ChainComplex;
+use crate::simplex::ConceptComplex;
+use crate::types::AttentionTensor;
+use nalgebra::{DMatrix, DVector};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::BTreeMap;
+
+/// Default PageRank damping factor
+pub const DEFAULT_DAMPING: f64 = 0.85;
+
+/// A random walk between simplices of one dimension
+#[derive(Debug, Clone)]
+pub struct SimplicialWalk {
+    /// Simplices the walk moves between (sorted vertex ids)
+    pub simplices: Vec<Vec<usize>>,
+    /// Symmetric adjacency weights
+    pub adjacency: DMatrix<f64>,
+    /// Row-stochastic transition matrix (isolated simplices stay put)
+    pub transition: DMatrix<f64>,
+}
+
+impl SimplicialWalk {
+    /// Walk on ideas through relations, weighted by relation strength
+    pub fn on_vertices(complex: &ConceptComplex) -> Self {
+        let mut ids: Vec<usize> = complex.vertices.keys().copied().collect();
+        ids.sort_unstable();
+        let index: BTreeMap<usize, usize> = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+        let mut adjacency: DMatrix<f64> = DMatrix::zeros(ids.len(), ids.len());
+        for relation in complex.faces.iter().filter(|f| f.dimension >= 1) {
+            for (k, a) in relation.vertices.iter().enumerate() {
+                for b in &relation.vertices[k + 1..] {
+                    let (Some(&i), Some(&j)) = (index.get(a), index.get(b)) else { continue };
+                    let w = adjacency[(i, j)].max(relation.weight);
+                    adjacency[(i, j)] = w;
+                    adjacency[(j, i)] = w;
+                }
+            }
+        }
+
+        let simplices = ids.into_iter().map(|id| vec![id]).collect();
+        Self::from_adjacency(simplices, adjacency)
+    }
+
+    /// Walk on relations (edges) mixing up and down moves
+    ///
+    /// With `up_weight` = 1 the walk only crosses shared triangles, with 0
+    /// it only crosses shared ideas.
+    pub fn on_edges(complex: &ConceptComplex, up_weight: f64) -> Self {
+        let up_weight = up_weight.clamp(0.0, 1.0);
+        let chains = complex.chain_complex(2);
+
+        let edges: Vec<usize> = (0..chains.len()).filter(|&c| chains.cells[c].dimension() == 1).collect();
+        let position: BTreeMap<usize, usize> = edges.iter().enumerate().map(|(i, &c)| (c, i)).collect();
+
+        let mut adjacency: DMatrix<f64> = DMatrix::zeros(edges.len(), edges.len());
+        for (i, &e) in edges.iter().enumerate() {
+            for (j, &f) in edges.iter().enumerate().skip(i + 1) {
+                let shared = chains.cells[e].vertices.iter().any(|v| chains.cells[f].vertices.contains(v));
+                if shared {
+                    adjacency[(i, j)] += 1.0 - up_weight;
+                    adjacency[(j, i)] += 1.0 - up_weight;
+                }
+            }
+        }
+        for triangle in chains.cells.iter().filter(|c| c.dimension() == 2) {
+            for (k, a) in triangle.boundary.iter().enumerate() {
+                for b in &triangle.boundary[k + 1..] {
+                    let (i, j) = (position[a], position[b]);
+                    adjacency[(i, j)] += up_weight;
+                    adjacency[(j, i)] += up_weight;
+                }
+            }
+        }
+
+        let simplices = edges.iter().map(|&c| chains.cells[c].vertices.clone()).collect();
+        Self::from_adjacency(simplices, adjacency)
+    }
+
+    fn from_adjacency(simplices: Vec<Vec<usize>>, adjacency: DMatrix<f64>) -> Self {
+        let n = simplices.len();
+        let mut transition = DMatrix::zeros(n, n);
+        for i in 0..n {
+            let degree: f64 = adjacency.row(i).sum();
+            if degree > 0.0 {
+                for j in 0..n {
+                    transition[(i, j)] = adjacency[(i, j)] / degree;
+                }
+            } else {
+                transition[(i, i)] = 1.0;
+            }
+        }
+        Self { simplices, adjacency, transition }
+    }
+
+    /// Number of simplices in the walk
+    pub fn len(&self) -> usize {
+        self.simplices.len()
+    }
+
+    /// Check if the walk has no simplices
+    pub fn is_empty(&self) -> bool {
+        self.simplices.is_empty()
+    }
+
+    /// Position of a simplex (given by its vertex ids)
+    pub fn index_of(&self, simplex: &[usize]) -> Option<usize> {
+        let mut key = simplex.to_vec();
+        key.sort_unstable();
+        self.simplices.iter().position(|s| *s == key)
+    }
+
+    /// Probability distribution after `steps` steps from `start`
+    pub fn distribution_after(&self, start: usize, steps: usize) -> DVector<f64> {
+        let mut p = DVector::zeros(self.len());
+        if start < self.len() {
+            p[start] = 1.0;
+        }
+        let transposed = self.transition.transpose();
+        for _ in 0..steps {
+            p = &transposed * p;
+        }
+        p
+    }
+
+    /// Sample a random walk of `steps` moves, returning simplex positions
+    ///
+    /// The same seed always takes the same walk.
+    pub fn sample(&self, start: usize, steps: usize, seed: u64) -> Vec<usize> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut path = vec![start];
+        let mut current = start;
+        for _ in 0..steps {
+            if current >= self.len() {
+                break;
+            }
+            let mut threshold = rng.gen_range(0.0..1.0);
+            let mut next = current;
+            for j in 0..self.len() {
+                threshold -= self.transition[(current, j)];
+                if threshold < 0.0 && self.transition[(current, j)] > 0.0 {
+                    next = j;
+                    break;
+                }
+            }
+            current = next;
+            path.push(current);
+        }
+        path
+    }
+
+    /// Simplicial PageRank: stationary distribution with teleportation
+    pub fn pagerank(&self, damping: f64) -> DVector<f64> {
+        let n = self.len();
+        if n == 0 {
+            return DVector::zeros(0);
+        }
+        let transposed = self.transition.transpose();
+        let teleport = (1.0 - damping) / n as f64;
+        let mut rank = DVector::from_element(n, 1.0 / n as f64);
+        for _ in 0..1000 {
+            let next = (&transposed * &rank) * damping + DVector::from_element(n, teleport);
+            let change = (&next - &rank).abs().sum();
+            rank = next;
+            if change < 1e-12 {
+                break;
+            }
+        }
+        rank
+    }
+
+    /// Eigenvector centrality: principal eigenvector of the adjacency, summing to 1
+    pub fn eigenvector_centrality(&self) -> DVector<f64> {
+        let n = self.len();
+        if n == 0 {
+            return DVector::zeros(0);
+        }
+        let eigen = self.adjacency.clone().symmetric_eigen();
+        let principal = eigen.eigenvalues.imax();
+        let vector = eigen.eigenvectors.column(principal).abs();
+        let total = vector.sum();
+        if total > 0.0 {
+            vector / total
+        } else {
+            DVector::from_element(n, 1.0 / n as f64)
+        }
+    }
+}
+
+/// Centrality scores for ideas and relations
+#[derive(Debug, Clone, Default)]
+pub struct CentralityReport {
+    /// Simplicial PageRank of each idea
+    pub pagerank: BTreeMap<usize, f64>,
+    /// Eigenvector centrality of each idea
+    pub eigenvector: BTreeMap<usize, f64>,
+    /// Simplicial PageRank of each relation (edge)
+    pub edge_pagerank: BTreeMap<(usize, usize), f64>,
+    /// Eigenvector centrality of each relation (edge)
+    pub edge_eigenvector: BTreeMap<(usize, usize), f64>,
+}
+
+impl CentralityReport {
+    /// Ideas ordered by PageRank, most central first
+    pub fn ranking(&self) -> Vec<(usize, f64)> {
+        let mut ranked: Vec<(usize, f64)> = self.pagerank.iter().map(|(&id, &r)| (id, r)).collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked
+    }
+
+    /// Attention metric over the given ideas, weighted by relative PageRank
+    pub fn focus(&self, ideas: &[usize]) -> AttentionTensor {
+        let max = self.pagerank.values().copied().fold(0.0, f64::max);
+        let weights: Vec<f64> = ideas.iter()
+            .map(|id| match self.pagerank.get(id) {
+                Some(r) if max > 0.0 => r / max,
+                _ => 0.0,
+            })
+            .collect();
+        AttentionTensor::focused(ideas.len(), &weights)
+    }
+}
+
+/// How removing one idea changes the shape of the complex
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bottleneck {
+    /// The removed idea
+    pub idea_id: usize,
+    /// Change in β₀ (components)
+    pub delta_b0: isize,
+    /// Change in β₁ (holes)
+    pub delta_b1: isize,
+}
+
+impl Bottleneck {
+    /// Total change |Δβ₀| + |Δβ₁|
+    pub fn impact(&self) -> usize {
+        self.delta_b0.unsigned_abs() + self.delta_b1.unsigned_abs()
+    }
+}
+
+impl ConceptComplex {
+    /// PageRank and eigenvector centrality for ideas and relations
+    pub fn centrality(&self) -> CentralityReport {
+        let mut report = CentralityReport::default();
+
+        let vertices = SimplicialWalk::on_vertices(self);
+        for ((simplex, rank), centrality) in vertices.simplices.iter()
+            .zip(vertices.pagerank(DEFAULT_DAMPING).iter())
+            .zip(vertices.eigenvector_centrality().iter())
+        {
+            report.pagerank.insert(simplex[0], *rank);
+            report.eigenvector.insert(simplex[0], *centrality);
+        }
+
+        let edges = SimplicialWalk::on_edges(self, 0.5);
+        for ((simplex, rank), centrality) in edges.simplices.iter()
+            .zip(edges.pagerank(DEFAULT_DAMPING).iter())
+            .zip(edges.eigenvector_centrality().iter())
+        {
+            report.edge_pagerank.insert((simplex[0], simplex[1]), *rank);
+            report.edge_eigenvector.insert((simplex[0], simplex[1]), *centrality);
+        }
+        report
+    }
+
+    /// Ideas ranked by how much their removal changes β₀ and β₁
+    ///
+    /// Removing an idea deletes every simplex containing it; the remaining
+    /// faces of its relations stay.
+    pub fn bottleneck_ideas(&self) -> Vec<Bottleneck> {
+        let closure = self.chain_complex(2);
+        let low_betti = |removed: Option<usize>| {
+            let mut complex = ChainComplex::new();
+            for cell in closure.cells.iter().filter(|c| !matches!(removed, Some(id) if c.vertices.contains(&id))) {
+                complex.add_simplex(cell.vertices.clone(), 0.0);
+            }
+            let betti = complex.betti_numbers();
+            let at = |k: usize| betti.get(k).copied().unwrap_or(0) as isize;
+            (at(0), at(1))
+        };
+        let (b0, b1) = low_betti(None);
+        let pagerank = SimplicialWalk::on_vertices(self).pagerank(DEFAULT_DAMPING);
+        let mut ids: Vec<usize> = self.vertices.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut bottlenecks: Vec<(Bottleneck, f64)> = ids.iter()
+            .enumerate()
+            .map(|(i, &id)| {
+                let (r0, r1) = low_betti(Some(id));
+                (Bottleneck { idea_id: id, delta_b0: r0 - b0, delta_b1: r1 - b1 }, pagerank[i])
+            })
+            .collect();
+
+        bottlenecks.sort_by(|a, b| {
+            b.0.impact().cmp(&a.0.impact())
+                .then(b.1.total_cmp(&a.1))
+                .then(a.0.idea_id.cmp(&b.0.idea_id))
+        });
+        bottlenecks.into_iter().map(|(b, _)| b).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex::ComplexBuilder;
+
+    /// Hub 0 joined to 1..=4
+    fn star() -> ConceptComplex {
+        let mut builder = ComplexBuilder::new();
+        let hub = builder.add_idea("Hub");
+        for i in 0..4 {
+            let leaf = builder.add_idea(format!("Leaf {}", i));
+            builder.connect(hub, leaf);
+        }
+        builder.build()
+    }
+
+    /// Two filled triangles sharing idea 2
+    fn bowtie() -> ConceptComplex {
+        let mut builder = ComplexBuilder::new();
+        for i in 0..5 {
+            builder.add_idea(format!("Idea {}", i));
+        }
+        builder.add_triangle(0, 1, 2).add_triangle(2, 3, 4);
+        builder.build()
+    }
+
+    #[test]
+    fn test_hub_is_most_central() {
+        let report = star().centrality();
+        assert_eq!(report.ranking()[0].0, 0);
+        let top = report.eigenvector.iter().max_by(|a, b| a.1.total_cmp(b.1)).unwrap();
+        assert_eq!(*top.0, 0);
+    }
+
+    #[test]
+    fn test_pagerank_is_a_distribution() {
+        let report = bowtie().centrality();
+        let total: f64 = report.pagerank.values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        let total: f64 = report.edge_pagerank.values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert_eq!(report.edge_pagerank.len(), 6);
+    }
+
+    #[test]
+    fn test_up_walk_stays_in_triangle() {
+        let walk = SimplicialWalk::on_edges(&bowtie(), 1.0);
+        let start = walk.index_of(&[0, 1]).unwrap();
+        let p = walk.distribution_after(start, 5);
+        let left: f64 = [[0, 1], [0, 2], [1, 2]].iter().map(|e| p[walk.index_of(e).unwrap()]).sum();
+        assert!((left - 1.0).abs() < 1e-12);
+
+        // Down moves cross the shared idea
+        let walk = SimplicialWalk::on_edges(&bowtie(), 0.0);
+        let p = walk.distribution_after(start, 2);
+        assert!(p[walk.index_of(&[3, 4]).unwrap()].abs() < 1e-12);
+        assert!(p[walk.index_of(&[2, 3]).unwrap()] > 0.0);
+    }
+
+    #[test]
+    fn test_sampled_walk_follows_relations() {
+        let walk = SimplicialWalk::on_vertices(&star());
+        let path = walk.sample(0, 20, 7);
+        assert_eq!(path.len(), 21);
+        for pair in path.windows(2) {
+            assert!(walk.transition[(pair[0], pair[1])] > 0.0);
+        }
+        assert_eq!(walk.sample(0, 20, 7), path);
+    }
+
+    #[test]
+    fn test_bottleneck_idea() {
+        let bottlenecks = bowtie().bottleneck_ideas();
+        assert_eq!(bottlenecks[0], Bottleneck { idea_id: 2, delta_b0: 1, delta_b1: 0 });
+
+        // Every idea of a bare cycle is needed to close the loop
+        let mut builder = ComplexBuilder::new();
+        for i in 0..4 {
+            builder.add_idea(format!("Idea {}", i));
+        }
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 0)] {
+            builder.connect(a, b);
+        }
+        let square = builder.build();
+        assert!(square.bottleneck_ideas().iter().all(|b| b.delta_b1 == -1 && b.delta_b0 == 0));
+    }
+
+    #[test]
+    fn test_focus_weights_attention() {
+        let complex = star();
+        let attention = complex.centrality().focus(&[0, 1]);
+        assert!((attention.metric[(0, 0)] - 1.0).abs() < 1e-12);
+        assert!(attention.metric[(1, 1)] < attention.metric[(0, 0)]);
+    }
+}
@@ -0,0 +1,415 @@
+//! Connection Estimation - learning how the world twists belief.
+//!
+//! Instead of choosing a connection and watching perspectives move, observe
+//! (WorldPoint, Perspective) histories and recover the connection that
+//! moved them.
+//!
+//! Each consecutive pair of stops is treated as transport along a straight
+//! segment: ψ₁ ≈ U(x₀ → x₁) ψ₀. A connection that is linear in position is
+//! linear in its Lie-algebra coordinates. The Cayley midpoint rule
+//!
+//!   ψ₁ - ψ₀ ≈ -A(m)·Δx (ψ₀ + ψ₁)/2,   m = (x₀ + x₁)/2
+//!
+//! is therefore an ordinary least-squares problem. Gauss–Newton steps
+//! against exact transport then remove the discretisation error of long
+//! steps.
+
+use crate::bundle::{ConnectionForm, CurvatureForm, LieAlgebra, Perspective, WorldPoint};
+use crate::types::*;
+use nalgebra::{DMatrix, DVector};
+
+/// Default number of Gauss–Newton refinements after the linear fit
+pub const DEFAULT_REFINEMENTS: usize = 5;
+
+/// Fits a `ConnectionForm` to recorded belief trajectories
+#[derive(Debug, Clone)]
+pub struct ConnectionEstimator {
+    algebra: LieAlgebra,
+    base_dimension: usize,
+    fit_gradients: bool,
+    refinements: usize,
+    histories: usize,
+    steps: Vec<Step>,
+}
+
+/// One observed transport: (x₀, ψ₀) → (x₁, ψ₁)
+#[derive(Debug, Clone)]
+struct Step {
+    history: usize,
+    from: DVector<f64>,
+    to: DVector<f64>,
+    before: DVector<f64>,
+    after: DVector<f64>,
+}
+
+/// Misfit of one observed step under the fitted connection
+#[derive(Debug, Clone)]
+pub struct StepResidual {
+    /// Which history the step came from (in order of `add_history`)
+    pub history: usize,
+    /// Index of the step's first stop within its history
+    pub step: usize,
+    /// ‖ψ₁ - U ψ₀‖
+    pub error: f64,
+}
+
+/// A fitted connection with its diagnostics
+#[derive(Debug, Clone)]
+pub struct ConnectionFit {
+    /// The estimated connection
+    pub connection: ConnectionForm,
+    /// Per-step residuals under exact transport
+    pub residuals: Vec<StepResidual>,
+    /// Rank of the linearised design matrix
+    pub rank: usize,
+    /// Number of fitted Lie-algebra coordinates
+    pub parameters: usize,
+    points: Vec<DVector<f64>>,
+}
+
+impl ConnectionEstimator {
+    /// Estimator for a connection with values in `algebra`
+    pub fn new(algebra: LieAlgebra, base_dimension: usize) -> Self {
+        Self {
+            algebra,
+            base_dimension,
+            fit_gradients: true,
+            refinements: DEFAULT_REFINEMENTS,
+            histories: 0,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Fit only constant components (no position dependence)
+    pub fn constant_only(mut self) -> Self {
+        self.fit_gradients = false;
+        self
+    }
+
+    /// Set the number of Gauss–Newton refinements (0 keeps the linear fit)
+    pub fn with_refinements(mut self, refinements: usize) -> Self {
+        self.refinements = refinements;
+        self
+    }
+
+    /// Add one recorded history, e.g. `PerspectivalSelf::history()`
+    pub fn add_history(&mut self, history: &[(WorldPoint, Perspective)]) -> Result<&mut Self, TopologyError> {
+        let n = self.algebra.fiber_dimension;
+        for (world, perspective) in history {
+            if world.coordinates.dimension != self.base_dimension {
+                return Err(TopologyError::DimensionMismatch {
+                    expected: self.base_dimension,
+                    found: world.coordinates.dimension,
+                });
+            }
+            if perspective.state.len() != n {
+                return Err(TopologyError::DimensionMismatch { expected: n, found: perspective.state.len() });
+            }
+        }
+
+        let index = self.histories;
+        self.histories += 1;
+        for pair in history.windows(2) {
+            self.steps.push(Step {
+                history: index,
+                from: pair[0].0.coordinates.position.clone(),
+                to: pair[1].0.coordinates.position.clone(),
+                before: pair[0].1.state.clone(),
+                after: pair[1].1.state.clone(),
+            });
+        }
+        Ok(self)
+    }
+
+    /// Number of observed steps
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Check if no steps have been observed
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Number of Lie-algebra coordinates being fitted
+    pub fn parameter_count(&self) -> usize {
+        let per_direction = if self.fit_gradients { 1 + self.base_dimension } else { 1 };
+        self.base_dimension * per_direction * self.algebra.dimension()
+    }
+
+    /// Least-squares fit of the connection
+    pub fn fit(&self) -> Result<ConnectionFit, TopologyError> {
+        if self.steps.is_empty() {
+            return Err(TopologyError::insufficient_data("no transport steps observed"));
+        }
+        let n = self.algebra.fiber_dimension;
+        let p = self.parameter_count();
+
+        // Linear Cayley-midpoint system
+        let mut design = DMatrix::zeros(self.steps.len() * n, p);
+        let mut target = DVector::zeros(self.steps.len() * n);
+        let layout = self.layout();
+        for (k, step) in self.steps.iter().enumerate() {
+            let mid = (&step.from + &step.to) / 2.0;
+            let delta = &step.to - &step.from;
+            let mean = (&step.before + &step.after) / 2.0;
+            for (column, &(mu, nu, a)) in layout.iter().enumerate() {
+                let weight = delta[mu] * nu.map_or(1.0, |nu| mid[nu]);
+                let effect = &self.algebra.generators[a] * &mean * weight;
+                design.view_mut((k * n, column), (n, 1)).copy_from(&effect);
+            }
+            target.rows_mut(k * n, n).copy_from(&(&step.before - &step.after));
+        }
+
+        let rank = if p == 0 { 0 } else { design.rank(1e-9) };
+        let mut theta = least_squares(&design, &target);
+
+        // Gauss–Newton against exact transport
+        let mut error = self.residual_vector(&theta).norm();
+        for _ in 0..self.refinements {
+            let residual = self.residual_vector(&theta);
+            let h = 1e-6;
+            let mut jacobian = DMatrix::zeros(residual.len(), p);
+            for j in 0..p {
+                let mut shifted = theta.clone();
+                shifted[j] += h;
+                jacobian.set_column(j, &((self.residual_vector(&shifted) - &residual) / h));
+            }
+            let candidate = &theta - least_squares(&jacobian, &residual);
+            let candidate_error = self.residual_vector(&candidate).norm();
+            if candidate_error >= error {
+                break;
+            }
+            theta = candidate;
+            error = candidate_error;
+        }
+
+        let connection = self.connection(&theta);
+        let mut step_in_history = 0;
+        let residuals = self.steps.iter().enumerate()
+            .map(|(k, step)| {
+                if k > 0 && self.steps[k - 1].history != step.history {
+                    step_in_history = 0;
+                }
+                let predicted = connection.segment_transport(&step.from, &step.to) * &step.before;
+                step_in_history += 1;
+                StepResidual { history: step.history, step: step_in_history - 1, error: (&step.after - predicted).norm() }
+            })
+            .collect();
+
+        let mut points: Vec<DVector<f64>> = Vec::new();
+        for step in &self.steps {
+            for x in [&step.from, &step.to] {
+                if !points.iter().any(|q| (q - x).norm() < 1e-12) {
+                    points.push(x.clone());
+                }
+            }
+        }
+
+        Ok(ConnectionFit { connection, residuals, rank, parameters: p, points })
+    }
+
+    /// Parameter layout: (μ, None, a) for A_μ, then (μ, Some(ν), a) for ∂_ν A_μ
+    fn layout(&self) -> Vec<(usize, Option<usize>, usize)> {
+        let d = self.base_dimension;
+        let m = self.algebra.dimension();
+        let mut layout = Vec::with_capacity(self.parameter_count());
+        for mu in 0..d {
+            layout.extend((0..m).map(|a| (mu, None, a)));
+        }
+        if self.fit_gradients {
+            for mu in 0..d {
+                for nu in 0..d {
+                    layout.extend((0..m).map(|a| (mu, Some(nu), a)));
+                }
+            }
+        }
+        layout
+    }
+
+    fn connection(&self, theta: &DVector<f64>) -> ConnectionForm {
+        let m = self.algebra.dimension();
+        let mut connection = ConnectionForm::new(self.algebra.clone(), self.base_dimension);
+        // An abelian fiber of dimension one has no generators, hence no parameters
+        if m == 0 {
+            return connection;
+        }
+        let layout = self.layout();
+        for (chunk, coefficients) in theta.as_slice().chunks(m).enumerate() {
+            let (mu, nu, _) = layout[chunk * m];
+            connection = match nu {
+                None => connection.with_component(mu, coefficients),
+                Some(nu) => connection.with_gradient(mu, nu, coefficients),
+            };
+        }
+        connection
+    }
+
+    fn residual_vector(&self, theta: &DVector<f64>) -> DVector<f64> {
+        let n = self.algebra.fiber_dimension;
+        let connection = self.connection(theta);
+        let mut residual = DVector::zeros(self.steps.len() * n);
+        for (k, step) in self.steps.iter().enumerate() {
+            let predicted = connection.segment_transport(&step.from, &step.to) * &step.before;
+            residual.rows_mut(k * n, n).copy_from(&(predicted - &step.after));
+        }
+        residual
+    }
+}
+
+fn least_squares(a: &DMatrix<f64>, b: &DVector<f64>) -> DVector<f64> {
+    if a.ncols() == 0 {
+        return DVector::zeros(0);
+    }
+    a.clone()
+        .svd(true, true)
+        .solve(b, 1e-12)
+        .unwrap_or_else(|_| DVector::zeros(a.ncols()))
+}
+
+impl ConnectionFit {
+    /// Root-mean-square step residual
+    pub fn rms(&self) -> f64 {
+        if self.residuals.is_empty() {
+            return 0.0;
+        }
+        let sum: f64 = self.residuals.iter().map(|r| r.error * r.error).sum();
+        (sum / self.residuals.len() as f64).sqrt()
+    }
+
+    /// Largest step residual
+    pub fn max_residual(&self) -> f64 {
+        self.residuals.iter().map(|r| r.error).fold(0.0, f64::max)
+    }
+
+    /// Check if the data pinned down every fitted coordinate
+    pub fn is_identified(&self) -> bool {
+        self.rank == self.parameters
+    }
+
+    /// Implied curvature at every observed world point
+    pub fn curvature_map(&self) -> Vec<CurvatureForm> {
+        self.points.iter().map(|x| self.connection.curvature_form(x)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::PerspectivalSelf;
+
+    /// A wandering history generated by a known connection
+    fn record(connection: &ConnectionForm, start: Vec<f64>, state: Vec<f64>, phase: f64) -> Vec<(WorldPoint, Perspective)> {
+        let mut x = DVector::from_vec(start);
+        let mut perspective = Perspective::new(state.len()).with_state(state);
+        let mut history = vec![(WorldPoint::new(x.as_slice().to_vec(), "start"), perspective.clone())];
+        for k in 0..25 {
+            let t = k as f64 * 0.7 + phase;
+            let next = &x + DVector::from_vec(vec![0.3 * t.cos(), 0.3 * (1.3 * t).sin()]);
+            perspective.state = connection.segment_transport(&x, &next) * &perspective.state;
+            x = next;
+            history.push((WorldPoint::new(x.as_slice().to_vec(), format!("stop_{}", k)), perspective.clone()));
+        }
+        history
+    }
+
+    #[test]
+    fn test_recovers_abelian_curvature() {
+        let mut traveller = PerspectivalSelf::new(2, 2, 0.8);
+        traveller.emerge_at(WorldPoint::new(vec![0.0, 0.0], "home"), Perspective::new(2).with_state(vec![1.0, 0.0]));
+        let mut at = Coordinate::new(vec![0.0, 0.0]);
+        for k in 0..20 {
+            let angle = k as f64 * 0.9;
+            let to = Coordinate::new(vec![angle.cos() * (1.0 + 0.05 * k as f64), angle.sin()]);
+            traveller.journey(&Path::new(vec![at, to.clone()])).unwrap();
+            at = to;
+        }
+
+        let mut estimator = ConnectionEstimator::new(LieAlgebra::so(2), 2);
+        estimator.add_history(traveller.history()).unwrap();
+        let fit = estimator.fit().unwrap();
+
+        // Only the rotation is observable from one history; curvature is
+        // gauge invariant, so it is recovered exactly everywhere
+        assert!(fit.rms() < 1e-6, "rms {}", fit.rms());
+        for form in fit.curvature_map() {
+            assert!((form.magnitude() - 0.8).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_recovers_non_abelian_connection() {
+        let truth = ConnectionForm::new(LieAlgebra::so(3), 2)
+            .with_component(0, &[0.5, 0.0, -0.3])
+            .with_component(1, &[0.1, 0.6, 0.0])
+            .with_gradient(0, 1, &[0.0, 0.4, 0.2])
+            .with_gradient(1, 0, &[-0.3, 0.0, 0.1]);
+
+        let mut estimator = ConnectionEstimator::new(LieAlgebra::so(3), 2);
+        for (k, state) in [vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]].into_iter().enumerate() {
+            estimator.add_history(&record(&truth, vec![0.0, 0.0], state, k as f64)).unwrap();
+        }
+        let fit = estimator.fit().unwrap();
+        assert!(fit.is_identified());
+        assert!(fit.max_residual() < 1e-6);
+
+        let x = DVector::from_vec(vec![0.4, -0.2]);
+        let difference = truth.curvature_form(&x).components[0][1].clone() - fit.connection.curvature_form(&x).components[0][1].clone();
+        assert!(difference.norm() < 1e-4);
+        assert!((fit.connection.component(1, &x) - truth.component(1, &x)).norm() < 1e-4);
+    }
+
+    #[test]
+    fn test_refinement_beats_linear_fit() {
+        let truth = ConnectionForm::curved(2, 1.5);
+        let history = record(&truth, vec![0.5, 0.5], vec![0.0, 1.0], 0.2);
+
+        let fit_with = |refinements| {
+            let mut estimator = ConnectionEstimator::new(LieAlgebra::so(2), 2).with_refinements(refinements);
+            estimator.add_history(&history).unwrap();
+            estimator.fit().unwrap().rms()
+        };
+        let linear = fit_with(0);
+        let refined = fit_with(DEFAULT_REFINEMENTS);
+        assert!(refined < linear);
+        assert!(refined < 1e-6);
+    }
+
+    #[test]
+    fn test_constant_model_leaves_residuals() {
+        // A curved world cannot be explained by a constant abelian field
+        let truth = ConnectionForm::curved(2, 1.0);
+        let mut estimator = ConnectionEstimator::new(LieAlgebra::so(2), 2).constant_only();
+        estimator.add_history(&record(&truth, vec![0.0, 0.0], vec![1.0, 0.0], 0.0)).unwrap();
+        assert_eq!(estimator.parameter_count(), 2);
+
+        let fit = estimator.fit().unwrap();
+        assert!(fit.rms() > 1e-3);
+        assert!(fit.curvature_map().iter().all(|f| f.magnitude() < 1e-12));
+        assert_eq!(fit.residuals.len(), estimator.len());
+    }
+
+    #[test]
+    fn test_rejects_bad_histories() {
+        let mut estimator = ConnectionEstimator::new(LieAlgebra::so(2), 2);
+        assert!(matches!(estimator.fit(), Err(TopologyError::InsufficientData { .. })));
+
+        let wrong_fiber = vec![(WorldPoint::new(vec![0.0, 0.0], "a"), Perspective::new(3))];
+        assert!(matches!(
+            estimator.add_history(&wrong_fiber),
+            Err(TopologyError::DimensionMismatch { expected: 2, found: 3 })
+        ));
+        assert!(estimator.is_empty());
+
+        // so(1) has no generators: the only connection is the flat one
+        let mut trivial = ConnectionEstimator::new(LieAlgebra::so(1), 2);
+        let history = vec![
+            (WorldPoint::new(vec![0.0, 0.0], "a"), Perspective::new(1)),
+            (WorldPoint::new(vec![1.0, 0.0], "b"), Perspective::new(1)),
+        ];
+        trivial.add_history(&history).unwrap();
+        let fit = trivial.fit().unwrap();
+        assert_eq!(fit.parameters, 0);
+        assert!(fit.rms() < 1e-12);
+    }
+}
@@ -116,7 +116,7 @@ pub struct TauQubit {
     /// Amplitude envelope
     pub amplitude: f64,
     /// Phase memory (history of phase states)
-    phase_memory: Vec<f64>,
+    pub(crate) phase_memory: Vec<f64>,
 }
 
 impl TauQubit {
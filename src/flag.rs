@@ -0,0 +1,441 @@
+//! Flag complexes - relations read off the graph of ideas.
+//!
+//! Relations have a direction: "A implies B" is not "B implies A".
+//!
+//! - The directed flag complex has an ordered k-simplex (v₀, …, v_k) for
+//!   every directed clique: vᵢ → vⱼ whenever i < j. A feedback loop of
+//!   reasoning (A → B → C → A) is not a clique, so it stays a hole; a
+//!   transitive triangle (A → B → C, A → C) is filled.
+//! - Weighted clique filtrations read relation weights as filtration values:
+//!   a simplex appears once its heaviest edge has. Pass inverse strengths to
+//!   let strong relations enter first.
+
+use crate::homology::{ChainComplex, PersistenceInterval};
+use crate::simplex::{ConceptComplex, HomologicalHole, Idea, Relation};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// How the arcs of a closed walk are oriented
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CycleKind {
+    /// Every arc follows the walk: reasoning feeds back on itself
+    FeedbackLoop,
+    /// Adjacent ideas are related, but arcs point both ways along the walk
+    UndirectedCycle,
+    /// Some consecutive ideas are not related at all
+    NotACycle,
+}
+
+/// A directed flag complex over ideas
+#[derive(Debug, Clone, Default)]
+pub struct DirectedFlagComplex {
+    /// All ideas (vertices)
+    pub ideas: BTreeMap<usize, Idea>,
+    /// Directed relations (source, target) → weight
+    pub arcs: BTreeMap<(usize, usize), f64>,
+    /// Highest simplex dimension to build
+    pub max_dimension: usize,
+}
+
+impl DirectedFlagComplex {
+    /// Empty complex building simplices up to `max_dimension`
+    pub fn new(max_dimension: usize) -> Self {
+        Self {
+            ideas: BTreeMap::new(),
+            arcs: BTreeMap::new(),
+            max_dimension,
+        }
+    }
+
+    /// Add an idea (vertex)
+    pub fn add_idea(&mut self, idea: Idea) -> &mut Self {
+        self.ideas.insert(idea.id, idea);
+        self
+    }
+
+    /// Add a weighted directed relation; self-loops are ignored
+    pub fn add_arc(&mut self, source: usize, target: usize, weight: f64) -> &mut Self {
+        if source != target {
+            self.arcs.insert((source, target), weight);
+        }
+        self
+    }
+
+    /// "source implies target" with unit weight
+    pub fn implies(&mut self, source: usize, target: usize) -> &mut Self {
+        self.add_arc(source, target, 1.0)
+    }
+
+    /// Check for an arc source → target
+    pub fn has_arc(&self, source: usize, target: usize) -> bool {
+        self.arcs.contains_key(&(source, target))
+    }
+
+    fn successors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.arcs.range((v, 0)..=(v, usize::MAX)).map(|(&(_, t), _)| t)
+    }
+
+    /// Ordered simplices by dimension: `simplices()[k]` holds the k-simplices
+    pub fn simplices(&self) -> Vec<Vec<Vec<usize>>> {
+        let mut by_dim: Vec<Vec<Vec<usize>>> = vec![self.ideas.keys().map(|&v| vec![v]).collect()];
+
+        // Every ordered (k+1)-clique is a k-clique followed by a common successor
+        for k in 1..=self.max_dimension {
+            let next: Vec<Vec<usize>> = by_dim[k - 1].iter()
+                .flat_map(|simplex| {
+                    let last = *simplex.last().unwrap_or(&0);
+                    self.successors(last)
+                        .filter(|&w| self.ideas.contains_key(&w))
+                        .filter(|&w| simplex.iter().all(|&u| self.has_arc(u, w)))
+                        .map(|w| {
+                            let mut extended = simplex.clone();
+                            extended.push(w);
+                            extended
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            if next.is_empty() {
+                break;
+            }
+            by_dim.push(next);
+        }
+
+        by_dim
+    }
+
+    /// Filtration value of an ordered simplex: its heaviest arc
+    pub fn filtration_value(&self, simplex: &[usize]) -> f64 {
+        let mut value: f64 = 0.0;
+        for (i, &u) in simplex.iter().enumerate() {
+            for &w in &simplex[i + 1..] {
+                value = value.max(self.arcs.get(&(u, w)).copied().unwrap_or(f64::INFINITY));
+            }
+        }
+        value
+    }
+
+    /// The weighted directed flag filtration as a chain complex
+    pub fn chain_complex(&self) -> ChainComplex {
+        let mut complex = ChainComplex::new();
+        for simplex in self.simplices().into_iter().flatten() {
+            let value = self.filtration_value(&simplex);
+            complex.add_simplex(simplex, value);
+        }
+        complex
+    }
+
+    /// Directed Betti numbers
+    pub fn betti_numbers(&self) -> Vec<usize> {
+        self.chain_complex().betti_numbers()
+    }
+
+    /// Persistence of the weighted directed flag filtration
+    pub fn persistence(&self) -> Vec<PersistenceInterval> {
+        self.chain_complex().persistence()
+    }
+
+    /// The undirected complex: one edge per related pair, weight = lightest arc
+    pub fn underlying(&self) -> ConceptComplex {
+        let mut complex = ConceptComplex::new();
+        for idea in self.ideas.values() {
+            complex.add_idea(idea.clone());
+        }
+        let mut edges: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+        for (&(a, b), &w) in &self.arcs {
+            let weight = edges.entry((a.min(b), a.max(b))).or_insert(w);
+            *weight = weight.min(w);
+        }
+        for ((a, b), w) in edges {
+            complex.add_relation(Relation::edge(a, b).with_weight(w));
+        }
+        complex.calculate_homology();
+        complex
+    }
+
+    /// Betti numbers of the undirected flag complex on the same graph
+    pub fn undirected_betti_numbers(&self) -> Vec<usize> {
+        self.underlying().clique_filtration(self.max_dimension).betti_numbers()
+    }
+
+    /// Classify a closed walk of ideas (first idea not repeated at the end)
+    pub fn classify_cycle(&self, cycle: &[usize]) -> CycleKind {
+        if cycle.len() < 2 {
+            return CycleKind::NotACycle;
+        }
+        let steps: Vec<(usize, usize)> = (0..cycle.len())
+            .map(|i| (cycle[i], cycle[(i + 1) % cycle.len()]))
+            .collect();
+
+        if steps.iter().any(|&(a, b)| !self.has_arc(a, b) && !self.has_arc(b, a)) {
+            CycleKind::NotACycle
+        } else if steps.iter().all(|&(a, b)| self.has_arc(a, b))
+            || steps.iter().all(|&(a, b)| self.has_arc(b, a))
+        {
+            CycleKind::FeedbackLoop
+        } else {
+            CycleKind::UndirectedCycle
+        }
+    }
+
+    /// Elementary directed cycles of length ≤ `max_length`
+    ///
+    /// Each cycle is listed once, starting from its smallest idea.
+    pub fn feedback_loops(&self, max_length: usize) -> Vec<Vec<usize>> {
+        let mut cycles = Vec::new();
+        for &start in self.ideas.keys() {
+            let mut path = vec![start];
+            self.extend_cycles(start, &mut path, max_length, &mut cycles);
+        }
+        cycles
+    }
+
+    fn extend_cycles(&self, start: usize, path: &mut Vec<usize>, max_length: usize, cycles: &mut Vec<Vec<usize>>) {
+        let last = *path.last().unwrap_or(&start);
+        for next in self.successors(last) {
+            if next == start && path.len() >= 2 {
+                cycles.push(path.clone());
+            } else if next > start && !path.contains(&next) && path.len() < max_length {
+                path.push(next);
+                self.extend_cycles(start, path, max_length, cycles);
+                path.pop();
+            }
+        }
+    }
+
+    /// Feedback loops that are directed holes, as homological holes
+    ///
+    /// Loops that bound a chain of directed cliques are filled, so only
+    /// loops that survive in directed H₁ are reported.
+    pub fn feedback_holes(&self, max_length: usize) -> Vec<HomologicalHole> {
+        let complex = self.chain_complex();
+        self.feedback_loops(max_length)
+            .into_iter()
+            .filter(|cycle| {
+                let chain: Vec<usize> = (0..cycle.len())
+                    .filter_map(|i| complex.find(&[cycle[i], cycle[(i + 1) % cycle.len()]]))
+                    .collect();
+                !complex.is_boundary(&chain)
+            })
+            .map(|cycle| {
+                let mut hole = HomologicalHole::new(1, vec![cycle]);
+                hole.gap_description = "Feedback loop of reasoning: each idea implies the next".to_string();
+                hole
+            })
+            .collect()
+    }
+}
+
+/// Builder for directed flag complexes
+pub struct DirectedComplexBuilder {
+    complex: DirectedFlagComplex,
+    next_id: usize,
+}
+
+impl DirectedComplexBuilder {
+    /// Builder for simplices up to dimension 2
+    pub fn new() -> Self {
+        Self {
+            complex: DirectedFlagComplex::new(2),
+            next_id: 0,
+        }
+    }
+
+    /// Set the highest simplex dimension
+    pub fn max_dimension(&mut self, dimension: usize) -> &mut Self {
+        self.complex.max_dimension = dimension;
+        self
+    }
+
+    /// Add an idea and return its id
+    pub fn add_idea(&mut self, name: impl Into<String>) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.complex.add_idea(Idea::new(id, name));
+        id
+    }
+
+    /// "a implies b"
+    pub fn implies(&mut self, a: usize, b: usize) -> &mut Self {
+        self.complex.implies(a, b);
+        self
+    }
+
+    /// Weighted "a implies b"
+    pub fn implies_weighted(&mut self, a: usize, b: usize, weight: f64) -> &mut Self {
+        self.complex.add_arc(a, b, weight);
+        self
+    }
+
+    /// Finish building
+    pub fn build(self) -> DirectedFlagComplex {
+        self.complex
+    }
+}
+
+impl Default for DirectedComplexBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConceptComplex {
+    /// Weighted clique filtration of the 1-skeleton
+    ///
+    /// Ideas enter at 0, edges at their weight (edges implied by higher
+    /// relations take that relation's weight), and every clique up to
+    /// `max_dimension` enters with its heaviest edge.
+    pub fn clique_filtration(&self, max_dimension: usize) -> ChainComplex {
+        let mut weights: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+        for relation in self.faces.iter().filter(|f| f.dimension >= 1) {
+            for (i, &a) in relation.vertices.iter().enumerate() {
+                for &b in &relation.vertices[i + 1..] {
+                    let w = weights.entry((a.min(b), a.max(b))).or_insert(relation.weight);
+                    *w = w.min(relation.weight);
+                }
+            }
+        }
+
+        let mut neighbours: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+        for &(a, b) in weights.keys() {
+            neighbours.entry(a).or_default().insert(b);
+            neighbours.entry(b).or_default().insert(a);
+        }
+
+        let mut complex = ChainComplex::new();
+        let mut ids: Vec<usize> = self.vertices.keys().copied().collect();
+        ids.sort_unstable();
+        let mut layer: Vec<Vec<usize>> = ids.iter().map(|&v| vec![v]).collect();
+        for simplex in &layer {
+            complex.add_simplex(simplex.clone(), 0.0);
+        }
+
+        // Grow cliques by appending a larger common neighbour
+        for _ in 0..max_dimension {
+            let mut next = Vec::new();
+            for simplex in &layer {
+                let last = *simplex.last().unwrap_or(&0);
+                let Some(candidates) = neighbours.get(&last) else { continue };
+                for &w in candidates.range(last + 1..) {
+                    if simplex.iter().all(|u| neighbours.get(u).is_some_and(|n| n.contains(&w))) {
+                        let mut clique = simplex.clone();
+                        clique.push(w);
+                        let value = clique.iter().enumerate()
+                            .flat_map(|(i, &a)| clique[i + 1..].iter().map(move |&b| (a, b)))
+                            .map(|edge| weights.get(&edge).copied().unwrap_or(f64::INFINITY))
+                            .fold(0.0, f64::max);
+                        complex.add_simplex(clique.clone(), value);
+                        next.push(clique);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            layer = next;
+        }
+
+        complex
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle(transitive: bool) -> DirectedFlagComplex {
+        let mut builder = DirectedComplexBuilder::new();
+        let a = builder.add_idea("Premise");
+        let b = builder.add_idea("Inference");
+        let c = builder.add_idea("Conclusion");
+        builder.implies(a, b).implies(b, c);
+        if transitive {
+            builder.implies(a, c);
+        } else {
+            builder.implies(c, a);
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn test_transitive_triangle_is_filled() {
+        let complex = triangle(true);
+        assert_eq!(complex.simplices()[2], vec![vec![0, 1, 2]]);
+        assert_eq!(complex.betti_numbers(), vec![1, 0, 0]);
+    }
+
+    #[test]
+    fn test_feedback_loop_is_a_directed_hole() {
+        let complex = triangle(false);
+        assert_eq!(complex.simplices().len(), 2); // no 2-simplices
+        assert_eq!(complex.betti_numbers(), vec![1, 1]);
+        // The undirected flag complex fills the same triangle
+        assert_eq!(complex.undirected_betti_numbers(), vec![1, 0, 0]);
+    }
+
+    #[test]
+    fn test_classify_cycle() {
+        assert_eq!(triangle(false).classify_cycle(&[0, 1, 2]), CycleKind::FeedbackLoop);
+        assert_eq!(triangle(true).classify_cycle(&[0, 1, 2]), CycleKind::UndirectedCycle);
+        assert_eq!(triangle(true).classify_cycle(&[0, 2, 5]), CycleKind::NotACycle);
+    }
+
+    #[test]
+    fn test_feedback_loops() {
+        let complex = triangle(false);
+        assert_eq!(complex.feedback_loops(5), vec![vec![0, 1, 2]]);
+        assert!(triangle(true).feedback_loops(5).is_empty());
+
+        let holes = complex.feedback_holes(5);
+        assert_eq!(holes.len(), 1);
+        assert_eq!(holes[0].dimension, 1);
+    }
+
+    #[test]
+    fn test_reciprocal_arcs_form_a_loop() {
+        let mut builder = DirectedComplexBuilder::new();
+        let a = builder.add_idea("A");
+        let b = builder.add_idea("B");
+        builder.implies(a, b).implies(b, a);
+        let complex = builder.build();
+        assert_eq!(complex.betti_numbers(), vec![1, 1]);
+        assert_eq!(complex.feedback_loops(2), vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn test_weighted_directed_persistence() {
+        let mut builder = DirectedComplexBuilder::new();
+        let a = builder.add_idea("A");
+        let b = builder.add_idea("B");
+        let c = builder.add_idea("C");
+        let d = builder.add_idea("D");
+        builder.implies_weighted(a, b, 1.0)
+            .implies_weighted(b, c, 1.0)
+            .implies_weighted(c, d, 1.0)
+            .implies_weighted(a, d, 1.0)
+            .implies_weighted(a, c, 2.0);
+        let complex = builder.build();
+
+        // The square closes at 1.0 and is filled when A → C arrives at 2.0
+        let loops: Vec<_> = complex.persistence().into_iter().filter(|i| i.dimension == 1).collect();
+        assert_eq!(loops.len(), 1);
+        assert_eq!((loops[0].birth, loops[0].death), (1.0, 2.0));
+    }
+
+    #[test]
+    fn test_undirected_clique_filtration_uses_weights() {
+        let mut complex = ConceptComplex::new();
+        for i in 0..3 {
+            complex.add_idea(Idea::new(i, format!("I{}", i)));
+        }
+        complex.add_relation(Relation::edge(0, 1).with_weight(0.5));
+        complex.add_relation(Relation::edge(1, 2).with_weight(0.5));
+        complex.add_relation(Relation::edge(0, 2).with_weight(2.0));
+
+        let filtration = complex.clique_filtration(2);
+        assert_eq!(filtration.betti_at(0.5), vec![1, 0, 0]);
+        assert_eq!(filtration.betti_numbers(), vec![1, 0, 0]);
+        // Clique appears with its heaviest edge
+        let triangle = filtration.find(&[0, 1, 2]).unwrap();
+        assert_eq!(filtration.cells[triangle].filtration, 2.0);
+    }
+}
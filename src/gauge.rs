@@ -0,0 +1,487 @@
+//! Gauge Transformations - relabelling the fiber without changing the world.
+//!
+//! A gauge g(x) is a change of fiber frame at every base point. It acts
+//! consistently on everything in the bundle:
+//!
+//! - perspectives: ψ → g(x) ψ
+//! - connections:  A_μ → g A_μ g⁻¹ - (∂_μ g) g⁻¹
+//! - transport:    U(path) → g(end) U g(start)⁻¹
+//! - curvature:    F_μν → g F_μν g⁻¹
+//!
+//! Gauge-covariant quantities only change by conjugation. Gauge-invariant
+//! ones, such as Wilson loops tr U, do not change at all. A belief shift
+//! that survives every relabelling is real. One that a frame change can
+//! remove is an artefact of labelling.
+
+use crate::bundle::{
+    commutator, magnus_transport, ConnectionForm, CurvatureForm, HolonomyResult, LieAlgebra, Perspective,
+};
+use crate::types::*;
+use nalgebra::{DMatrix, DVector};
+
+/// A change of fiber frame over the base: g(x) = frame · exp(X(x))
+///
+/// X(x) = Λ₀ + Σ_ν x^ν Λ_ν takes values in a Lie algebra; `frame` is any
+/// constant invertible matrix (e.g. a relabelling of belief axes).
+#[derive(Debug, Clone)]
+pub struct GaugeTransformation {
+    /// Lie algebra the exponent takes values in
+    pub algebra: LieAlgebra,
+    /// Constant frame applied after the exponential
+    pub frame: DMatrix<f64>,
+    frame_inverse: DMatrix<f64>,
+    /// Λ₀, the exponent at the origin
+    pub generator: DMatrix<f64>,
+    /// Λ_ν = ∂_ν X, one per base direction
+    pub gradients: Vec<DMatrix<f64>>,
+}
+
+impl GaugeTransformation {
+    /// The identity gauge on the algebra's fiber
+    pub fn new(algebra: LieAlgebra) -> Self {
+        let n = algebra.fiber_dimension;
+        Self {
+            algebra,
+            frame: DMatrix::identity(n, n),
+            frame_inverse: DMatrix::identity(n, n),
+            generator: DMatrix::zeros(n, n),
+            gradients: Vec::new(),
+        }
+    }
+
+    /// A constant frame change by an invertible matrix
+    pub fn constant(frame: DMatrix<f64>) -> Result<Self, TopologyError> {
+        Self::new(LieAlgebra::so(frame.nrows())).with_frame(frame)
+    }
+
+    /// Set the constant frame (must be square, invertible and fiber-sized)
+    pub fn with_frame(mut self, frame: DMatrix<f64>) -> Result<Self, TopologyError> {
+        let n = self.fiber_dimension();
+        if frame.nrows() != n || frame.ncols() != n {
+            return Err(TopologyError::DimensionMismatch { expected: n, found: frame.ncols() });
+        }
+        self.frame_inverse = frame
+            .clone()
+            .try_inverse()
+            .ok_or(TopologyError::SingularGauge { dimension: n })?;
+        self.frame = frame;
+        Ok(self)
+    }
+
+    /// Set Λ₀ from algebra coordinates
+    pub fn with_constant(mut self, coefficients: &[f64]) -> Self {
+        self.generator = self.algebra.element(coefficients);
+        self
+    }
+
+    /// Set Λ_ν = ∂_ν X from algebra coordinates
+    pub fn with_gradient(mut self, nu: usize, coefficients: &[f64]) -> Self {
+        let n = self.fiber_dimension();
+        if self.gradients.len() <= nu {
+            self.gradients.resize(nu + 1, DMatrix::zeros(n, n));
+        }
+        self.gradients[nu] = self.algebra.element(coefficients);
+        self
+    }
+
+    /// Dimension of the fiber the gauge acts on
+    pub fn fiber_dimension(&self) -> usize {
+        self.algebra.fiber_dimension
+    }
+
+    /// Check if g does not depend on position
+    pub fn is_constant(&self) -> bool {
+        self.gradients.iter().all(|l| l.norm() < 1e-14)
+    }
+
+    /// The exponent X(x)
+    pub fn exponent(&self, x: &DVector<f64>) -> DMatrix<f64> {
+        let mut exponent = self.generator.clone();
+        for (nu, lambda) in self.gradients.iter().enumerate().take(x.len()) {
+            exponent += lambda * x[nu];
+        }
+        exponent
+    }
+
+    /// g(x)
+    pub fn at(&self, x: &DVector<f64>) -> DMatrix<f64> {
+        &self.frame * self.exponent(x).exp()
+    }
+
+    /// g(x)⁻¹
+    pub fn inverse_at(&self, x: &DVector<f64>) -> DMatrix<f64> {
+        (-self.exponent(x)).exp() * &self.frame_inverse
+    }
+
+    /// ∂_ν g at x
+    ///
+    /// Exact: the upper-right block of exp([[X, Λ_ν], [0, X]]) is the
+    /// derivative of exp(X + tΛ_ν) at t = 0.
+    pub fn derivative(&self, nu: usize, x: &DVector<f64>) -> DMatrix<f64> {
+        let n = self.fiber_dimension();
+        let Some(lambda) = self.gradients.get(nu) else {
+            return DMatrix::zeros(n, n);
+        };
+        let exponent = self.exponent(x);
+        let mut block = DMatrix::zeros(2 * n, 2 * n);
+        block.view_mut((0, 0), (n, n)).copy_from(&exponent);
+        block.view_mut((n, n), (n, n)).copy_from(&exponent);
+        block.view_mut((0, n), (n, n)).copy_from(lambda);
+        &self.frame * block.exp().view((0, n), (n, n))
+    }
+
+    /// ∂_μ ∂_ν g at x
+    ///
+    /// Exact: the corner block of exp([[X, Λ_μ, 0], [0, X, Λ_ν], [0, 0, X]])
+    /// is one ordering of the mixed derivative; the two orderings sum to it.
+    pub fn second_derivative(&self, mu: usize, nu: usize, x: &DVector<f64>) -> DMatrix<f64> {
+        let n = self.fiber_dimension();
+        let (Some(first), Some(second)) = (self.gradients.get(mu), self.gradients.get(nu)) else {
+            return DMatrix::zeros(n, n);
+        };
+        let exponent = self.exponent(x);
+        let ordered = |a: &DMatrix<f64>, b: &DMatrix<f64>| {
+            let mut block = DMatrix::zeros(3 * n, 3 * n);
+            for k in 0..3 {
+                block.view_mut((k * n, k * n), (n, n)).copy_from(&exponent);
+            }
+            block.view_mut((0, n), (n, n)).copy_from(a);
+            block.view_mut((n, 2 * n), (n, n)).copy_from(b);
+            block.exp().view((0, 2 * n), (n, n)).into_owned()
+        };
+        &self.frame * (ordered(first, second) + ordered(second, first))
+    }
+
+    /// ψ → g(x) ψ
+    pub fn transform_perspective(&self, perspective: &Perspective, at: &DVector<f64>) -> Perspective {
+        let mut transformed = perspective.clone();
+        if perspective.state.len() == self.fiber_dimension() {
+            transformed.state = self.at(at) * &perspective.state;
+        }
+        transformed
+    }
+
+    /// The connection seen in the new frame
+    pub fn transform_connection(&self, connection: &ConnectionForm) -> GaugedConnection {
+        GaugedConnection { connection: connection.clone(), gauge: self.clone() }
+    }
+
+    /// U → g(x₀) U g(x₀)⁻¹ for a loop based at `base`
+    pub fn transform_holonomy(
+        &self,
+        result: &HolonomyResult,
+        base: &DVector<f64>,
+    ) -> Result<HolonomyResult, TopologyError> {
+        let holonomy = self.at(base) * &result.holonomy * self.inverse_at(base);
+        HolonomyResult::from_transport(self.transform_perspective(&result.initial_perspective, base), holonomy)
+    }
+
+    /// Compare holonomy in the new frame against the covariance prediction
+    ///
+    /// The transformed holonomy is integrated from the transformed connection,
+    /// never from the original transport, so agreement tests the action.
+    pub fn covariance(
+        &self,
+        connection: &ConnectionForm,
+        closed_path: &Path,
+        initial: &Perspective,
+    ) -> Result<GaugeCovariance, TopologyError> {
+        let base = closed_path.points.first()
+            .map(|p| p.position.clone())
+            .unwrap_or_else(|| DVector::zeros(connection.base_dimension()));
+
+        let original = HolonomyResult::from_transport(initial.clone(), connection.transport_matrix(closed_path))?;
+        let predicted = self.transform_holonomy(&original, &base)?;
+        let gauged = self.transform_connection(connection);
+        let observed = gauged.holonomy(closed_path, &predicted.initial_perspective)?;
+
+        Ok(GaugeCovariance {
+            transport_error: (&observed.holonomy - &predicted.holonomy).norm(),
+            state_error: (&observed.final_perspective.state - &predicted.final_perspective.state).norm(),
+            wilson_error: (observed.wilson_trace() - original.wilson_trace()).abs(),
+        })
+    }
+}
+
+/// A connection expressed in a gauge-transformed frame
+#[derive(Debug, Clone)]
+pub struct GaugedConnection {
+    /// The connection in the original frame
+    pub connection: ConnectionForm,
+    /// The frame change
+    pub gauge: GaugeTransformation,
+}
+
+impl GaugedConnection {
+    /// A'_μ(x) = g A_μ g⁻¹ - (∂_μ g) g⁻¹
+    pub fn component(&self, mu: usize, x: &DVector<f64>) -> DMatrix<f64> {
+        let g = self.gauge.at(x);
+        let g_inv = self.gauge.inverse_at(x);
+        g * self.connection.component(mu, x) * &g_inv - self.gauge.derivative(mu, x) * g_inv
+    }
+
+    /// Σ_μ A'_μ(x) dx^μ
+    pub fn along(&self, x: &DVector<f64>, dx: &DVector<f64>) -> DMatrix<f64> {
+        let n = self.gauge.fiber_dimension();
+        let d = self.connection.base_dimension().max(self.gauge.gradients.len()).min(dx.len());
+        let mut a = DMatrix::zeros(n, n);
+        for mu in 0..d {
+            a += self.component(mu, x) * dx[mu];
+        }
+        a
+    }
+
+    /// Transport matrix along a straight segment, integrated from A'
+    pub fn segment_transport(&self, from: &DVector<f64>, to: &DVector<f64>) -> DMatrix<f64> {
+        magnus_transport(self.gauge.fiber_dimension(), from, to, |x, dx| self.along(x, dx))
+    }
+
+    /// Path-ordered exponential of A' along a piecewise-linear path
+    pub fn transport_matrix(&self, path: &Path) -> DMatrix<f64> {
+        let n = self.gauge.fiber_dimension();
+        path.points.windows(2).fold(DMatrix::identity(n, n), |u, w| {
+            self.segment_transport(&w[0].position, &w[1].position) * u
+        })
+    }
+
+    /// Transport predicted by covariance: g(end) U g(start)⁻¹
+    pub fn covariant_transport(&self, path: &Path) -> DMatrix<f64> {
+        let n = self.gauge.fiber_dimension();
+        match (path.points.first(), path.points.last()) {
+            (Some(start), Some(end)) => {
+                self.gauge.at(&end.position)
+                    * self.connection.transport_matrix(path)
+                    * self.gauge.inverse_at(&start.position)
+            }
+            _ => DMatrix::identity(n, n),
+        }
+    }
+
+    /// Holonomy of `initial` (given in the new frame) around a loop
+    pub fn holonomy(&self, closed_path: &Path, initial: &Perspective) -> Result<HolonomyResult, TopologyError> {
+        HolonomyResult::from_transport(initial.clone(), self.transport_matrix(closed_path))
+    }
+
+    /// ∂_ν A'_μ at x, differentiating A' = g A_μ g⁻¹ - (∂_μ g) g⁻¹ exactly
+    fn component_gradient(&self, mu: usize, nu: usize, x: &DVector<f64>) -> DMatrix<f64> {
+        let g = self.gauge.at(x);
+        let g_inv = self.gauge.inverse_at(x);
+        let dg = self.gauge.derivative(nu, x);
+        // ∂_ν g⁻¹ = -g⁻¹ (∂_ν g) g⁻¹
+        let dg_inv = -&g_inv * &dg * &g_inv;
+        let a = self.connection.component(mu, x);
+        &dg * &a * &g_inv + &g * self.connection.gradient(mu, nu) * &g_inv + &g * &a * &dg_inv
+            - self.gauge.second_derivative(nu, mu, x) * &g_inv
+            - self.gauge.derivative(mu, x) * dg_inv
+    }
+
+    /// F' = dA' + A'∧A', computed from the transformed components
+    ///
+    /// Covariance predicts F'_μν = g F_μν g⁻¹; this does not assume it.
+    pub fn curvature_form(&self, x: &DVector<f64>) -> CurvatureForm {
+        let d = self.connection.base_dimension().max(self.gauge.gradients.len());
+        let a: Vec<DMatrix<f64>> = (0..d).map(|mu| self.component(mu, x)).collect();
+        let components = (0..d)
+            .map(|mu| {
+                (0..d)
+                    .map(|nu| {
+                        self.component_gradient(nu, mu, x) - self.component_gradient(mu, nu, x)
+                            + commutator(&a[mu], &a[nu])
+                    })
+                    .collect()
+            })
+            .collect();
+        CurvatureForm { at: x.clone(), components }
+    }
+
+    /// Wilson loop in the new frame (equal to the original's)
+    pub fn wilson_loop(&self, closed_path: &Path) -> f64 {
+        self.transport_matrix(closed_path).trace()
+    }
+
+    /// Rewrite as a plain `ConnectionForm` when the gauge is constant
+    ///
+    /// A constant g conjugates every component and gradient, so A' stays
+    /// linear in position. The algebra becomes g 𝔤 g⁻¹.
+    pub fn to_connection_form(&self) -> Option<ConnectionForm> {
+        if !self.gauge.is_constant() {
+            return None;
+        }
+        let origin = DVector::zeros(self.connection.base_dimension());
+        let g = self.gauge.at(&origin);
+        let g_inv = self.gauge.inverse_at(&origin);
+        let conjugate = |m: &DMatrix<f64>| &g * m * &g_inv;
+
+        let algebra = LieAlgebra {
+            name: format!("g·{}·g⁻¹", self.connection.algebra.name),
+            fiber_dimension: self.connection.algebra.fiber_dimension,
+            generators: self.connection.algebra.generators.iter().map(conjugate).collect(),
+        };
+        Some(ConnectionForm {
+            algebra,
+            components: self.connection.components.iter().map(conjugate).collect(),
+            gradients: self.connection.gradients.iter()
+                .map(|row| row.iter().map(conjugate).collect())
+                .collect(),
+        })
+    }
+}
+
+/// How far a holonomy computation is from exact gauge covariance
+#[derive(Debug, Clone, Copy)]
+pub struct GaugeCovariance {
+    /// ‖U' - g U g⁻¹‖ with U' integrated from the transformed connection
+    pub transport_error: f64,
+    /// ‖ψ'_final - g ψ_final‖
+    pub state_error: f64,
+    /// |tr U' - tr U|
+    pub wilson_error: f64,
+}
+
+impl GaugeCovariance {
+    /// Check if every error is within `tolerance`
+    pub fn holds(&self, tolerance: f64) -> bool {
+        self.transport_error <= tolerance && self.state_error <= tolerance && self.wilson_error <= tolerance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::FiberBundle;
+
+    fn square(corner: (f64, f64), side: f64) -> Path {
+        let (x, y) = corner;
+        Path::closed(vec![
+            Coordinate::new(vec![x, y]),
+            Coordinate::new(vec![x + side, y]),
+            Coordinate::new(vec![x + side, y + side]),
+            Coordinate::new(vec![x, y + side]),
+        ])
+    }
+
+    fn non_abelian() -> ConnectionForm {
+        ConnectionForm::new(LieAlgebra::so(3), 2)
+            .with_component(0, &[0.4, 0.0, -0.2])
+            .with_component(1, &[0.0, 0.7, 0.1])
+            .with_gradient(0, 1, &[0.3, 0.0, 0.0])
+    }
+
+    fn twisting_gauge() -> GaugeTransformation {
+        GaugeTransformation::new(LieAlgebra::so(3))
+            .with_constant(&[0.2, -0.5, 0.9])
+            .with_gradient(0, &[0.0, 0.6, 0.0])
+            .with_gradient(1, &[0.8, 0.0, -0.3])
+    }
+
+    #[test]
+    fn test_relabelling_flips_angle_not_wilson_loop() {
+        // Swapping the two belief axes reverses the apparent sense of rotation
+        let bundle = FiberBundle::curved(2, 2, 0.5);
+        let swap = GaugeTransformation::constant(DMatrix::from_row_slice(2, 2, &[0.0, 1.0, 1.0, 0.0])).unwrap();
+        let path = square((0.0, 0.0), 1.0);
+
+        let original = bundle.holonomy(&path).unwrap();
+        let relabelled = swap.transform_holonomy(&original, &DVector::zeros(2)).unwrap();
+        assert!((original.rotation_angle - 0.5).abs() < 1e-9);
+        assert!((relabelled.rotation_angle + 0.5).abs() < 1e-9);
+        assert!((relabelled.wilson_trace() - bundle.wilson_loop(&path).unwrap()).abs() < 1e-12);
+
+        let plain = swap.transform_connection(&bundle.connection).to_connection_form().unwrap();
+        assert!((plain.transport_matrix(&path) - &relabelled.holonomy).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_holonomy_is_gauge_covariant() {
+        let connection = non_abelian();
+        let gauge = twisting_gauge();
+        let initial = Perspective::new(3).with_state(vec![1.0, 0.0, 0.0]);
+
+        for (corner, side) in [((0.0, 0.0), 1.0), ((-0.5, 0.3), 0.6), ((1.0, -1.0), 1.5)] {
+            let report = gauge.covariance(&connection, &square(corner, side), &initial).unwrap();
+            assert!(report.holds(1e-6), "{:?}", report);
+        }
+    }
+
+    #[test]
+    fn test_open_path_transport_matches_covariance() {
+        let gauged = twisting_gauge().transform_connection(&non_abelian());
+        let path = Path::new(vec![
+            Coordinate::new(vec![0.0, 0.0]),
+            Coordinate::new(vec![0.8, 0.3]),
+            Coordinate::new(vec![0.2, 1.1]),
+        ]);
+        let integrated = gauged.transport_matrix(&path);
+        assert!((integrated - gauged.covariant_transport(&path)).norm() < 1e-6);
+        assert!(gauged.to_connection_form().is_none());
+    }
+
+    #[test]
+    fn test_pure_gauge_is_flat() {
+        // Transforming the trivial connection produces A' ≠ 0 with no curvature
+        let gauged = twisting_gauge().transform_connection(&ConnectionForm::new(LieAlgebra::so(3), 2));
+        let x = DVector::from_vec(vec![0.3, -0.4]);
+        assert!(gauged.component(0, &x).norm() > 0.1);
+        assert!(gauged.curvature_form(&x).magnitude() < 1e-12);
+
+        let path = square((0.2, 0.1), 0.9);
+        assert!((gauged.transport_matrix(&path) - DMatrix::identity(3, 3)).norm() < 1e-6);
+        assert!((gauged.wilson_loop(&path) - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_curvature_and_perspectives_transform() {
+        let connection = non_abelian();
+        let gauge = twisting_gauge();
+        let gauged = gauge.transform_connection(&connection);
+        let x = DVector::from_vec(vec![0.5, 0.5]);
+
+        // Orthogonal gauges preserve the curvature's size and the state's norm
+        let before = connection.curvature_form(&x).magnitude();
+        assert!((gauged.curvature_form(&x).magnitude() - before).abs() < 1e-9);
+        let psi = Perspective::new(3).with_state(vec![0.6, 0.0, 0.8]);
+        assert!((gauge.transform_perspective(&psi, &x).state.norm() - 1.0).abs() < 1e-12);
+
+        // ∂g against a central difference
+        let h = 1e-6;
+        let e0 = DVector::from_vec(vec![h, 0.0]);
+        let numeric = (gauge.at(&(&x + &e0)) - gauge.at(&(&x - &e0))) / (2.0 * h);
+        assert!((numeric - gauge.derivative(0, &x)).norm() < 1e-7);
+    }
+
+    #[test]
+    fn test_curvature_from_transformed_components() {
+        let connection = non_abelian();
+        let gauge = twisting_gauge();
+        let gauged = gauge.transform_connection(&connection);
+
+        for x in [DVector::from_vec(vec![0.5, 0.5]), DVector::from_vec(vec![-1.2, 0.3])] {
+            // dA' + A'∧A' lands on g F g⁻¹
+            let (g, g_inv) = (gauge.at(&x), gauge.inverse_at(&x));
+            let original = connection.curvature_form(&x);
+            let transformed = gauged.curvature_form(&x);
+            for mu in 0..2 {
+                for nu in 0..2 {
+                    let predicted = &g * original.component(mu, nu) * &g_inv;
+                    assert!((transformed.component(mu, nu) - predicted).norm() < 1e-9);
+                }
+            }
+
+            // ∂_0 ∂_1 g against a central difference of ∂_1 g
+            let h = 1e-6;
+            let e0 = DVector::from_vec(vec![h, 0.0]);
+            let numeric = (gauge.derivative(1, &(&x + &e0)) - gauge.derivative(1, &(&x - &e0))) / (2.0 * h);
+            assert!((numeric - gauge.second_derivative(0, 1, &x)).norm() < 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_singular_frame_rejected() {
+        let singular = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 4.0]);
+        assert!(matches!(
+            GaugeTransformation::constant(singular),
+            Err(TopologyError::SingularGauge { dimension: 2 })
+        ));
+        assert!(GaugeTransformation::constant(DMatrix::identity(2, 3)).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! Geodesic Spheres - subdivided shapes as finer concept lattices.
+//!
+//! Frequency-n subdivision cuts every triangle into n² smaller ones and
+//! pushes the new points out to the sphere. For the icosahedron this gives
+//! the icosphere:
+//!
+//! - vertices 10n² + 2, faces 20n², edges 30n²
+//!
+//! The kernel's rotations still permute the subdivided shape, so vertices,
+//! faces and edges fall into orbits. `OrbitLabels` names each element by
+//! its orbit, so the original 12 vertices stay one class however fine the
+//! sphere gets.
+
+use crate::kernel::{GenerativeKernel, Polyhedron};
+use nalgebra::Vector3;
+use std::collections::HashMap;
+
+impl Polyhedron {
+    /// Frequency-n geodesic subdivision (class I)
+    ///
+    /// Points along shared edges are created once, so neighbouring faces
+    /// share them. Frequency 1 returns the shape unchanged.
+    pub fn subdivide(&self, frequency: usize) -> Polyhedron {
+        let n = frequency.max(1);
+        let mut vertices = self.vertices.clone();
+        let mut on_edges: HashMap<(usize, usize, usize), usize> = HashMap::new();
+
+        let mut faces = Vec::new();
+        for &[a, b, c] in &self.faces {
+            let (va, vb, vc) = (self.vertices[a], self.vertices[b], self.vertices[c]);
+            // Index of the point with barycentric weights (i, j, k) / n
+            let mut interior: HashMap<(usize, usize), usize> = HashMap::new();
+            let mut point = |i: usize, j: usize, vertices: &mut Vec<Vector3<f64>>| -> usize {
+                let k = n - i - j;
+                let corners = [(a, i), (b, j), (c, k)];
+                let weighted: Vec<(usize, usize)> = corners.into_iter().filter(|&(_, w)| w > 0).collect();
+                match weighted.as_slice() {
+                    [(v, _)] => *v,
+                    [(u, wu), (v, _)] => {
+                        // Keyed from the lower vertex so both faces agree
+                        let key = if u < v { (*u, *v, n - wu) } else { (*v, *u, *wu) };
+                        *on_edges.entry(key).or_insert_with(|| {
+                            let t = key.2 as f64 / n as f64;
+                            vertices.push(self.vertices[key.0] * (1.0 - t) + self.vertices[key.1] * t);
+                            vertices.len() - 1
+                        })
+                    }
+                    _ => *interior.entry((i, j)).or_insert_with(|| {
+                        vertices.push((va * i as f64 + vb * j as f64 + vc * k as f64) / n as f64);
+                        vertices.len() - 1
+                    }),
+                }
+            };
+
+            // Rows run from corner a (i = n) towards the side bc (i = 0)
+            for i in (0..n).rev() {
+                for j in 0..n - i {
+                    let p = point(i + 1, j, &mut vertices);
+                    let q = point(i, j, &mut vertices);
+                    let r = point(i, j + 1, &mut vertices);
+                    faces.push([p, r, q]);
+                    if j + 1 < n - i {
+                        let s = point(i + 1, j + 1, &mut vertices);
+                        faces.push([p, s, r]);
+                    }
+                }
+            }
+        }
+
+        let name = if n == 1 { self.name.clone() } else { format!("{} (frequency {})", self.name, n) };
+        Polyhedron::from_faces(name, vertices, faces)
+    }
+
+    /// The frequency-n icosphere: 10n² + 2 vertices, 20n² faces, 30n² edges
+    pub fn icosphere(frequency: usize) -> Polyhedron {
+        Polyhedron::icosahedron().subdivide(frequency)
+    }
+}
+
+/// Orbit labels of a kernel's vertices, faces and edges
+///
+/// Orbits are numbered in order of their first member, so the orbit of
+/// vertex 0 is label 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrbitLabels {
+    /// Orbit of each vertex
+    pub vertices: Vec<usize>,
+    /// Orbit of each face
+    pub faces: Vec<usize>,
+    /// Orbit of each edge
+    pub edges: Vec<usize>,
+}
+
+impl OrbitLabels {
+    /// Number of vertex, face and edge orbits
+    pub fn counts(&self) -> (usize, usize, usize) {
+        let count = |labels: &[usize]| labels.iter().max().map_or(0, |&m| m + 1);
+        (count(&self.vertices), count(&self.faces), count(&self.edges))
+    }
+
+    /// Members of a vertex orbit
+    pub fn vertex_orbit(&self, label: usize) -> Vec<usize> {
+        (0..self.vertices.len()).filter(|&v| self.vertices[v] == label).collect()
+    }
+}
+
+impl GenerativeKernel {
+    /// The same kernel on its shape subdivided at frequency n
+    ///
+    /// The group is unchanged; the generated space grows with the shape.
+    pub fn subdivide(&self, frequency: usize) -> GenerativeKernel {
+        let mut kernel = self.clone();
+        kernel.geometry = self.geometry.subdivide(frequency);
+        kernel
+    }
+
+    /// The icosahedral kernel on the frequency-n icosphere
+    pub fn icosphere(frequency: usize) -> GenerativeKernel {
+        GenerativeKernel::icosahedral().subdivide(frequency)
+    }
+
+    /// Label every vertex, face and edge by its orbit under the kernel
+    pub fn orbit_labels(&self) -> OrbitLabels {
+        let permutations: Vec<Vec<usize>> = self.elements.iter()
+            .filter_map(|g| self.vertex_permutation(g))
+            .collect();
+        let label = |cells: Vec<Vec<usize>>| -> Vec<usize> {
+            let index: HashMap<Vec<usize>, usize> = cells.iter().cloned().enumerate().map(|(i, c)| (c, i)).collect();
+            let mut labels = vec![usize::MAX; cells.len()];
+            let mut next = 0;
+            for start in 0..cells.len() {
+                if labels[start] != usize::MAX {
+                    continue;
+                }
+                for p in &permutations {
+                    let mut image: Vec<usize> = cells[start].iter().map(|&v| p[v]).collect();
+                    image.sort_unstable();
+                    if let Some(&i) = index.get(&image) {
+                        labels[i] = next;
+                    }
+                }
+                labels[start] = next;
+                next += 1;
+            }
+            labels
+        };
+        let sorted = |cell: &[usize]| {
+            let mut cell = cell.to_vec();
+            cell.sort_unstable();
+            cell
+        };
+
+        let geometry = &self.geometry;
+        OrbitLabels {
+            vertices: label((0..geometry.vertices.len()).map(|v| vec![v]).collect()),
+            faces: label(geometry.faces.iter().map(|f| sorted(f)).collect()),
+            edges: label(geometry.edges.iter().map(|e| sorted(e)).collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_icosphere_counts() {
+        for n in 1..=4 {
+            let sphere = Polyhedron::icosphere(n);
+            assert_eq!(sphere.vertices.len(), 10 * n * n + 2);
+            assert_eq!(sphere.faces.len(), 20 * n * n);
+            assert_eq!(sphere.edges.len(), 30 * n * n);
+            assert!(sphere.vertices.iter().all(|v| (v.norm() - 1.0).abs() < 1e-12));
+        }
+        assert_eq!(Polyhedron::icosphere(1).name, "icosahedron");
+        assert_eq!(Polyhedron::icosphere(3).name, "icosahedron (frequency 3)");
+    }
+
+    #[test]
+    fn test_subdivision_keeps_orientation_and_vertices() {
+        let ico = Polyhedron::icosahedron();
+        let sphere = ico.subdivide(3);
+        assert!((0..12).all(|v| (sphere.vertices[v] - ico.vertices[v]).norm() < 1e-12));
+        // Every small face points outward like its parent
+        for face in &sphere.faces {
+            let [a, b, c] = face.map(|v| sphere.vertices[v]);
+            assert!((b - a).cross(&(c - a)).dot(&(a + b + c)) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_orbit_labels_survive_subdivision() {
+        let coarse = GenerativeKernel::icosahedral().orbit_labels();
+        assert_eq!(coarse.counts(), (1, 1, 1));
+
+        // Frequency 2: vertices 12 + 30 (edge midpoints), faces 20 centres + 60, edges 60 + 60
+        let kernel = GenerativeKernel::icosphere(2);
+        let labels = kernel.orbit_labels();
+        assert_eq!(labels.counts(), (2, 2, 2));
+        assert_eq!(labels.vertex_orbit(0), (0..12).collect::<Vec<_>>());
+        assert_eq!(labels.vertex_orbit(1).len(), 30);
+
+        // Frequency 3 adds the face centres as a 20-vertex orbit
+        let labels = GenerativeKernel::icosphere(3).orbit_labels();
+        let sizes: Vec<usize> = (0..labels.counts().0).map(|l| labels.vertex_orbit(l).len()).collect();
+        assert_eq!(sizes, vec![12, 60, 20]);
+    }
+
+    #[test]
+    fn test_generated_space_scales() {
+        let kernel = GenerativeKernel::icosphere(4);
+        let space = kernel.generate_space();
+        assert_eq!(space.pitch_classes.len(), 162);
+        assert_eq!(space.rhythmic_cells.len(), 320);
+        assert_eq!(space.timbral_morphs.len(), 480);
+        assert_eq!(space.symmetry_order, 60);
+        assert!(kernel.verify().is_ok());
+        assert_eq!(GenerativeKernel::tetrahedral().subdivide(2).geometry.vertices.len(), 10);
+    }
+}
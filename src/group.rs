@@ -0,0 +1,710 @@
+//! Finite Rotation Groups - the kernel's symmetries, checked rather than assumed.
+//!
+//! A group is generated by closure from a few rotations, deduplicated by
+//! matrix, and tabulated. The Cayley table makes the group axioms
+//! checkable. Conjugacy classes and characters then describe the group
+//! independently of any labelling of its elements.
+
+use crate::kernel::GroupElement;
+use crate::types::*;
+use nalgebra::{Matrix3, Vector3};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::f64::consts::PI;
+
+/// Largest group `FiniteGroup::generate` will enumerate
+///
+/// The Cayley table holds order² entries, so this is about the largest
+/// group that tabulates in around a second.
+pub const MAX_GROUP_ORDER: usize = 2_000;
+
+/// Matrices closer than this are the same element
+const MATRIX_TOLERANCE: f64 = 1e-9;
+
+/// Grid spacing for hashing matrix entries
+const MATRIX_GRID: f64 = 1e-6;
+
+/// Hash lookup of rotation matrices by their entries rounded to a grid
+///
+/// An entry within `MATRIX_TOLERANCE` of a cell boundary is looked up in
+/// both neighbouring cells, so rounding never splits one element in two.
+#[derive(Debug, Clone, Default)]
+struct MatrixIndex {
+    cells: HashMap<[i64; 9], Vec<usize>>,
+}
+
+impl MatrixIndex {
+    fn cell(m: &Matrix3<f64>) -> [i64; 9] {
+        // Rounding puts the common entries 0, ±½ and ±1 at cell centres
+        std::array::from_fn(|k| (m[k] / MATRIX_GRID).round() as i64)
+    }
+
+    fn insert(&mut self, m: &Matrix3<f64>, index: usize) {
+        self.cells.entry(Self::cell(m)).or_default().push(index);
+    }
+
+    /// Index of the matrix in `matrices` within tolerance of `m`
+    fn find(&self, m: &Matrix3<f64>, matrices: impl Fn(usize) -> Matrix3<f64>) -> Option<usize> {
+        let base = Self::cell(m);
+        // Entries near a boundary may have been filed in the adjacent cell
+        let mut ambiguous = [(0, 0); 9];
+        let mut count = 0;
+        for k in 0..9 {
+            let offset = m[k] / MATRIX_GRID - base[k] as f64;
+            if (0.5 - offset.abs()) * MATRIX_GRID < MATRIX_TOLERANCE {
+                ambiguous[count] = (k, offset.signum() as i64);
+                count += 1;
+            }
+        }
+        (0..1usize << count)
+            .map(|mask| {
+                let mut key = base;
+                for (bit, &(k, step)) in ambiguous[..count].iter().enumerate() {
+                    if mask & (1 << bit) != 0 {
+                        key[k] += step;
+                    }
+                }
+                key
+            })
+            .filter_map(|key| self.cells.get(&key))
+            .flatten()
+            .copied()
+            .find(|&i| (matrices(i) - m).norm() < MATRIX_TOLERANCE)
+    }
+}
+
+/// Rotation angle of a 3×3 rotation, from its trace and skew part
+pub(crate) fn rotation_angle(m: &Matrix3<f64>) -> f64 {
+    // atan2 stays accurate near 0 and π, where acos of the trace does not
+    let skew = Vector3::new(m[(2, 1)] - m[(1, 2)], m[(0, 2)] - m[(2, 0)], m[(1, 0)] - m[(0, 1)]);
+    (skew.norm() / 2.0).atan2((m.trace() - 1.0) / 2.0)
+}
+
+/// A finite group of rotations with its multiplication table
+///
+/// Element 0 is always the identity.
+#[derive(Debug, Clone)]
+pub struct FiniteGroup {
+    /// The elements
+    pub elements: Vec<GroupElement>,
+    products: Vec<Vec<usize>>,
+    inverses: Vec<usize>,
+    index: MatrixIndex,
+}
+
+/// A conjugacy class
+#[derive(Debug, Clone)]
+pub struct ConjugacyClass {
+    /// Class name, e.g. "12C5"
+    pub name: String,
+    /// Member element indices
+    pub members: Vec<usize>,
+    /// Order shared by every member
+    pub element_order: usize,
+    /// Rotation angle shared by every member
+    pub angle: f64,
+}
+
+impl ConjugacyClass {
+    /// Number of members
+    pub fn size(&self) -> usize {
+        self.members.len()
+    }
+}
+
+/// Characters of the irreducible representations, one column per class
+#[derive(Debug, Clone)]
+pub struct CharacterTable {
+    /// Class names, in column order
+    pub classes: Vec<String>,
+    /// Class sizes, in column order
+    pub class_sizes: Vec<usize>,
+    /// (irrep name, character on each class)
+    pub irreps: Vec<(String, Vec<f64>)>,
+}
+
+impl CharacterTable {
+    /// Order of the group
+    pub fn group_order(&self) -> usize {
+        self.class_sizes.iter().sum()
+    }
+
+    /// Weighted inner product ⟨χ_a, χ_b⟩ = (1/|G|) Σ_c |c| χ_a(c) χ_b(c)
+    pub fn inner_product(&self, a: usize, b: usize) -> f64 {
+        let (chi_a, chi_b) = (&self.irreps[a].1, &self.irreps[b].1);
+        let sum: f64 = self.class_sizes.iter().zip(chi_a.iter().zip(chi_b))
+            .map(|(&size, (x, y))| size as f64 * x * y)
+            .sum();
+        sum / self.group_order() as f64
+    }
+
+    /// Check row orthonormality and Σ dim² = |G|
+    pub fn is_orthogonal(&self, tolerance: f64) -> bool {
+        let n = self.irreps.len();
+        let rows = (0..n).all(|a| {
+            (0..n).all(|b| {
+                let expected = if a == b { 1.0 } else { 0.0 };
+                (self.inner_product(a, b) - expected).abs() < tolerance
+            })
+        });
+        let dimensions: f64 = self.irreps.iter().map(|(_, chi)| chi[0] * chi[0]).sum();
+        rows && (dimensions - self.group_order() as f64).abs() < tolerance
+    }
+}
+
+impl FiniteGroup {
+    /// The closure of a set of generators
+    pub fn generate(generators: &[GroupElement]) -> Result<Self, TopologyError> {
+        Self::tabulate(Self::generate_elements(generators)?)
+    }
+
+    /// The named elements generated by a set of rotations, without tabulating them
+    pub(crate) fn generate_elements(generators: &[GroupElement]) -> Result<Vec<GroupElement>, TopologyError> {
+        let mut matrices = vec![Matrix3::identity()];
+        let mut index = MatrixIndex::default();
+        index.insert(&matrices[0], 0);
+        let mut frontier = 0;
+        while frontier < matrices.len() {
+            let current = matrices[frontier];
+            for g in generators {
+                let product = g.matrix * current;
+                if index.find(&product, |i| matrices[i]).is_none() {
+                    if matrices.len() >= MAX_GROUP_ORDER {
+                        return Err(TopologyError::group_structure(format!("generators do not close within {} elements", MAX_GROUP_ORDER)));
+                    }
+                    index.insert(&product, matrices.len());
+                    matrices.push(product);
+                }
+            }
+            frontier += 1;
+        }
+        Ok(name_elements(matrices))
+    }
+
+    /// A group from a complete element list (duplicates are rejected)
+    pub fn from_elements(elements: Vec<GroupElement>) -> Result<Self, TopologyError> {
+        if elements.len() > MAX_GROUP_ORDER {
+            return Err(TopologyError::group_structure(format!("{} elements exceed the limit of {}", elements.len(), MAX_GROUP_ORDER)));
+        }
+        let mut index = MatrixIndex::default();
+        for (i, a) in elements.iter().enumerate() {
+            if index.find(&a.matrix, |j| elements[j].matrix).is_some() {
+                return Err(TopologyError::group_structure(format!("element {} ({}) is a duplicate", i, a.name)));
+            }
+            index.insert(&a.matrix, i);
+        }
+        let mut elements = elements;
+        match elements.iter().position(|e| (e.matrix - Matrix3::identity()).norm() < MATRIX_TOLERANCE) {
+            Some(i) => elements.swap(0, i),
+            None => return Err(TopologyError::group_structure("no identity element")),
+        }
+        Self::tabulate(elements)
+    }
+
+    /// Build the Cayley table; fails if a product leaves the set
+    fn tabulate(elements: Vec<GroupElement>) -> Result<Self, TopologyError> {
+        let mut index = MatrixIndex::default();
+        for (i, e) in elements.iter().enumerate() {
+            index.insert(&e.matrix, i);
+        }
+        let find = |m: &Matrix3<f64>| index.find(m, |i| elements[i].matrix);
+        let mut products = Vec::with_capacity(elements.len());
+        for (i, a) in elements.iter().enumerate() {
+            let row = elements.iter().enumerate()
+                .map(|(j, b)| {
+                    find(&(a.matrix * b.matrix))
+                        .ok_or_else(|| TopologyError::group_structure(format!("{} · {} is not in the set ({}, {})", a.name, b.name, i, j)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            products.push(row);
+        }
+        let inverses = products.iter().enumerate()
+            .map(|(i, row)| row.iter().position(|&p| p == 0).ok_or_else(|| TopologyError::group_structure(format!("element {} has no inverse", i))))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { elements, products, inverses, index })
+    }
+
+    /// Number of elements
+    pub fn order(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Index of the identity (always 0)
+    pub fn identity(&self) -> usize {
+        0
+    }
+
+    /// Index of the element with the given matrix
+    pub fn index_of(&self, matrix: &Matrix3<f64>) -> Option<usize> {
+        self.index.find(matrix, |i| self.elements[i].matrix)
+    }
+
+    /// Index of a · b
+    pub fn multiply(&self, a: usize, b: usize) -> usize {
+        self.products[a][b]
+    }
+
+    /// Index of a⁻¹
+    pub fn inverse(&self, a: usize) -> usize {
+        self.inverses[a]
+    }
+
+    /// The multiplication table: `cayley_table()[a][b]` is the index of a · b
+    pub fn cayley_table(&self) -> &[Vec<usize>] {
+        &self.products
+    }
+
+    /// Conjugacy classes, ordered by element order then angle
+    pub fn conjugacy_classes(&self) -> Vec<ConjugacyClass> {
+        let mut assigned = vec![false; self.order()];
+        let mut classes = Vec::new();
+        for x in 0..self.order() {
+            if assigned[x] {
+                continue;
+            }
+            let mut members: Vec<usize> = (0..self.order())
+                .map(|g| self.multiply(self.multiply(g, x), self.inverse(g)))
+                .collect();
+            members.sort_unstable();
+            members.dedup();
+            for &m in &members {
+                assigned[m] = true;
+            }
+
+            let order = self.element_order(x);
+            let angle = rotation_angle(&self.elements[x].matrix);
+            let turns = (angle * order as f64 / (2.0 * PI)).round() as usize;
+            let name = match (order, turns) {
+                (1, _) => "e".to_string(),
+                (n, 1) => format!("{}C{}", members.len(), n),
+                (n, k) => format!("{}C{}^{}", members.len(), n, k),
+            };
+            classes.push(ConjugacyClass { name, members, element_order: order, angle });
+        }
+        classes.sort_by(|a, b| a.element_order.cmp(&b.element_order).then(a.angle.total_cmp(&b.angle)));
+        classes
+    }
+
+    /// Character table, for groups whose table is known (currently A₅)
+    ///
+    /// Classes are matched by element order and rotation angle, so the
+    /// result does not depend on how the elements were produced.
+    pub fn character_table(&self) -> Option<CharacterTable> {
+        let classes = self.conjugacy_classes();
+        let sizes: Vec<usize> = classes.iter().map(ConjugacyClass::size).collect();
+        if self.order() != 60 || sizes != [1, 15, 20, 12, 12] {
+            return None;
+        }
+
+        // Columns: e, 15C2, 20C3, 12C5 (72°), 12C5^2 (144°)
+        let phi = crate::kernel::PHI;
+        let irreps = vec![
+            ("1", vec![1.0, 1.0, 1.0, 1.0, 1.0]),
+            ("3", vec![3.0, -1.0, 0.0, phi, 1.0 - phi]),
+            ("3'", vec![3.0, -1.0, 0.0, 1.0 - phi, phi]),
+            ("4", vec![4.0, 0.0, 1.0, -1.0, -1.0]),
+            ("5", vec![5.0, 1.0, -1.0, 0.0, 0.0]),
+        ];
+        Some(CharacterTable {
+            classes: classes.iter().map(|c| c.name.clone()).collect(),
+            class_sizes: sizes,
+            irreps: irreps.into_iter().map(|(n, chi)| (n.to_string(), chi)).collect(),
+        })
+    }
+
+    /// Check the group axioms on the Cayley table
+    ///
+    /// Closure and inverses hold by construction; this re-checks them,
+    /// along with the identity, associativity and the Latin-square property.
+    /// If a character table is known, it also checks that the table is
+    /// orthogonal and that the defining 3D representation has character "3".
+    pub fn verify(&self) -> Result<(), TopologyError> {
+        let n = self.order();
+        for a in 0..n {
+            if self.multiply(0, a) != a || self.multiply(a, 0) != a {
+                return Err(TopologyError::group_structure(format!("element 0 is not an identity for {}", a)));
+            }
+            if self.multiply(a, self.inverse(a)) != 0 || self.multiply(self.inverse(a), a) != 0 {
+                return Err(TopologyError::group_structure(format!("inverse of {} is wrong", a)));
+            }
+            let mut seen = vec![false; n];
+            for b in 0..n {
+                let p = self.multiply(a, b);
+                if p >= n || seen[p] {
+                    return Err(TopologyError::group_structure(format!("row {} of the Cayley table is not a permutation", a)));
+                }
+                seen[p] = true;
+                for c in 0..n {
+                    if self.multiply(p, c) != self.multiply(a, self.multiply(b, c)) {
+                        return Err(TopologyError::group_structure(format!("({} {}) {} is not associative", a, b, c)));
+                    }
+                }
+            }
+        }
+
+        if let Some(table) = self.character_table() {
+            if !table.is_orthogonal(1e-9) {
+                return Err(TopologyError::group_structure("character table is not orthogonal"));
+            }
+            let defining: Vec<f64> = self.conjugacy_classes().iter()
+                .map(|c| self.elements[c.members[0]].matrix.trace())
+                .collect();
+            if !table.irreps.iter().any(|(_, chi)| chi.iter().zip(&defining).all(|(x, y)| (x - y).abs() < 1e-9)) {
+                return Err(TopologyError::group_structure("the rotation representation is not irreducible"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A subgroup, as sorted indices into its parent group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subgroup {
+    /// Isomorphism type, e.g. "A4", "D5", "Z3", "V4"
+    pub name: String,
+    /// Member indices in the parent group, sorted
+    pub members: Vec<usize>,
+}
+
+impl Subgroup {
+    /// Number of elements
+    pub fn order(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Check membership of a parent element
+    pub fn contains(&self, element: usize) -> bool {
+        self.members.binary_search(&element).is_ok()
+    }
+
+    /// Check if this subgroup lies inside another
+    pub fn is_subgroup_of(&self, other: &Subgroup) -> bool {
+        self.members.iter().all(|&m| other.contains(m))
+    }
+}
+
+/// All subgroups ordered by size, with their covering relation
+#[derive(Debug, Clone)]
+pub struct SubgroupLattice {
+    /// Subgroups, smallest first (trivial first, whole group last)
+    pub subgroups: Vec<Subgroup>,
+    /// (H, K) where H is a maximal subgroup of K
+    pub covers: Vec<(usize, usize)>,
+}
+
+impl SubgroupLattice {
+    /// Indices of the maximal subgroups of subgroup `index`
+    pub fn maximal_subgroups(&self, index: usize) -> Vec<usize> {
+        self.covers.iter().filter(|&&(_, k)| k == index).map(|&(h, _)| h).collect()
+    }
+
+    /// How many subgroups of each isomorphism type
+    pub fn census(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for subgroup in &self.subgroups {
+            *counts.entry(subgroup.name.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// A longest chain from the whole group down to the trivial subgroup
+    pub fn longest_chain(&self) -> Vec<Subgroup> {
+        // depth[i]: longest chain from subgroup i down to the trivial one
+        let mut depth = vec![0usize; self.subgroups.len()];
+        let mut next = vec![None; self.subgroups.len()];
+        for k in 0..self.subgroups.len() {
+            for h in self.maximal_subgroups(k) {
+                if depth[h] + 1 > depth[k] {
+                    depth[k] = depth[h] + 1;
+                    next[k] = Some(h);
+                }
+            }
+        }
+        let mut chain = Vec::new();
+        let mut current = self.subgroups.len().checked_sub(1);
+        while let Some(i) = current {
+            chain.push(self.subgroups[i].clone());
+            current = next[i];
+        }
+        chain
+    }
+}
+
+impl FiniteGroup {
+    /// Order of an element, from the Cayley table
+    pub fn element_order(&self, a: usize) -> usize {
+        let mut power = a;
+        let mut order = 1;
+        while power != 0 {
+            power = self.multiply(power, a);
+            order += 1;
+        }
+        order
+    }
+
+    /// The subgroup generated by some elements
+    pub fn closure(&self, generators: &[usize]) -> Subgroup {
+        let mut present = vec![false; self.order()];
+        present[0] = true;
+        let mut members = vec![0];
+        let mut i = 0;
+        while i < members.len() {
+            let x = members[i];
+            for &g in generators {
+                let product = self.multiply(x, g);
+                if !present[product] {
+                    present[product] = true;
+                    members.push(product);
+                }
+            }
+            i += 1;
+        }
+        members.sort_unstable();
+        Subgroup { name: self.isomorphism_type(&members), members }
+    }
+
+    /// Name the isomorphism type of a subgroup from its element orders
+    ///
+    /// Recognizes the types that occur among finite rotation groups.
+    pub(crate) fn isomorphism_type(&self, members: &[usize]) -> String {
+        let n = members.len();
+        let orders: Vec<usize> = members.iter().map(|&m| self.element_order(m)).collect();
+        let largest = orders.iter().copied().max().unwrap_or(1);
+        let involutions = orders.iter().filter(|&&o| o == 2).count();
+        let abelian = members.iter().all(|&a| members.iter().all(|&b| self.multiply(a, b) == self.multiply(b, a)));
+
+        match n {
+            1 => "1".to_string(),
+            _ if largest == n => format!("Z{}", n),
+            4 if abelian => "V4".to_string(),
+            12 if largest == 3 && involutions == 3 => "A4".to_string(),
+            24 if largest == 4 && involutions == 9 => "S4".to_string(),
+            60 if largest == 5 && involutions == 15 => "A5".to_string(),
+            _ if n % 2 == 0 && largest == n / 2 && involutions == n / 2 + (n / 2 + 1) % 2 => format!("D{}", n / 2),
+            _ => format!("G{}", n),
+        }
+    }
+
+    /// Every subgroup, smallest first
+    ///
+    /// Starts from the cyclic subgroups and closes under joins with single
+    /// elements until nothing new appears.
+    pub fn subgroups(&self) -> Vec<Subgroup> {
+        let mut found: BTreeSet<Vec<usize>> = BTreeSet::new();
+        let mut queue: Vec<Vec<usize>> = Vec::new();
+        for a in 0..self.order() {
+            let cyclic = self.closure(&[a]).members;
+            if found.insert(cyclic.clone()) {
+                queue.push(cyclic);
+            }
+        }
+        while let Some(members) = queue.pop() {
+            for g in 0..self.order() {
+                if members.binary_search(&g).is_ok() {
+                    continue;
+                }
+                let mut generators = members.clone();
+                generators.push(g);
+                let joined = self.closure(&generators).members;
+                if found.insert(joined.clone()) {
+                    queue.push(joined);
+                }
+            }
+        }
+
+        let mut subgroups: Vec<Subgroup> = found.into_iter()
+            .map(|members| Subgroup { name: self.isomorphism_type(&members), members })
+            .collect();
+        subgroups.sort_by(|a, b| a.order().cmp(&b.order()).then(a.members.cmp(&b.members)));
+        subgroups
+    }
+
+    /// The subgroup lattice with its covering relation
+    pub fn subgroup_lattice(&self) -> SubgroupLattice {
+        let subgroups = self.subgroups();
+        let mut covers = Vec::new();
+        for (k, big) in subgroups.iter().enumerate() {
+            for (h, small) in subgroups[..k].iter().enumerate() {
+                if small.order() == big.order() || !small.is_subgroup_of(big) {
+                    continue;
+                }
+                let between = subgroups.iter().any(|mid| {
+                    mid.order() > small.order() && mid.order() < big.order()
+                        && small.is_subgroup_of(mid) && mid.is_subgroup_of(big)
+                });
+                if !between {
+                    covers.push((h, k));
+                }
+            }
+        }
+        SubgroupLattice { subgroups, covers }
+    }
+
+    /// A subgroup as a group in its own right
+    pub fn restrict(&self, subgroup: &Subgroup) -> Result<FiniteGroup, TopologyError> {
+        FiniteGroup::from_elements(subgroup.members.iter().map(|&m| self.elements[m].clone()).collect())
+    }
+
+    /// The distinct images of a point
+    pub fn orbit(&self, point: &Vector3<f64>) -> Vec<Vector3<f64>> {
+        let mut orbit: Vec<Vector3<f64>> = Vec::new();
+        for element in &self.elements {
+            let image = element.apply(point);
+            if !orbit.iter().any(|p| (p - image).norm() < MATRIX_TOLERANCE) {
+                orbit.push(image);
+            }
+        }
+        orbit
+    }
+
+    /// Elements fixing a point
+    pub fn stabilizer(&self, point: &Vector3<f64>) -> Subgroup {
+        self.setwise_stabilizer(std::slice::from_ref(point))
+    }
+
+    /// Elements mapping a finite point set onto itself
+    pub fn setwise_stabilizer(&self, points: &[Vector3<f64>]) -> Subgroup {
+        let members: Vec<usize> = (0..self.order())
+            .filter(|&i| {
+                points.iter().all(|p| {
+                    let image = self.elements[i].apply(p);
+                    points.iter().any(|q| (q - image).norm() < MATRIX_TOLERANCE)
+                })
+            })
+            .collect();
+        self.closure(&members)
+    }
+}
+
+/// Wrap matrices as named elements: identity first, then by order and angle
+fn name_elements(matrices: Vec<Matrix3<f64>>) -> Vec<GroupElement> {
+    // The matrices closed under multiplication, so every order is finite
+    let mut elements: Vec<(usize, GroupElement)> = matrices.into_iter()
+        .map(|matrix| {
+            let element = GroupElement { matrix, name: String::new() };
+            (element.order().expect("elements of a closed group have finite order"), element)
+        })
+        .collect();
+    elements.sort_by(|(m, a), (n, b)| {
+        m.cmp(n).then(rotation_angle(&a.matrix).total_cmp(&rotation_angle(&b.matrix)))
+    });
+
+    let mut counts: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    for (order, element) in &mut elements {
+        let turns = (rotation_angle(&element.matrix) * *order as f64 / (2.0 * PI)).round() as usize;
+        let count = counts.entry((*order, turns)).or_insert(0);
+        element.name = match (*order, turns) {
+            (1, _) => "e".to_string(),
+            (n, 1) => format!("C{}_{}", n, count),
+            (n, k) => format!("C{}^{}_{}", n, k, count),
+        };
+        *count += 1;
+    }
+    elements.into_iter().map(|(_, element)| element).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::GenerativeKernel;
+
+    #[test]
+    fn test_icosahedral_group_verifies() {
+        let kernel = GenerativeKernel::icosahedral();
+        let group = FiniteGroup::from_elements(kernel.elements.clone()).unwrap();
+        assert_eq!(group.order(), 60);
+        assert!(group.verify().is_ok());
+        assert!(kernel.verify().is_ok());
+    }
+
+    #[test]
+    fn test_conjugacy_classes_of_a5() {
+        let group = FiniteGroup::from_elements(GenerativeKernel::icosahedral().elements).unwrap();
+        let names: Vec<String> = group.conjugacy_classes().iter().map(|c| c.name.clone()).collect();
+        assert_eq!(names, vec!["e", "15C2", "20C3", "12C5", "12C5^2"]);
+    }
+
+    #[test]
+    fn test_character_table() {
+        let group = FiniteGroup::from_elements(GenerativeKernel::icosahedral().elements).unwrap();
+        let table = group.character_table().unwrap();
+        assert!(table.is_orthogonal(1e-12));
+        assert_eq!(table.irreps.len(), 5);
+        assert!((table.inner_product(1, 2)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cayley_table_inverses() {
+        let group = FiniteGroup::from_elements(GenerativeKernel::icosahedral().elements).unwrap();
+        for a in 0..group.order() {
+            let inverse = group.inverse(a);
+            assert!((group.elements[inverse].matrix - group.elements[a].matrix.transpose()).norm() < 1e-9);
+            assert_eq!(group.cayley_table()[a][inverse], group.identity());
+        }
+    }
+
+    #[test]
+    fn test_cyclic_closure_and_rejection() {
+        let quarter = GroupElement::rotation(Vector3::z(), PI / 2.0, "C4");
+        let cyclic = FiniteGroup::generate(std::slice::from_ref(&quarter)).unwrap();
+        assert_eq!(cyclic.order(), 4);
+        assert!(cyclic.verify().is_ok());
+        assert!(cyclic.character_table().is_none());
+
+        // Half of a cyclic group is not closed
+        let partial = vec![GroupElement::identity(), quarter];
+        assert!(matches!(FiniteGroup::from_elements(partial), Err(TopologyError::GroupStructure { .. })));
+
+        // The largest cyclic group closes; one more element is refused
+        let step = GroupElement::rotation(Vector3::z(), 2.0 * PI / MAX_GROUP_ORDER as f64, "Cmax");
+        assert_eq!(FiniteGroup::generate_elements(&[step]).unwrap().len(), MAX_GROUP_ORDER);
+        let past = GroupElement::rotation(Vector3::z(), 2.0 * PI / (MAX_GROUP_ORDER + 1) as f64, "Cmax+1");
+        assert!(FiniteGroup::generate_elements(&[past]).is_err());
+
+        // An irrational rotation has no order and never closes
+        let irrational = GroupElement::rotation(Vector3::z(), 1.0, "irrational");
+        assert!(matches!(irrational.order(), Err(TopologyError::GroupStructure { .. })));
+        assert!(FiniteGroup::generate(&[irrational]).is_err());
+    }
+
+    #[test]
+    fn test_subgroup_lattice_of_a5() {
+        let group = GenerativeKernel::icosahedral().group_structure().unwrap();
+        let lattice = group.subgroup_lattice();
+        assert_eq!(lattice.subgroups.len(), 59);
+
+        let census = lattice.census();
+        let expected = [("1", 1), ("Z2", 15), ("Z3", 10), ("V4", 5), ("Z5", 6), ("D3", 10), ("D5", 6), ("A4", 5), ("A5", 1)];
+        for (name, count) in expected {
+            assert_eq!(census.get(name), Some(&count), "{}", name);
+        }
+
+        // A₅'s maximal subgroups are A₄, D₅ and D₃
+        let top = lattice.subgroups.len() - 1;
+        let mut maximal: Vec<String> = lattice.maximal_subgroups(top).iter().map(|&i| lattice.subgroups[i].name.clone()).collect();
+        maximal.sort();
+        maximal.dedup();
+        assert_eq!(maximal, vec!["A4", "D3", "D5"]);
+
+        let chain: Vec<String> = lattice.longest_chain().into_iter().map(|s| s.name).collect();
+        assert_eq!(chain, vec!["A5", "A4", "V4", "Z2", "1"]);
+    }
+
+    #[test]
+    fn test_orbit_stabilizer() {
+        let kernel = GenerativeKernel::icosahedral();
+        let group = kernel.group_structure().unwrap();
+        let vertex = kernel.geometry.vertices[0];
+        let face = kernel.geometry.faces[0].iter().map(|&v| kernel.geometry.vertices[v]).sum::<Vector3<f64>>();
+        let edge = kernel.geometry.vertices[0] + kernel.geometry.vertices[2];
+        let generic = Vector3::new(0.3, -0.2, 0.9);
+
+        for (point, orbit, stabilizer) in [(vertex, 12, "Z5"), (face, 20, "Z3"), (edge, 30, "Z2"), (generic, 60, "1")] {
+            let found = group.stabilizer(&point);
+            assert_eq!(group.orbit(&point).len(), orbit);
+            assert_eq!(found.name, stabilizer);
+            assert_eq!(orbit * found.order(), group.order());
+        }
+        assert_eq!(group.setwise_stabilizer(&[vertex, -vertex]).name, "D5");
+        assert_eq!(group.restrict(&group.stabilizer(&face)).unwrap().order(), 3);
+    }
+}
@@ -0,0 +1,423 @@
+//! Simplicial homology over GF(2) - the algebra behind the Betti numbers.
+//!
+//! A `ChainComplex` stores cells together with a filtration value. Column
+//! reduction of the boundary matrix, taken in filtration order, yields the
+//! Betti numbers, the persistence intervals and a representative cycle for
+//! every hole.
+//!
+//! Simplices are vertex lists. Their faces are found by deleting one vertex
+//! while keeping the order, so the same machinery handles unordered simplices
+//! (sorted lists) and ordered ones (directed flag complexes).
+
+use crate::simplex::ConceptComplex;
+use std::collections::HashMap;
+
+/// A cell of the chain complex (a simplex with a filtration value)
+#[derive(Debug, Clone)]
+pub struct Cell {
+    /// Vertices of the simplex, in the complex's order
+    pub vertices: Vec<usize>,
+    /// Filtration value at which the cell appears
+    pub filtration: f64,
+    /// Indices of the codimension-1 faces
+    pub boundary: Vec<usize>,
+}
+
+impl Cell {
+    /// Dimension = number of vertices - 1
+    pub fn dimension(&self) -> usize {
+        self.vertices.len().saturating_sub(1)
+    }
+}
+
+/// A persistence interval: a hole born at `birth` and filled at `death`
+#[derive(Debug, Clone)]
+pub struct PersistenceInterval {
+    /// Homological dimension (0 = component, 1 = loop, 2 = void)
+    pub dimension: usize,
+    /// Filtration value at which the hole appears
+    pub birth: f64,
+    /// Filtration value at which the hole is filled (∞ if never)
+    pub death: f64,
+    /// A cycle representing the hole, as simplices
+    pub representative: Vec<Vec<usize>>,
+}
+
+impl PersistenceInterval {
+    /// Holes that are never filled
+    pub fn is_essential(&self) -> bool {
+        self.death.is_infinite()
+    }
+
+    /// How long the hole persists
+    pub fn lifetime(&self) -> f64 {
+        self.death - self.birth
+    }
+}
+
+/// A filtered chain complex over GF(2)
+#[derive(Debug, Clone, Default)]
+pub struct ChainComplex {
+    /// All cells; faces always precede their cofaces
+    pub cells: Vec<Cell>,
+    index: HashMap<Vec<usize>, usize>,
+}
+
+impl ChainComplex {
+    /// An empty complex
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Check if there are no cells
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Look up a simplex by its vertex list
+    pub fn find(&self, vertices: &[usize]) -> Option<usize> {
+        self.index.get(vertices).copied()
+    }
+
+    /// Highest cell dimension (0 if empty)
+    pub fn max_dimension(&self) -> usize {
+        self.cells.iter().map(Cell::dimension).max().unwrap_or(0)
+    }
+
+    /// Count the cells of each dimension
+    pub fn cell_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.max_dimension() + 1];
+        for cell in &self.cells {
+            counts[cell.dimension()] += 1;
+        }
+        counts
+    }
+
+    /// Add a simplex and, recursively, all of its faces
+    ///
+    /// Missing faces appear at the same filtration value. A simplex never
+    /// appears before its faces: its value is raised to theirs if needed.
+    pub fn add_simplex(&mut self, vertices: Vec<usize>, filtration: f64) -> usize {
+        if let Some(&existing) = self.index.get(&vertices) {
+            return existing;
+        }
+
+        let mut boundary = Vec::new();
+        let mut value = filtration;
+        if vertices.len() > 1 {
+            for i in 0..vertices.len() {
+                let mut face = vertices.clone();
+                face.remove(i);
+                let f = self.add_simplex(face, filtration);
+                value = value.max(self.cells[f].filtration);
+                boundary.push(f);
+            }
+        }
+
+        let id = self.cells.len();
+        self.index.insert(vertices.clone(), id);
+        self.cells.push(Cell { vertices, filtration: value, boundary });
+        id
+    }
+
+    /// Add a cell with an explicit boundary (e.g. a Morse cell)
+    ///
+    /// The boundary must refer to existing cells.
+    pub fn add_cell(&mut self, vertices: Vec<usize>, filtration: f64, boundary: Vec<usize>) -> usize {
+        let id = self.cells.len();
+        self.index.insert(vertices.clone(), id);
+        self.cells.push(Cell { vertices, filtration, boundary });
+        id
+    }
+
+    /// Check if a chain (set of cell indices, all of one dimension) bounds
+    ///
+    /// A cycle that does not bound is a genuine hole.
+    pub fn is_boundary(&self, chain: &[usize]) -> bool {
+        let mut target: Vec<usize> = chain.to_vec();
+        target.sort_unstable();
+        dedup_mod2(&mut target);
+        let Some(dimension) = target.first().map(|&c| self.cells[c].dimension()) else {
+            return true;
+        };
+
+        // Column-reduce the boundaries of the (dimension + 1)-cells
+        let mut pivot: HashMap<usize, Vec<usize>> = HashMap::new();
+        for cell in self.cells.iter().filter(|c| c.dimension() == dimension + 1) {
+            let mut col = cell.boundary.clone();
+            col.sort_unstable();
+            dedup_mod2(&mut col);
+            while let Some(&low) = col.last() {
+                match pivot.get(&low) {
+                    Some(other) => col = add_mod2(&col, other),
+                    None => {
+                        pivot.insert(low, col);
+                        break;
+                    }
+                }
+            }
+        }
+
+        while let Some(&low) = target.last() {
+            match pivot.get(&low) {
+                Some(col) => target = add_mod2(&target, col),
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Betti numbers β₀ … β_max of the whole complex
+    pub fn betti_numbers(&self) -> Vec<usize> {
+        if self.cells.is_empty() {
+            return vec![0];
+        }
+        let mut betti = vec![0; self.max_dimension() + 1];
+        for interval in self.intervals() {
+            if interval.is_essential() {
+                betti[interval.dimension] += 1;
+            }
+        }
+        betti
+    }
+
+    /// Betti numbers of the sub-complex present at filtration value `t`
+    pub fn betti_at(&self, t: f64) -> Vec<usize> {
+        let mut betti = vec![0; self.max_dimension() + 1];
+        for interval in self.intervals() {
+            if interval.birth <= t && t < interval.death {
+                betti[interval.dimension] += 1;
+            }
+        }
+        betti
+    }
+
+    /// Persistence intervals with positive lifetime, sorted by dimension then birth
+    pub fn persistence(&self) -> Vec<PersistenceInterval> {
+        let mut intervals: Vec<PersistenceInterval> = self.intervals()
+            .into_iter()
+            .filter(|i| i.death > i.birth)
+            .collect();
+        intervals.sort_by(|a, b| {
+            a.dimension.cmp(&b.dimension)
+                .then(a.birth.total_cmp(&b.birth))
+                .then(b.death.total_cmp(&a.death))
+        });
+        intervals
+    }
+
+    /// All intervals, including zero-length ones
+    fn intervals(&self) -> Vec<PersistenceInterval> {
+        // Filtration order: by value, then dimension, then insertion
+        let mut order: Vec<usize> = (0..self.cells.len()).collect();
+        order.sort_by(|&a, &b| {
+            self.cells[a].filtration.total_cmp(&self.cells[b].filtration)
+                .then(self.cells[a].dimension().cmp(&self.cells[b].dimension()))
+                .then(a.cmp(&b))
+        });
+        let mut position = vec![0; self.cells.len()];
+        for (p, &c) in order.iter().enumerate() {
+            position[c] = p;
+        }
+
+        // R = ∂·V, columns as sorted position lists
+        let mut r: Vec<Vec<usize>> = order.iter()
+            .map(|&c| {
+                let mut col: Vec<usize> = self.cells[c].boundary.iter().map(|&f| position[f]).collect();
+                col.sort_unstable();
+                dedup_mod2(&mut col);
+                col
+            })
+            .collect();
+        let mut v: Vec<Vec<usize>> = (0..order.len()).map(|p| vec![p]).collect();
+        let mut pivot: HashMap<usize, usize> = HashMap::new();
+
+        for j in 0..order.len() {
+            while let Some(&low) = r[j].last() {
+                match pivot.get(&low) {
+                    Some(&k) => {
+                        r[j] = add_mod2(&r[j], &r[k]);
+                        v[j] = add_mod2(&v[j], &v[k]);
+                    }
+                    None => {
+                        pivot.insert(low, j);
+                        break;
+                    }
+                }
+            }
+        }
+
+        let mut intervals = Vec::new();
+        for i in 0..order.len() {
+            if !r[i].is_empty() {
+                continue; // negative column: kills a class
+            }
+            let cell = &self.cells[order[i]];
+            let death = pivot.get(&i).map_or(f64::INFINITY, |&j| self.cells[order[j]].filtration);
+            intervals.push(PersistenceInterval {
+                dimension: cell.dimension(),
+                birth: cell.filtration,
+                death,
+                representative: v[i].iter().map(|&p| self.cells[order[p]].vertices.clone()).collect(),
+            });
+        }
+        intervals
+    }
+}
+
+impl ConceptComplex {
+    /// Close the complex under faces, keeping simplices up to `max_dimension`
+    ///
+    /// Relations of higher dimension contribute their `max_dimension` faces.
+    pub fn chain_complex(&self, max_dimension: usize) -> ChainComplex {
+        let mut complex = ChainComplex::new();
+        let mut ids: Vec<usize> = self.vertices.keys().copied().collect();
+        ids.sort_unstable();
+        for id in ids {
+            complex.add_simplex(vec![id], 0.0);
+        }
+        for relation in &self.faces {
+            let mut vertices = relation.vertices.clone();
+            vertices.sort_unstable();
+            vertices.dedup();
+            for simplex in combinations(&vertices, vertices.len().min(max_dimension + 1)) {
+                complex.add_simplex(simplex, 0.0);
+            }
+        }
+        complex
+    }
+}
+
+/// Cancel repeated entries of a sorted list in pairs (x + x = 0)
+fn dedup_mod2(col: &mut Vec<usize>) {
+    let mut out: Vec<usize> = Vec::with_capacity(col.len());
+    for &x in col.iter() {
+        if out.last() == Some(&x) {
+            out.pop();
+        } else {
+            out.push(x);
+        }
+    }
+    *col = out;
+}
+
+/// All k-element subsets, in lexicographic order
+pub(crate) fn combinations(items: &[usize], k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    if items.len() < k {
+        return Vec::new();
+    }
+    let mut result: Vec<Vec<usize>> = combinations(&items[1..], k - 1)
+        .into_iter()
+        .map(|mut rest| {
+            rest.insert(0, items[0]);
+            rest
+        })
+        .collect();
+    result.extend(combinations(&items[1..], k));
+    result
+}
+
+/// Sum of two sorted GF(2) vectors (symmetric difference)
+pub(crate) fn add_mod2(a: &[usize], b: &[usize]) -> Vec<usize> {
+    let mut out = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => {
+                out.push(a[i]);
+                i += 1;
+            }
+            std::cmp::Ordering::Greater => {
+                out.push(b[j]);
+                j += 1;
+            }
+            std::cmp::Ordering::Equal => {
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    out.extend_from_slice(&a[i..]);
+    out.extend_from_slice(&b[j..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(combinations(&[1, 2, 3], 2), vec![vec![1, 2], vec![1, 3], vec![2, 3]]);
+        assert!(combinations(&[1], 2).is_empty());
+    }
+
+    #[test]
+    fn test_hollow_triangle() {
+        let mut complex = ChainComplex::new();
+        complex.add_simplex(vec![0, 1], 0.0);
+        complex.add_simplex(vec![1, 2], 0.0);
+        complex.add_simplex(vec![0, 2], 0.0);
+        assert_eq!(complex.betti_numbers(), vec![1, 1]);
+    }
+
+    #[test]
+    fn test_filled_triangle() {
+        let mut complex = ChainComplex::new();
+        complex.add_simplex(vec![0, 1, 2], 0.0);
+        assert_eq!(complex.len(), 7);
+        assert_eq!(complex.betti_numbers(), vec![1, 0, 0]);
+    }
+
+    #[test]
+    fn test_hollow_tetrahedron_has_void() {
+        let mut complex = ChainComplex::new();
+        for face in [[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3]] {
+            complex.add_simplex(face.to_vec(), 0.0);
+        }
+        assert_eq!(complex.betti_numbers(), vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn test_persistence_of_filled_loop() {
+        let mut complex = ChainComplex::new();
+        complex.add_simplex(vec![0, 1], 1.0);
+        complex.add_simplex(vec![1, 2], 1.0);
+        complex.add_simplex(vec![0, 2], 2.0);
+        complex.add_simplex(vec![0, 1, 2], 5.0);
+
+        let loops: Vec<_> = complex.persistence().into_iter().filter(|i| i.dimension == 1).collect();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].birth, 2.0);
+        assert_eq!(loops[0].death, 5.0);
+        assert_eq!(loops[0].representative.len(), 3);
+
+        assert_eq!(complex.betti_at(3.0), vec![1, 1, 0]);
+        assert_eq!(complex.betti_at(5.0), vec![1, 0, 0]);
+    }
+
+    #[test]
+    fn test_is_boundary() {
+        let mut complex = ChainComplex::new();
+        complex.add_simplex(vec![0, 1, 2], 0.0);
+        complex.add_simplex(vec![2, 3], 0.0);
+        complex.add_simplex(vec![0, 3], 0.0);
+        let edge = |a: usize, b: usize| complex.find(&[a, b]).unwrap();
+
+        assert!(complex.is_boundary(&[edge(0, 1), edge(1, 2), edge(0, 2)]));
+        assert!(!complex.is_boundary(&[edge(0, 2), edge(2, 3), edge(0, 3)]));
+    }
+
+    #[test]
+    fn test_add_mod2() {
+        assert_eq!(add_mod2(&[1, 3, 5], &[3, 4]), vec![1, 4, 5]);
+    }
+}
@@ -0,0 +1,1021 @@
+//! Interchange formats for the Concept Complex.
+//!
+//! Concept maps are drafted in other tools; the analysis goes back to them.
+//!
+//! - JSON: lossless round-trip, versioned with `SCHEMA_VERSION`
+//! - GraphML: edges for 1-simplices, hyperedges for higher simplices
+//! - DOT: Graphviz export that highlights homological holes
+
+use crate::simplex::{ConceptComplex, HomologicalHole, Idea, Relation};
+use crate::types::*;
+use crate::SCHEMA_VERSION;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+impl ConceptComplex {
+    /// Serialize the complex to versioned JSON
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        out.push_str("{\n");
+        let _ = writeln!(out, "  \"schema_version\": {},", json_string(SCHEMA_VERSION));
+
+        out.push_str("  \"ideas\": [");
+        let ideas = self.sorted_ideas();
+        for (i, idea) in ideas.iter().enumerate() {
+            let embedding: Vec<String> = idea.embedding.iter().map(|e| e.to_string()).collect();
+            let members = if idea.members.is_empty() {
+                String::new()
+            } else {
+                let members: Vec<String> = idea.members.iter().map(|m| m.to_string()).collect();
+                format!(", \"members\": [{}]", members.join(", "))
+            };
+            let _ = write!(
+                out,
+                "{}\n    {{\"id\": {}, \"name\": {}, \"embedding\": [{}]{}}}",
+                if i == 0 { "" } else { "," },
+                idea.id,
+                json_string(&idea.name),
+                embedding.join(", "),
+                members,
+            );
+        }
+        out.push_str(if ideas.is_empty() { "],\n" } else { "\n  ],\n" });
+
+        out.push_str("  \"relations\": [");
+        for (i, relation) in self.faces.iter().enumerate() {
+            let vertices: Vec<String> = relation.vertices.iter().map(|v| v.to_string()).collect();
+            let _ = write!(
+                out,
+                "{}\n    {{\"vertices\": [{}], \"weight\": {}}}",
+                if i == 0 { "" } else { "," },
+                vertices.join(", "),
+                json_number(relation.weight),
+            );
+        }
+        out.push_str(if self.faces.is_empty() { "],\n" } else { "\n  ],\n" });
+
+        // Informational only: recomputed on import
+        let betti: Vec<String> = self.betti_numbers.iter().map(|b| b.to_string()).collect();
+        let _ = writeln!(out, "  \"betti_numbers\": [{}]", betti.join(", "));
+        out.push('}');
+        out
+    }
+
+    /// Deserialize a complex from JSON produced by [`ConceptComplex::to_json`]
+    pub fn from_json(input: &str) -> Result<Self, TopologyError> {
+        let root = JsonParser::new(input).parse_document()?;
+
+        let version = root.get("schema_version")
+            .and_then(Json::as_str)
+            .ok_or_else(|| TopologyError::malformed("json", "missing schema_version"))?;
+        check_schema_version(version)?;
+
+        let mut complex = ConceptComplex::new();
+
+        let ideas = root.get("ideas")
+            .and_then(Json::as_array)
+            .ok_or_else(|| TopologyError::malformed("json", "missing ideas array"))?;
+        for entry in ideas {
+            let id = entry.get("id")
+                .and_then(Json::as_index)
+                .ok_or_else(|| TopologyError::malformed("json", "idea without integer id"))?;
+            let name = entry.get("name").and_then(Json::as_str).unwrap_or_default();
+            let mut idea = Idea::new(id, name);
+            if let Some(embedding) = entry.get("embedding").and_then(Json::as_array) {
+                idea.embedding = embedding.iter()
+                    .map(|e| e.as_integer().ok_or_else(|| TopologyError::malformed("json", "non-integer embedding")))
+                    .collect::<Result<_, _>>()?;
+            }
+            if let Some(members) = entry.get("members").and_then(Json::as_array) {
+                idea.members = members.iter()
+                    .map(|m| m.as_index().ok_or_else(|| TopologyError::malformed("json", "non-integer member")))
+                    .collect::<Result<_, _>>()?;
+            }
+            complex.add_idea(idea);
+        }
+
+        let relations = root.get("relations")
+            .and_then(Json::as_array)
+            .ok_or_else(|| TopologyError::malformed("json", "missing relations array"))?;
+        for entry in relations {
+            let vertices = entry.get("vertices")
+                .and_then(Json::as_array)
+                .ok_or_else(|| TopologyError::malformed("json", "relation without vertices"))?
+                .iter()
+                .map(|v| v.as_index().ok_or_else(|| TopologyError::malformed("json", "non-integer vertex")))
+                .collect::<Result<Vec<_>, _>>()?;
+            if vertices.len() < 2 {
+                return Err(TopologyError::malformed("json", format!("relation with {} vertices", vertices.len())));
+            }
+            let weight = entry.get("weight").and_then(Json::as_f64).unwrap_or(1.0);
+            complex.add_relation(Relation::new(vertices).with_weight(weight));
+        }
+
+        complex.check_references("json")?;
+        complex.calculate_homology();
+        Ok(complex)
+    }
+
+    /// Serialize the complex to GraphML
+    ///
+    /// Edges carry 1-simplices; triangles and higher are written as hyperedges.
+    pub fn to_graphml(&self) -> String {
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        out.push_str("  <key id=\"schema_version\" for=\"graph\" attr.name=\"schema_version\" attr.type=\"string\"/>\n");
+        out.push_str("  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n");
+        out.push_str("  <key id=\"embedding\" for=\"node\" attr.name=\"embedding\" attr.type=\"string\"/>\n");
+        out.push_str("  <key id=\"members\" for=\"node\" attr.name=\"members\" attr.type=\"string\"/>\n");
+        out.push_str("  <key id=\"weight\" for=\"all\" attr.name=\"weight\" attr.type=\"double\"/>\n");
+        out.push_str("  <graph id=\"concept_complex\" edgedefault=\"undirected\">\n");
+        let _ = writeln!(out, "    <data key=\"schema_version\">{}</data>", xml_escape(SCHEMA_VERSION));
+
+        for idea in self.sorted_ideas() {
+            let embedding: Vec<String> = idea.embedding.iter().map(|e| e.to_string()).collect();
+            let members = if idea.members.is_empty() {
+                String::new()
+            } else {
+                let members: Vec<String> = idea.members.iter().map(|m| m.to_string()).collect();
+                format!("<data key=\"members\">{}</data>", members.join(","))
+            };
+            let _ = writeln!(
+                out,
+                "    <node id=\"n{}\"><data key=\"name\">{}</data><data key=\"embedding\">{}</data>{}</node>",
+                idea.id,
+                xml_escape(&idea.name),
+                embedding.join(","),
+                members,
+            );
+        }
+
+        for relation in &self.faces {
+            let weight = format!("<data key=\"weight\">{}</data>", relation.weight);
+            if relation.dimension == 1 {
+                let _ = writeln!(
+                    out,
+                    "    <edge source=\"n{}\" target=\"n{}\">{}</edge>",
+                    relation.vertices[0], relation.vertices[1], weight,
+                );
+            } else {
+                let endpoints: String = relation.vertices.iter()
+                    .map(|v| format!("<endpoint node=\"n{}\"/>", v))
+                    .collect();
+                let _ = writeln!(out, "    <hyperedge>{}{}</hyperedge>", endpoints, weight);
+            }
+        }
+
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    /// Deserialize a complex from GraphML
+    ///
+    /// Node ids of the form `n<k>` keep `k` as the idea id; any other id,
+    /// or a second spelling of a taken `k` such as `n05` after `n5`, is
+    /// assigned a fresh one. Node names are read from a key named `name` or
+    /// `label`, falling back to the node id.
+    pub fn from_graphml(input: &str) -> Result<Self, TopologyError> {
+        let graph = GraphmlDocument::parse(input)?;
+
+        if let Some(version) = graph.schema_version.as_deref() {
+            check_schema_version(version)?;
+        }
+
+        // Resolve node ids: keep `n<k>` numbering, append the rest
+        let mut ids: HashMap<String, usize> = HashMap::new();
+        let mut taken: HashSet<usize> = HashSet::new();
+        for node in &graph.nodes {
+            if let Some(k) = node.id.strip_prefix('n').and_then(|k| k.parse::<usize>().ok()) {
+                if !ids.contains_key(&node.id) && taken.insert(k) {
+                    ids.insert(node.id.clone(), k);
+                }
+            }
+        }
+        let mut next_id = ids.values().max().map_or(0, |m| m + 1);
+        for node in &graph.nodes {
+            ids.entry(node.id.clone()).or_insert_with(|| {
+                next_id += 1;
+                next_id - 1
+            });
+        }
+
+        let mut complex = ConceptComplex::new();
+        for node in &graph.nodes {
+            let name = node.name.clone().unwrap_or_else(|| node.id.clone());
+            let mut idea = Idea::new(ids[&node.id], name);
+            if let Some(embedding) = &node.embedding {
+                idea.embedding = embedding.split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.parse::<i64>().map_err(|_| TopologyError::malformed("graphml", "non-integer embedding")))
+                    .collect::<Result<_, _>>()?;
+            }
+            if let Some(members) = &node.members {
+                idea.members = parse_index_list(members)?;
+            }
+            complex.add_idea(idea);
+        }
+
+        for simplex in &graph.simplices {
+            let vertices = simplex.endpoints.iter()
+                .map(|e| ids.get(e).copied()
+                    .ok_or_else(|| TopologyError::malformed("graphml", format!("unknown node '{}'", e))))
+                .collect::<Result<Vec<_>, _>>()?;
+            complex.add_relation(Relation::new(vertices).with_weight(simplex.weight.unwrap_or(1.0)));
+        }
+
+        complex.calculate_homology();
+        Ok(complex)
+    }
+
+    /// Export the 1-skeleton to Graphviz DOT, highlighting hole representatives
+    ///
+    /// Vertices of each representative are drawn in red, as are the edges that
+    /// join consecutive representative vertices (the cycle itself).
+    pub fn to_dot(&self, holes: &[HomologicalHole]) -> String {
+        let mut hole_vertices: HashSet<usize> = HashSet::new();
+        let mut hole_edges: HashSet<(usize, usize)> = HashSet::new();
+        for hole in holes {
+            for rep in &hole.representatives {
+                hole_vertices.extend(rep.iter().copied());
+                if rep.len() >= 2 {
+                    for i in 0..rep.len() {
+                        let (a, b) = (rep[i], rep[(i + 1) % rep.len()]);
+                        hole_edges.insert((a.min(b), a.max(b)));
+                    }
+                }
+            }
+        }
+
+        // 1-skeleton: explicit edges plus the edges of every higher simplex
+        let mut edges: BTreeSet<(usize, usize)> = BTreeSet::new();
+        let mut weights: HashMap<(usize, usize), f64> = HashMap::new();
+        for relation in self.faces.iter().filter(|f| f.dimension >= 1) {
+            for (i, &a) in relation.vertices.iter().enumerate() {
+                for &b in &relation.vertices[i + 1..] {
+                    let key = (a.min(b), a.max(b));
+                    edges.insert(key);
+                    if relation.dimension == 1 {
+                        weights.insert(key, relation.weight);
+                    }
+                }
+            }
+        }
+
+        let betti: Vec<String> = self.betti_numbers.iter().map(|b| b.to_string()).collect();
+
+        let mut out = String::new();
+        out.push_str("graph concept_complex {\n");
+        let _ = writeln!(out, "  label=\"β = [{}]\";", betti.join(", "));
+        out.push_str("  node [shape=ellipse];\n");
+
+        for idea in self.sorted_ideas() {
+            let highlight = if hole_vertices.contains(&idea.id) {
+                ", color=red, fontcolor=red, penwidth=2"
+            } else {
+                ""
+            };
+            let _ = writeln!(out, "  n{} [label={}{}];", idea.id, dot_string(&idea.name), highlight);
+        }
+
+        for &(a, b) in &edges {
+            let mut attrs = Vec::new();
+            match weights.get(&(a, b)) {
+                Some(w) => attrs.push(format!("penwidth={}", w.clamp(0.5, 8.0))),
+                None => attrs.push("style=dashed".to_string()), // implied by a higher simplex
+            }
+            if hole_edges.contains(&(a, b)) {
+                attrs.push("color=red".to_string());
+            }
+            let _ = writeln!(out, "  n{} -- n{} [{}];", a, b, attrs.join(", "));
+        }
+
+        for relation in self.faces.iter().filter(|f| f.dimension >= 2) {
+            let _ = writeln!(out, "  // {}-simplex {:?} weight={}", relation.dimension, relation.vertices, relation.weight);
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    fn sorted_ideas(&self) -> Vec<&Idea> {
+        let mut ideas: Vec<&Idea> = self.vertices.values().collect();
+        ideas.sort_by_key(|idea| idea.id);
+        ideas
+    }
+
+    /// Every relation must reference existing ideas
+    fn check_references(&self, format: &str) -> Result<(), TopologyError> {
+        for relation in &self.faces {
+            if let Some(v) = relation.vertices.iter().find(|v| !self.vertices.contains_key(v)) {
+                return Err(TopologyError::malformed(format, format!("relation references unknown idea {}", v)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Comma- or whitespace-separated list of indices
+fn parse_index_list(s: &str) -> Result<Vec<usize>, TopologyError> {
+    s.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<usize>().map_err(|_| TopologyError::malformed("graphml", "non-integer member")))
+        .collect()
+}
+
+/// Accept any document with the same major schema version
+fn check_schema_version(found: &str) -> Result<(), TopologyError> {
+    let major = |v: &str| v.split('.').next().map(str::to_string);
+    if major(found) == major(SCHEMA_VERSION) {
+        Ok(())
+    } else {
+        Err(TopologyError::SchemaMismatch {
+            expected: SCHEMA_VERSION.to_string(),
+            found: found.to_string(),
+        })
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_number(x: f64) -> String {
+    if x.is_finite() {
+        x.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn dot_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+// ============================================================================
+// JSON
+// ============================================================================
+
+/// Minimal JSON value, enough for the complex schema
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_array(&self) -> Option<&Vec<Json>> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(x) => Some(*x),
+            Json::Null => Some(f64::NAN),
+            _ => None,
+        }
+    }
+
+    fn as_integer(&self) -> Option<i64> {
+        match self {
+            Json::Number(x) if x.fract() == 0.0 && x.abs() < 9.0e15 => Some(*x as i64),
+            _ => None,
+        }
+    }
+
+    fn as_index(&self) -> Option<usize> {
+        self.as_integer().and_then(|i| usize::try_from(i).ok())
+    }
+}
+
+struct JsonParser<'a> {
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { src: input.as_bytes(), pos: 0 }
+    }
+
+    fn error(&self, message: &str) -> TopologyError {
+        TopologyError::malformed("json", format!("{} at byte {}", message, self.pos))
+    }
+
+    fn parse_document(&mut self) -> Result<Json, TopologyError> {
+        let value = self.parse_value()?;
+        self.skip_whitespace();
+        if self.pos != self.src.len() {
+            return Err(self.error("trailing characters"));
+        }
+        Ok(value)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.src.len() && self.src[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), TopologyError> {
+        self.skip_whitespace();
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", byte as char)))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Json, TopologyError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.parse_object(),
+            Some(b'[') => self.parse_array(),
+            Some(b'"') => self.parse_string().map(Json::Str),
+            Some(b't') => self.parse_literal("true", Json::Bool(true)),
+            Some(b'f') => self.parse_literal("false", Json::Bool(false)),
+            Some(b'n') => self.parse_literal("null", Json::Null),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            _ => Err(self.error("unexpected character")),
+        }
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: Json) -> Result<Json, TopologyError> {
+        if self.src[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(value)
+        } else {
+            Err(self.error("invalid literal"))
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Json, TopologyError> {
+        let start = self.pos;
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.src[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .map(Json::Number)
+            .ok_or_else(|| self.error("invalid number"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, TopologyError> {
+        let digits = self.src.get(self.pos..self.pos + 4)
+            .and_then(|d| std::str::from_utf8(d).ok())
+            .and_then(|d| u32::from_str_radix(d, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(digits)
+    }
+
+    fn parse_string(&mut self) -> Result<String, TopologyError> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+        loop {
+            let byte = self.peek().ok_or_else(|| self.error("unterminated string"))?;
+            self.pos += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escape = self.peek().ok_or_else(|| self.error("unterminated escape"))?;
+                    self.pos += 1;
+                    let c = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut code = self.parse_hex4()?;
+                            // Surrogate pair
+                            if (0xD800..0xDC00).contains(&code) && self.src[self.pos..].starts_with(b"\\u") {
+                                self.pos += 2;
+                                let low = self.parse_hex4()?;
+                                code = 0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF);
+                            }
+                            char::from_u32(code).ok_or_else(|| self.error("invalid code point"))?
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    let mut buf = [0u8; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                b => bytes.push(b),
+            }
+        }
+        String::from_utf8(bytes).map_err(|_| self.error("invalid utf-8"))
+    }
+
+    fn parse_array(&mut self) -> Result<Json, TopologyError> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Json, TopologyError> {
+        self.expect(b'{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.expect(b':')?;
+            let value = self.parse_value()?;
+            fields.push((key, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+}
+
+// ============================================================================
+// GRAPHML
+// ============================================================================
+
+/// A flattened XML event
+#[derive(Debug)]
+enum XmlEvent {
+    Start { name: String, attrs: Vec<(String, String)>, empty: bool },
+    End { name: String },
+    Text(String),
+}
+
+/// Tokenize XML into start/end/text events (no DTD validation)
+fn xml_events(input: &str) -> Result<Vec<XmlEvent>, TopologyError> {
+    let mut events = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        if let Some(body) = rest.strip_prefix("<!--") {
+            let end = body.find("-->").ok_or_else(|| TopologyError::malformed("graphml", "unterminated comment"))?;
+            rest = &body[end + 3..];
+        } else if let Some(body) = rest.strip_prefix("<![CDATA[") {
+            let end = body.find("]]>").ok_or_else(|| TopologyError::malformed("graphml", "unterminated CDATA"))?;
+            events.push(XmlEvent::Text(body[..end].to_string()));
+            rest = &body[end + 3..];
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            let end = rest.find('>').ok_or_else(|| TopologyError::malformed("graphml", "unterminated declaration"))?;
+            rest = &rest[end + 1..];
+        } else if let Some(body) = rest.strip_prefix("</") {
+            let end = body.find('>').ok_or_else(|| TopologyError::malformed("graphml", "unterminated end tag"))?;
+            events.push(XmlEvent::End { name: body[..end].trim().to_string() });
+            rest = &body[end + 1..];
+        } else if let Some(body) = rest.strip_prefix('<') {
+            let end = tag_end(body).ok_or_else(|| TopologyError::malformed("graphml", "unterminated tag"))?;
+            let mut tag = &body[..end];
+            let empty = tag.ends_with('/');
+            if empty {
+                tag = &tag[..tag.len() - 1];
+            }
+            let name_end = tag.find(|c: char| c.is_whitespace()).unwrap_or(tag.len());
+            events.push(XmlEvent::Start {
+                name: tag[..name_end].to_string(),
+                attrs: xml_attributes(&tag[name_end..])?,
+                empty,
+            });
+            rest = &body[end + 1..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            events.push(XmlEvent::Text(xml_unescape(&rest[..end])));
+            rest = &rest[end..];
+        }
+    }
+
+    Ok(events)
+}
+
+/// Index of the closing '>' of a tag, skipping quoted attribute values
+fn tag_end(body: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in body.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn xml_attributes(mut s: &str) -> Result<Vec<(String, String)>, TopologyError> {
+    let mut attrs = Vec::new();
+    loop {
+        s = s.trim_start();
+        if s.is_empty() {
+            return Ok(attrs);
+        }
+        let eq = s.find('=').ok_or_else(|| TopologyError::malformed("graphml", "attribute without value"))?;
+        let key = s[..eq].trim().to_string();
+        let value_part = s[eq + 1..].trim_start();
+        let quote = value_part.chars().next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or_else(|| TopologyError::malformed("graphml", "unquoted attribute"))?;
+        let close = value_part[1..].find(quote)
+            .ok_or_else(|| TopologyError::malformed("graphml", "unterminated attribute"))?;
+        attrs.push((key, xml_unescape(&value_part[1..1 + close])));
+        s = &value_part[close + 2..];
+    }
+}
+
+fn xml_unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp..];
+        let decoded = after.find(';').and_then(|semi| {
+            let entity = &after[1..semi];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity.strip_prefix("#x")
+                    .map(|h| u32::from_str_radix(h, 16).ok())
+                    .unwrap_or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                out.push(c);
+                rest = &after[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &after[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn attr<'a>(attrs: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+}
+
+#[derive(Debug, Default)]
+struct GraphmlNode {
+    id: String,
+    name: Option<String>,
+    embedding: Option<String>,
+    members: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct GraphmlSimplex {
+    endpoints: Vec<String>,
+    weight: Option<f64>,
+}
+
+/// What a GraphML element is currently being read into
+enum GraphmlScope {
+    None,
+    Node(GraphmlNode),
+    Simplex(GraphmlSimplex),
+}
+
+#[derive(Debug, Default)]
+struct GraphmlDocument {
+    schema_version: Option<String>,
+    nodes: Vec<GraphmlNode>,
+    simplices: Vec<GraphmlSimplex>,
+}
+
+impl GraphmlDocument {
+    fn parse(input: &str) -> Result<Self, TopologyError> {
+        let mut doc = GraphmlDocument::default();
+        // key id -> attr.name
+        let mut keys: HashMap<String, String> = HashMap::new();
+        let mut scope = GraphmlScope::None;
+        // (attr.name, text so far, nested element depth)
+        let mut data: Option<(String, String, usize)> = None;
+
+        for event in xml_events(input)? {
+            if let Some((_, text, depth)) = data.as_mut() {
+                match &event {
+                    XmlEvent::Text(t) if *depth == 0 => text.push_str(t),
+                    XmlEvent::Start { empty: false, .. } => *depth += 1,
+                    XmlEvent::End { name } if name == "data" && *depth == 0 => {
+                        let (key, text, _) = data.take().unwrap_or_default();
+                        doc.assign(&mut scope, &key, text.trim());
+                    }
+                    XmlEvent::End { .. } => *depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                continue;
+            }
+
+            match event {
+                XmlEvent::Start { name, attrs, empty } => {
+                    match name.as_str() {
+                        "key" => {
+                            if let (Some(id), Some(attr_name)) = (attr(&attrs, "id"), attr(&attrs, "attr.name")) {
+                                keys.insert(id.to_string(), attr_name.to_string());
+                            }
+                        }
+                        "node" => {
+                            let id = attr(&attrs, "id")
+                                .ok_or_else(|| TopologyError::malformed("graphml", "node without id"))?;
+                            scope = GraphmlScope::Node(GraphmlNode { id: id.to_string(), ..Default::default() });
+                        }
+                        "edge" => {
+                            let (source, target) = attr(&attrs, "source").zip(attr(&attrs, "target"))
+                                .ok_or_else(|| TopologyError::malformed("graphml", "edge without source/target"))?;
+                            scope = GraphmlScope::Simplex(GraphmlSimplex {
+                                endpoints: vec![source.to_string(), target.to_string()],
+                                weight: None,
+                            });
+                        }
+                        "hyperedge" => scope = GraphmlScope::Simplex(GraphmlSimplex::default()),
+                        "endpoint" => {
+                            if let (GraphmlScope::Simplex(s), Some(node)) = (&mut scope, attr(&attrs, "node")) {
+                                s.endpoints.push(node.to_string());
+                            }
+                        }
+                        "data" if !empty => {
+                            let key = attr(&attrs, "key").unwrap_or_default();
+                            let resolved = keys.get(key).cloned().unwrap_or_else(|| key.to_string());
+                            data = Some((resolved, String::new(), 0));
+                        }
+                        _ => {}
+                    }
+                    // Self-closing elements end immediately
+                    if empty && matches!(name.as_str(), "node" | "edge" | "hyperedge") {
+                        doc.finish(&mut scope);
+                    }
+                }
+                XmlEvent::End { name } => {
+                    if matches!(name.as_str(), "node" | "edge" | "hyperedge") {
+                        doc.finish(&mut scope);
+                    }
+                }
+                XmlEvent::Text(_) => {}
+            }
+        }
+
+        Ok(doc)
+    }
+
+    /// Store a `<data>` value on the current element
+    fn assign(&mut self, scope: &mut GraphmlScope, key: &str, value: &str) {
+        match (scope, key) {
+            (GraphmlScope::Node(node), "name" | "label") => node.name = Some(value.to_string()),
+            (GraphmlScope::Node(node), "embedding") => node.embedding = Some(value.to_string()),
+            (GraphmlScope::Node(node), "members") => node.members = Some(value.to_string()),
+            (GraphmlScope::Simplex(s), "weight") => s.weight = value.parse().ok(),
+            (GraphmlScope::None, "schema_version") => self.schema_version = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    /// Close the current node or simplex
+    fn finish(&mut self, scope: &mut GraphmlScope) {
+        match std::mem::replace(scope, GraphmlScope::None) {
+            GraphmlScope::Node(node) => self.nodes.push(node),
+            GraphmlScope::Simplex(s) if !s.endpoints.is_empty() => self.simplices.push(s),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex::ComplexBuilder;
+
+    fn sample_complex() -> ConceptComplex {
+        let mut builder = ComplexBuilder::new();
+        let a = builder.add_idea("Thesis");
+        let b = builder.add_idea("Antithesis \"&\" <Other>");
+        let c = builder.add_idea("Synthesis");
+        let d = builder.add_idea("Paradox");
+        builder.connect(a, b).connect(b, c).connect(c, a).connect(c, d);
+        builder.add_triangle(a, b, c);
+        let mut complex = builder.build();
+        complex.add_idea(Idea::new(7, "Outlier").with_embedding(vec![3, -2, 9]).with_members(vec![4, 11]));
+        complex.add_relation(Relation::edge(d, 7).with_weight(0.25));
+        complex.calculate_homology();
+        complex
+    }
+
+    fn assert_same(a: &ConceptComplex, b: &ConceptComplex) {
+        assert_eq!(a.vertices, b.vertices);
+        assert_eq!(a.faces.len(), b.faces.len());
+        for (x, y) in a.faces.iter().zip(&b.faces) {
+            assert_eq!(x.vertices, y.vertices);
+            assert!((x.weight - y.weight).abs() < 1e-12);
+        }
+        assert_eq!(a.betti_numbers, b.betti_numbers);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let complex = sample_complex();
+        let json = complex.to_json();
+        assert!(json.contains(&format!("\"schema_version\": \"{}\"", SCHEMA_VERSION)));
+
+        let restored = ConceptComplex::from_json(&json).unwrap();
+        assert_same(&complex, &restored);
+    }
+
+    #[test]
+    fn test_json_rejects_other_major_version() {
+        let json = sample_complex().to_json().replace(SCHEMA_VERSION, "2.0.0");
+        let err = ConceptComplex::from_json(&json).unwrap_err();
+        assert!(matches!(err, TopologyError::SchemaMismatch { .. }));
+    }
+
+    #[test]
+    fn test_json_rejects_degenerate_relation() {
+        for vertices in ["[]", "[0]"] {
+            let json = format!(r#"{{"schema_version": "1.0.0", "ideas": [{{"id": 0, "name": "A"}}], "relations": [{{"vertices": {}}}]}}"#, vertices);
+            let err = ConceptComplex::from_json(&json).unwrap_err();
+            assert!(matches!(err, TopologyError::MalformedInput { .. }), "{}", vertices);
+        }
+    }
+
+    #[test]
+    fn test_json_rejects_dangling_relation() {
+        let json = r#"{"schema_version": "1.0.0", "ideas": [{"id": 0, "name": "A"}],
+                       "relations": [{"vertices": [0, 5]}]}"#;
+        let err = ConceptComplex::from_json(json).unwrap_err();
+        assert!(matches!(err, TopologyError::MalformedInput { .. }));
+    }
+
+    #[test]
+    fn test_graphml_round_trip() {
+        let complex = sample_complex();
+        let graphml = complex.to_graphml();
+        assert!(graphml.contains("<hyperedge>"));
+
+        let restored = ConceptComplex::from_graphml(&graphml).unwrap();
+        assert_same(&complex, &restored);
+    }
+
+    #[test]
+    fn test_graphml_from_external_tool() {
+        let graphml = r#"<?xml version="1.0"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+              <key id="d0" for="node" attr.name="label" attr.type="string"/>
+              <!-- drawn elsewhere -->
+              <graph edgedefault="undirected">
+                <node id="alpha"><data key="d0">Alpha</data></node>
+                <node id="beta"><data key="d0">Beta &amp; Co</data></node>
+                <node id="gamma"/>
+                <edge source="alpha" target="beta"/>
+                <edge source="beta" target="gamma"/>
+                <edge source="gamma" target="alpha"/>
+              </graph>
+            </graphml>"#;
+
+        let complex = ConceptComplex::from_graphml(graphml).unwrap();
+        assert_eq!(complex.vertices.len(), 3);
+        assert_eq!(complex.faces.len(), 3);
+        assert!(complex.vertices.values().any(|i| i.name == "Beta & Co"));
+        assert!(complex.vertices.values().any(|i| i.name == "gamma"));
+        assert_eq!(complex.betti_numbers[1], 1);
+    }
+
+    #[test]
+    fn test_graphml_keeps_numerically_equal_ids_apart() {
+        let graphml = r#"<graphml><graph edgedefault="undirected">
+                <node id="n5"/>
+                <node id="n05"/>
+                <edge source="n5" target="n05"/>
+            </graph></graphml>"#;
+
+        let complex = ConceptComplex::from_graphml(graphml).unwrap();
+        assert_eq!(complex.vertices.len(), 2);
+        assert_eq!(complex.vertices[&5].name, "n5");
+        assert_eq!(complex.vertices[&6].name, "n05");
+        assert_eq!(complex.faces.len(), 1);
+    }
+
+    #[test]
+    fn test_dot_highlights_holes() {
+        let mut builder = ComplexBuilder::new();
+        let a = builder.add_idea("A");
+        let b = builder.add_idea("B");
+        let c = builder.add_idea("C");
+        builder.connect(a, b).connect(b, c).connect(c, a);
+        let complex = builder.build();
+
+        let holes: Vec<HomologicalHole> = complex.identify_gap().into_iter().collect();
+        let dot = complex.to_dot(&holes);
+
+        assert!(dot.starts_with("graph concept_complex {"));
+        assert!(dot.contains("n0 [label=\"A\", color=red"));
+        assert_eq!(dot.matches("color=red]").count(), 3);
+    }
+
+    #[test]
+    fn test_dot_highlights_every_edge_of_a_pentagon() {
+        let mut builder = ComplexBuilder::new();
+        let ids: Vec<usize> = (0..5).map(|i| builder.add_idea(format!("idea_{}", i))).collect();
+        for i in 0..5 {
+            builder.connect(ids[i], ids[(i + 1) % 5]);
+        }
+        // A filled triangle hanging off the loop is not part of the hole
+        let tip = builder.add_idea("tip");
+        builder.connect(ids[0], tip).connect(ids[1], tip);
+        builder.add_triangle(ids[0], ids[1], tip);
+        let complex = builder.build();
+
+        let holes: Vec<HomologicalHole> = complex.identify_gap().into_iter().collect();
+        let dot = complex.to_dot(&holes);
+        for i in 0..5 {
+            let (a, b) = (ids[i].min(ids[(i + 1) % 5]), ids[i].max(ids[(i + 1) % 5]));
+            let line = dot.lines().find(|l| l.trim_start().starts_with(&format!("n{} -- n{} [", a, b))).unwrap();
+            assert!(line.contains("color=red"), "edge {}-{}", a, b);
+            assert!(dot.contains(&format!("n{} [label=\"idea_{}\", color=red", ids[i], i)));
+        }
+        assert!(!dot.contains("n5 [label=\"tip\", color=red"));
+    }
+}
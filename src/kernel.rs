@@ -6,7 +6,14 @@
 //! - 12 vertices → Pitch Classes / Fundamental Concepts
 //! - 20 faces → Rhythmic Cells / Narrative Frames
 //! - 30 edges → Timbral Morphs / Transitional States
+//!
+//! Every `SymmetryGroup` has a kernel on its own shape, from a single
+//! triangle (trivial) through cones, bipyramids, the tetrahedron and the
+//! octahedron, so material can move between sparse and dense symmetry.
 
+use crate::group::{FiniteGroup, MAX_GROUP_ORDER};
+use crate::tuning::{vertex_pitches, NOTE_NAMES};
+use crate::types::TopologyError;
 use nalgebra::{Matrix3, Vector3};
 use std::f64::consts::PI;
 
@@ -29,6 +36,8 @@ pub enum SymmetryGroup {
     Octahedral,
     /// Icosahedral group A₅ (order 60)
     Icosahedral,
+    /// A user-defined finite group of the given order (see `permutation`)
+    Abstract(usize),
 }
 
 impl SymmetryGroup {
@@ -41,6 +50,7 @@ impl SymmetryGroup {
             Self::Tetrahedral => 12,
             Self::Octahedral => 24,
             Self::Icosahedral => 60,
+            Self::Abstract(n) => *n,
         }
     }
 }
@@ -50,7 +60,6 @@ impl SymmetryGroup {
 pub struct GroupElement {
     pub matrix: Matrix3<f64>,
     pub name: String,
-    pub order: usize, // Order of this element (smallest n where g^n = identity)
 }
 
 impl GroupElement {
@@ -58,7 +67,6 @@ impl GroupElement {
         Self {
             matrix: Matrix3::identity(),
             name: "e".to_string(),
-            order: 1,
         }
     }
 
@@ -76,35 +84,61 @@ impl GroupElement {
             t*x*z - s*y, t*y*z + s*x, t*z*z + c,
         );
 
-        // Compute order
-        let order = Self::compute_order(&matrix);
-
         Self {
             matrix,
             name: name.into(),
-            order,
         }
     }
 
-    fn compute_order(matrix: &Matrix3<f64>) -> usize {
-        let mut current = *matrix;
-        for n in 1..=60 {
-            if (current - Matrix3::identity()).norm() < 1e-10 {
-                return n;
-            }
-            current *= matrix;
-        }
-        60 // Maximum for icosahedral
+    /// Order: the least n with g^n the identity
+    ///
+    /// Fails for rotations by an irrational fraction of a turn, and for any
+    /// order above `MAX_GROUP_ORDER`.
+    pub fn order(&self) -> Result<usize, TopologyError> {
+        let turns = crate::group::rotation_angle(&self.matrix) / (2.0 * PI);
+        (1..=MAX_GROUP_ORDER)
+            .find(|&n| {
+                let multiple = turns * n as f64;
+                (multiple - multiple.round()).abs() < 1e-9
+            })
+            .ok_or_else(|| {
+                TopologyError::group_structure(format!("{} has no finite order up to {}", self.name, MAX_GROUP_ORDER))
+            })
+    }
+
+    /// Check if this is the identity rotation
+    pub fn is_identity(&self) -> bool {
+        (self.matrix - Matrix3::identity()).norm() < 1e-10
     }
 
     /// Compose two group elements
+    ///
+    /// Identities are absorbed and a product equal to the identity is named
+    /// "e"; `GenerativeKernel::reduce` gives the canonical name otherwise.
     pub fn compose(&self, other: &GroupElement) -> GroupElement {
         let matrix = self.matrix * other.matrix;
-        let order = Self::compute_order(&matrix);
+        let name = if (matrix - Matrix3::identity()).norm() < 1e-10 {
+            "e".to_string()
+        } else if other.is_identity() {
+            self.name.clone()
+        } else if self.is_identity() {
+            other.name.clone()
+        } else {
+            format!("{}∘{}", self.name, other.name)
+        };
+        GroupElement { matrix, name }
+    }
+
+    /// Inverse element (the transpose of a rotation)
+    pub fn inverse(&self) -> GroupElement {
+        let name = match self.name.strip_suffix("⁻¹") {
+            _ if matches!(self.order(), Ok(n) if n <= 2) => self.name.clone(),
+            Some(base) => base.to_string(),
+            None => format!("{}⁻¹", self.name),
+        };
         GroupElement {
-            matrix,
-            name: format!("{}∘{}", self.name, other.name),
-            order,
+            matrix: self.matrix.transpose(),
+            name,
         }
     }
 
@@ -170,6 +204,9 @@ impl Icosahedron {
     }
 
     /// Get the dual dodecahedron (20 vertices, 12 faces, 30 edges)
+    ///
+    /// Vertex i sits over icosahedron face i, face j around icosahedron
+    /// vertex j, and edge k crosses icosahedron edge k.
     pub fn dual(&self) -> Dodecahedron {
         // Dodecahedron vertices are at face centers of icosahedron
         let vertices: Vec<Vector3<f64>> = self.faces.iter()
@@ -181,7 +218,28 @@ impl Icosahedron {
             })
             .collect();
 
-        Dodecahedron { vertices }
+        // The faces around each vertex, counterclockwise seen from outside
+        let faces = self.vertices.iter().enumerate()
+            .map(|(v, axis)| {
+                let mut ring: Vec<usize> = (0..self.faces.len()).filter(|&f| self.faces[f].contains(&v)).collect();
+                let e1 = (vertices[ring[0]] - axis * axis.dot(&vertices[ring[0]])).normalize();
+                let e2 = axis.cross(&e1);
+                let angle = |f: usize| vertices[f].dot(&e2).atan2(vertices[f].dot(&e1));
+                ring.sort_by(|&a, &b| angle(a).total_cmp(&angle(b)));
+                [ring[0], ring[1], ring[2], ring[3], ring[4]]
+            })
+            .collect();
+
+        // The two faces on either side of each edge
+        let edges = self.edges.iter()
+            .map(|edge| {
+                let mut sides = (0..self.faces.len()).filter(|&f| edge.iter().all(|v| self.faces[f].contains(v)));
+                let (a, b) = (sides.next().unwrap_or(0), sides.next().unwrap_or(0));
+                [a.min(b), a.max(b)]
+            })
+            .collect();
+
+        Dodecahedron { vertices, faces, edges }
     }
 }
 
@@ -196,15 +254,169 @@ impl Default for Icosahedron {
 pub struct Dodecahedron {
     /// 20 vertices (at face centers of icosahedron)
     pub vertices: Vec<Vector3<f64>>,
+    /// 12 pentagonal faces, vertex indices in cyclic order
+    pub faces: Vec<[usize; 5]>,
+    /// 30 edges (as vertex index pairs)
+    pub edges: Vec<[usize; 2]>,
 }
 
-/// The Generative Kernel - produces transformations from icosahedral symmetry
-#[derive(Debug)]
+impl Dodecahedron {
+    /// The three vertices joined to each vertex
+    pub fn adjacency(&self) -> Vec<Vec<usize>> {
+        let mut adjacency = vec![Vec::new(); self.vertices.len()];
+        for &[a, b] in &self.edges {
+            adjacency[a].push(b);
+            adjacency[b].push(a);
+        }
+        for neighbours in &mut adjacency {
+            neighbours.sort_unstable();
+        }
+        adjacency
+    }
+
+    /// Faces meeting at a vertex
+    pub fn faces_at(&self, vertex: usize) -> Vec<usize> {
+        (0..self.faces.len()).filter(|&f| self.faces[f].contains(&vertex)).collect()
+    }
+
+    /// Center of a face, on the unit sphere
+    pub fn face_center(&self, face: usize) -> Vector3<f64> {
+        self.faces[face].iter().map(|&v| self.vertices[v]).sum::<Vector3<f64>>().normalize()
+    }
+}
+
+/// A triangulated shape whose rotations form a kernel's symmetry group
+///
+/// Vertices, faces and edges become pitch classes, rhythmic cells and
+/// timbral morphs. Shapes for the smaller groups may be open (a cone) or
+/// flat (a single triangle).
+#[derive(Debug, Clone)]
+pub struct Polyhedron {
+    /// Name of the shape
+    pub name: String,
+    /// Vertices on the unit sphere
+    pub vertices: Vec<Vector3<f64>>,
+    /// Triangular faces, as vertex index triples
+    pub faces: Vec<[usize; 3]>,
+    /// Edges (as vertex index pairs)
+    pub edges: Vec<[usize; 2]>,
+}
+
+impl Polyhedron {
+    /// A shape from vertices and faces; edges are read off the faces
+    ///
+    /// Degenerate faces (a repeated vertex) and repeated faces are dropped.
+    pub fn from_faces(name: impl Into<String>, vertices: Vec<Vector3<f64>>, faces: Vec<[usize; 3]>) -> Self {
+        let mut kept: Vec<[usize; 3]> = Vec::new();
+        for face in faces {
+            let mut sorted = face;
+            sorted.sort_unstable();
+            let degenerate = sorted[0] == sorted[1] || sorted[1] == sorted[2];
+            let repeated = kept.iter().any(|f| {
+                let mut other = *f;
+                other.sort_unstable();
+                other == sorted
+            });
+            if !degenerate && !repeated {
+                kept.push(face);
+            }
+        }
+
+        let mut edges = Vec::new();
+        for face in &kept {
+            for i in 0..3 {
+                let a = face[i].min(face[(i + 1) % 3]);
+                let b = face[i].max(face[(i + 1) % 3]);
+                if !edges.contains(&[a, b]) {
+                    edges.push([a, b]);
+                }
+            }
+        }
+        let vertices = vertices.into_iter().map(|v| v.normalize()).collect();
+        Self { name: name.into(), vertices, faces: kept, edges }
+    }
+
+    /// A scalene triangle: no rotation but the identity preserves it
+    pub fn triangle() -> Self {
+        let vertices = vec![Vector3::x(), Vector3::y(), Vector3::new(0.48, 0.6, 0.64)];
+        Self::from_faces("triangle", vertices, vec![[0, 1, 2]])
+    }
+
+    /// An open n-gonal cone: apex on +z, rim on the equator (C_n)
+    pub fn cone(n: usize) -> Self {
+        let mut vertices = vec![Vector3::z()];
+        vertices.extend(equator(n));
+        let faces = (0..n).map(|k| [0, 1 + k, 1 + (k + 1) % n]).collect();
+        Self::from_faces(format!("{}-gonal cone", n), vertices, faces)
+    }
+
+    /// An n-gonal bipyramid: rim on the equator, apexes at ±z (D_n)
+    pub fn bipyramid(n: usize) -> Self {
+        let mut vertices = vec![Vector3::z(), -Vector3::z()];
+        vertices.extend(equator(n));
+        let faces = (0..n)
+            .flat_map(|k| {
+                let (a, b) = (2 + k, 2 + (k + 1) % n);
+                [[0, a, b], [1, b, a]]
+            })
+            .collect();
+        Self::from_faces(format!("{}-gonal bipyramid", n), vertices, faces)
+    }
+
+    /// The regular tetrahedron (A₄): 4 vertices, 4 faces, 6 edges
+    pub fn tetrahedron() -> Self {
+        let vertices = vec![
+            Vector3::new(1.0, 1.0, 1.0),
+            Vector3::new(1.0, -1.0, -1.0),
+            Vector3::new(-1.0, 1.0, -1.0),
+            Vector3::new(-1.0, -1.0, 1.0),
+        ];
+        Self::from_faces("tetrahedron", vertices, vec![[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]])
+    }
+
+    /// The regular octahedron (S₄): 6 vertices, 8 faces, 12 edges
+    pub fn octahedron() -> Self {
+        let vertices = vec![
+            Vector3::x(), -Vector3::x(),
+            Vector3::y(), -Vector3::y(),
+            Vector3::z(), -Vector3::z(),
+        ];
+        let faces = vec![
+            [4, 0, 2], [4, 2, 1], [4, 1, 3], [4, 3, 0],
+            [5, 2, 0], [5, 1, 2], [5, 3, 1], [5, 0, 3],
+        ];
+        Self::from_faces("octahedron", vertices, faces)
+    }
+
+    /// The regular icosahedron (A₅): 12 vertices, 20 faces, 30 edges
+    pub fn icosahedron() -> Self {
+        Self::from(Icosahedron::new())
+    }
+}
+
+impl From<Icosahedron> for Polyhedron {
+    fn from(ico: Icosahedron) -> Self {
+        Self { name: "icosahedron".to_string(), vertices: ico.vertices, faces: ico.faces, edges: ico.edges }
+    }
+}
+
+/// n equally spaced points on the equator
+pub(crate) fn equator(n: usize) -> Vec<Vector3<f64>> {
+    (0..n)
+        .map(|k| {
+            let angle = 2.0 * PI * k as f64 / n as f64;
+            Vector3::new(angle.cos(), angle.sin(), 0.0)
+        })
+        .collect()
+}
+
+/// The Generative Kernel - produces transformations from a rotation group
+#[derive(Debug, Clone)]
 pub struct GenerativeKernel {
     /// The underlying symmetry group
     pub group: SymmetryGroup,
-    /// The icosahedron geometry
-    pub icosahedron: Icosahedron,
+    /// The geometry the group acts on
+    pub geometry: Polyhedron,
     /// Group elements (rotations)
     pub elements: Vec<GroupElement>,
     /// Coherence strain (how far from equilibrium)
@@ -212,64 +424,142 @@ pub struct GenerativeKernel {
 }
 
 impl GenerativeKernel {
-    /// Create an icosahedral generative kernel
-    pub fn icosahedral() -> Self {
-        let icosahedron = Icosahedron::new();
-        let elements = Self::generate_icosahedral_elements(&icosahedron);
-
-        Self {
-            group: SymmetryGroup::Icosahedral,
-            icosahedron,
-            elements,
-            strain: 0.0,
+    /// Create a kernel for any symmetry group
+    ///
+    /// Cyclic and dihedral orders below 1 are raised to 1. Groups larger
+    /// than `MAX_GROUP_ORDER` are rejected rather than enumerated; below
+    /// it, building the kernel is cheap, and `group_structure` tabulates
+    /// order² products.
+    pub fn new(group: SymmetryGroup) -> Result<Self, TopologyError> {
+        let group = match group {
+            SymmetryGroup::Cyclic(n) => SymmetryGroup::Cyclic(n.max(1)),
+            SymmetryGroup::Dihedral(n) => SymmetryGroup::Dihedral(n.max(1)),
+            other => other,
+        };
+        if group.order() > MAX_GROUP_ORDER {
+            return Err(TopologyError::group_structure(format!(
+                "{:?} has order {}, above the limit of {}", group, group.order(), MAX_GROUP_ORDER
+            )));
         }
+        // A 1/n turn about an axis
+        let turn = |axis: Vector3<f64>, n: usize, name: &str| GroupElement::rotation(axis, 2.0 * PI / n as f64, name);
+
+        let (geometry, generators) = match group {
+            // Abstract groups have no rotation model; see `PermutationKernel`
+            SymmetryGroup::Abstract(n) => {
+                return Err(TopologyError::group_structure(format!(
+                    "an abstract group of order {} has no rotations; use PermutationKernel", n
+                )));
+            }
+            SymmetryGroup::Trivial => (Polyhedron::triangle(), vec![]),
+            // Z₁ has no rim to turn; D₁ is a single half-turn of a 2-gonal cone
+            SymmetryGroup::Cyclic(1) => (Polyhedron::triangle(), vec![]),
+            SymmetryGroup::Dihedral(1) => (Polyhedron::cone(2), vec![turn(Vector3::z(), 2, "C2")]),
+            SymmetryGroup::Cyclic(n) => (Polyhedron::cone(n), vec![turn(Vector3::z(), n, "Cn")]),
+            SymmetryGroup::Dihedral(n) => (
+                Polyhedron::bipyramid(n),
+                vec![turn(Vector3::z(), n, "Cn"), turn(Vector3::x(), 2, "C2")],
+            ),
+            SymmetryGroup::Tetrahedral => (
+                Polyhedron::tetrahedron(),
+                vec![turn(Vector3::new(1.0, 1.0, 1.0), 3, "C3"), turn(Vector3::z(), 2, "C2")],
+            ),
+            SymmetryGroup::Octahedral => (
+                Polyhedron::octahedron(),
+                vec![turn(Vector3::z(), 4, "C4"), turn(Vector3::new(1.0, 1.0, 1.0), 3, "C3")],
+            ),
+            SymmetryGroup::Icosahedral => {
+                let icosahedron = Icosahedron::new();
+                let elements = Self::generate_icosahedral_elements(&icosahedron);
+                return Ok(Self { group, geometry: icosahedron.into(), elements, strain: 0.0 });
+            }
+        };
+
+        let elements = FiniteGroup::generate_elements(&generators)?;
+        Ok(Self { group, geometry, elements, strain: 0.0 })
     }
 
-    /// Generate all 60 elements of the icosahedral group A₅
-    fn generate_icosahedral_elements(ico: &Icosahedron) -> Vec<GroupElement> {
-        let mut elements = vec![GroupElement::identity()];
-
-        // 5-fold rotations about vertex axes (12 vertices, but opposite pairs = 6 axes)
-        // Each axis contributes 4 non-identity rotations (72°, 144°, 216°, 288°)
-        for i in 0..6 {
-            let axis = ico.vertices[i];
-            for k in 1..5 {
-                let theta = 2.0 * PI * k as f64 / 5.0;
-                elements.push(GroupElement::rotation(
-                    axis,
-                    theta,
-                    format!("C5_{},{}", i, k),
-                ));
-            }
-        }
+    /// Trivial kernel on a scalene triangle
+    pub fn trivial() -> Self {
+        Self::new(SymmetryGroup::Trivial).expect("the trivial group is finite")
+    }
 
-        // 3-fold rotations about face axes (20 faces, but opposite pairs = 10 axes)
-        // Each axis contributes 2 non-identity rotations (120°, 240°)
-        for (i, face) in ico.faces.iter().take(10).enumerate() {
-            let center = (ico.vertices[face[0]] + ico.vertices[face[1]] + ico.vertices[face[2]]) / 3.0;
-            let axis = center.normalize();
-            for k in 1..3 {
-                let theta = 2.0 * PI * k as f64 / 3.0;
-                elements.push(GroupElement::rotation(
-                    axis,
-                    theta,
-                    format!("C3_{},{}", i, k),
-                ));
-            }
-        }
+    /// Cyclic kernel Z_n on an open n-gonal cone
+    pub fn cyclic(n: usize) -> Result<Self, TopologyError> {
+        Self::new(SymmetryGroup::Cyclic(n))
+    }
 
-        // 2-fold rotations about edge midpoint axes (30 edges, but opposite pairs = 15 axes)
-        for (i, edge) in ico.edges.iter().take(15).enumerate() {
-            let midpoint = (ico.vertices[edge[0]] + ico.vertices[edge[1]]) / 2.0;
-            let axis = midpoint.normalize();
-            elements.push(GroupElement::rotation(
-                axis,
-                PI,
-                format!("C2_{}", i),
-            ));
-        }
+    /// Dihedral kernel D_n on an n-gonal bipyramid
+    pub fn dihedral(n: usize) -> Result<Self, TopologyError> {
+        Self::new(SymmetryGroup::Dihedral(n))
+    }
+
+    /// Tetrahedral kernel A₄ (sparse: 4 pitches, 4 cells, 6 morphs)
+    pub fn tetrahedral() -> Self {
+        Self::new(SymmetryGroup::Tetrahedral).expect("the tetrahedral group has 12 elements")
+    }
 
-        elements
+    /// Octahedral kernel S₄ (6 pitches, 8 cells, 12 morphs)
+    pub fn octahedral() -> Self {
+        Self::new(SymmetryGroup::Octahedral).expect("the octahedral group has 24 elements")
+    }
+
+    /// Create an icosahedral generative kernel (dense: 12 pitches, 20 cells, 30 morphs)
+    pub fn icosahedral() -> Self {
+        Self::new(SymmetryGroup::Icosahedral).expect("the icosahedral group has 60 elements")
+    }
+
+    /// Generate all 60 elements of the icosahedral group A₅
+    ///
+    /// A 5-fold rotation about a vertex and a 3-fold rotation about a face
+    /// generate the whole group; closure deduplicates by matrix.
+    fn generate_icosahedral_elements(ico: &Icosahedron) -> Vec<GroupElement> {
+        let face = ico.faces[0];
+        let center = ico.vertices[face[0]] + ico.vertices[face[1]] + ico.vertices[face[2]];
+        let generators = [
+            GroupElement::rotation(ico.vertices[0], 2.0 * PI / 5.0, "C5"),
+            GroupElement::rotation(center, 2.0 * PI / 3.0, "C3"),
+        ];
+        // A 5-fold and a 3-fold turn of the icosahedron always close on A₅
+        FiniteGroup::generate(&generators)
+            .expect("icosahedral generators close on 60 elements")
+            .elements
+    }
+
+    /// The icosahedron, for code written against the old `icosahedron` field
+    ///
+    /// `None` unless the kernel acts on the plain (unsubdivided) icosahedron.
+    #[deprecated(note = "use the `geometry` field")]
+    pub fn icosahedron(&self) -> Option<Icosahedron> {
+        let plain = self.group == SymmetryGroup::Icosahedral && self.geometry.vertices.len() == 12;
+        plain.then(|| Icosahedron {
+            vertices: self.geometry.vertices.clone(),
+            faces: self.geometry.faces.clone(),
+            edges: self.geometry.edges.clone(),
+        })
+    }
+
+    /// The elements as a finite group with its Cayley table
+    pub fn group_structure(&self) -> Result<FiniteGroup, TopologyError> {
+        FiniteGroup::from_elements(self.elements.clone())
+    }
+
+    /// Prove the elements form the declared group
+    ///
+    /// Checks closure, the group axioms and the order, and, for A₅, the
+    /// character table.
+    pub fn verify(&self) -> Result<FiniteGroup, TopologyError> {
+        let group = self.group_structure()?;
+        if group.order() != self.group.order() {
+            return Err(TopologyError::group_structure(format!(
+                "{:?} has order {}, but the elements close on {}",
+                self.group,
+                self.group.order(),
+                group.order()
+            )));
+        }
+        group.verify()?;
+        Ok(group)
     }
 
     /// Generate a transformation space from the kernel
@@ -282,22 +572,23 @@ impl GenerativeKernel {
         }
     }
 
-    /// 12 vertices → 12 pitch classes
+    /// Vertices → pitch classes (see `tuning::vertex_pitches`)
     fn generate_pitch_classes(&self) -> Vec<PitchClass> {
-        let note_names = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+        let pitches = vertex_pitches(&self.geometry);
 
-        self.icosahedron.vertices.iter().enumerate()
+        self.geometry.vertices.iter().enumerate()
             .map(|(i, v)| PitchClass {
                 index: i,
-                name: note_names[i % 12].to_string(),
+                pitch: pitches[i],
+                name: NOTE_NAMES[pitches[i]].to_string(),
                 position: *v,
             })
             .collect()
     }
 
-    /// 20 faces → 20 rhythmic cells
+    /// Faces → rhythmic cells
     fn generate_rhythmic_cells(&self) -> Vec<RhythmicCell> {
-        self.icosahedron.faces.iter().enumerate()
+        self.geometry.faces.iter().enumerate()
             .map(|(i, face)| {
                 // Each face has 3-fold symmetry
                 RhythmicCell {
@@ -309,12 +600,12 @@ impl GenerativeKernel {
             .collect()
     }
 
-    /// 30 edges → 30 timbral morphs
+    /// Edges → timbral morphs
     fn generate_timbral_morphs(&self) -> Vec<TimbralMorph> {
-        self.icosahedron.edges.iter().enumerate()
+        self.geometry.edges.iter().enumerate()
             .map(|(i, edge)| {
-                let v0 = self.icosahedron.vertices[edge[0]];
-                let v1 = self.icosahedron.vertices[edge[1]];
+                let v0 = self.geometry.vertices[edge[0]];
+                let v1 = self.geometry.vertices[edge[1]];
 
                 TimbralMorph {
                     index: i,
@@ -349,15 +640,17 @@ impl GenerativeKernel {
     }
 }
 
-/// A pitch class generated from icosahedral vertex
+/// A pitch class generated from a kernel vertex
 #[derive(Debug, Clone)]
 pub struct PitchClass {
     pub index: usize,
+    /// Pitch class, 0 = C … 11 = B
+    pub pitch: usize,
     pub name: String,
     pub position: Vector3<f64>,
 }
 
-/// A rhythmic cell generated from icosahedral face
+/// A rhythmic cell generated from a kernel face
 #[derive(Debug, Clone)]
 pub struct RhythmicCell {
     pub index: usize,
@@ -365,7 +658,7 @@ pub struct RhythmicCell {
     pub pulse_pattern: Vec<f64>,
 }
 
-/// A timbral morph generated from icosahedral edge
+/// A timbral morph generated from a kernel edge
 #[derive(Debug, Clone)]
 pub struct TimbralMorph {
     pub index: usize,
@@ -384,40 +677,57 @@ pub struct TransformationSpace {
 }
 
 impl TransformationSpace {
-    /// Get pitch class by index (mod 12)
+    /// Get pitch class by index (wraps around)
+    ///
+    /// Kernel spaces are never empty; use `get_pitch` for hand-built ones.
     pub fn pitch(&self, index: usize) -> &PitchClass {
-        &self.pitch_classes[index % 12]
+        &self.pitch_classes[index % self.pitch_classes.len()]
     }
 
-    /// Get rhythmic cell by index (mod 20)
+    /// Get rhythmic cell by index (wraps around)
     pub fn rhythm(&self, index: usize) -> &RhythmicCell {
-        &self.rhythmic_cells[index % 20]
+        &self.rhythmic_cells[index % self.rhythmic_cells.len()]
     }
 
-    /// Get timbral morph by index (mod 30)
+    /// Get timbral morph by index (wraps around)
     pub fn timbre(&self, index: usize) -> &TimbralMorph {
-        &self.timbral_morphs[index % 30]
+        &self.timbral_morphs[index % self.timbral_morphs.len()]
+    }
+
+    /// Get pitch class by index (wraps around; `None` if there are none)
+    pub fn get_pitch(&self, index: usize) -> Option<&PitchClass> {
+        self.pitch_classes.get(index % self.pitch_classes.len().max(1))
+    }
+
+    /// Get rhythmic cell by index (wraps around; `None` if there are none)
+    pub fn get_rhythm(&self, index: usize) -> Option<&RhythmicCell> {
+        self.rhythmic_cells.get(index % self.rhythmic_cells.len().max(1))
+    }
+
+    /// Get timbral morph by index (wraps around; `None` if there are none)
+    pub fn get_timbre(&self, index: usize) -> Option<&TimbralMorph> {
+        self.timbral_morphs.get(index % self.timbral_morphs.len().max(1))
     }
 
     /// Compute a composite transformation index from pitch, rhythm, timbre
     pub fn composite_index(&self, pitch: usize, rhythm: usize, timbre: usize) -> usize {
-        // Maps to element of A₅ via Chinese Remainder-like construction
-        (pitch + rhythm * 12 + timbre * 12 * 20) % self.symmetry_order
+        // Maps to a group element via a Chinese Remainder-like construction
+        let (pitches, rhythms) = (self.pitch_classes.len(), self.rhythmic_cells.len());
+        (pitch + rhythm * pitches + timbre * pitches * rhythms) % self.symmetry_order.max(1)
     }
 }
 
 /// Trait for generative kernel behavior
 pub trait GenerativeKernelTrait {
-    const SYMMETRY_GROUP: SymmetryGroup;
-
     fn generate_space(&self) -> TransformationSpace;
     fn strain(&self) -> f64;
     fn is_coherent(&self) -> bool;
+
+    /// The symmetry group of this kernel
+    fn symmetry_group(&self) -> SymmetryGroup;
 }
 
 impl GenerativeKernelTrait for GenerativeKernel {
-    const SYMMETRY_GROUP: SymmetryGroup = SymmetryGroup::Icosahedral;
-
     fn generate_space(&self) -> TransformationSpace {
         self.generate_space()
     }
@@ -429,6 +739,10 @@ impl GenerativeKernelTrait for GenerativeKernel {
     fn is_coherent(&self) -> bool {
         self.is_coherent()
     }
+
+    fn symmetry_group(&self) -> SymmetryGroup {
+        self.group
+    }
 }
 
 #[cfg(test)]
@@ -460,7 +774,7 @@ mod tests {
     #[test]
     fn test_identity_element() {
         let e = GroupElement::identity();
-        assert_eq!(e.order, 1);
+        assert_eq!(e.order().unwrap(), 1);
 
         let point = Vector3::new(1.0, 2.0, 3.0);
         let transformed = e.apply(&point);
@@ -493,7 +807,7 @@ mod tests {
     fn test_5fold_rotation() {
         let axis = Vector3::new(0.0, 1.0, PHI).normalize();
         let rotation = GroupElement::rotation(axis, 2.0 * PI / 5.0, "C5");
-        assert_eq!(rotation.order, 5);
+        assert_eq!(rotation.order().unwrap(), 5);
     }
 
     #[test]
@@ -506,7 +820,85 @@ mod tests {
         let g3 = g1.compose(g2);
 
         // The result should have a valid order
-        assert!(g3.order >= 1 && g3.order <= 60);
+        assert!((1..=60).contains(&g3.order().unwrap()));
+    }
+
+    #[test]
+    fn test_every_symmetry_group_has_a_kernel() {
+        let groups = [
+            SymmetryGroup::Trivial,
+            SymmetryGroup::Cyclic(5),
+            SymmetryGroup::Dihedral(3),
+            SymmetryGroup::Dihedral(6),
+            SymmetryGroup::Tetrahedral,
+            SymmetryGroup::Octahedral,
+            SymmetryGroup::Icosahedral,
+        ];
+        for group in groups {
+            let kernel = GenerativeKernel::new(group).unwrap();
+            assert_eq!(kernel.elements.len(), group.order(), "{:?}", group);
+            assert!(kernel.verify().is_ok(), "{:?}", group);
+            assert_eq!(GenerativeKernelTrait::symmetry_group(&kernel), group);
+        }
+    }
+
+    #[test]
+    fn test_elements_preserve_geometry() {
+        for kernel in [GenerativeKernel::cyclic(4).unwrap(), GenerativeKernel::dihedral(5).unwrap(), GenerativeKernel::tetrahedral(), GenerativeKernel::octahedral()] {
+            let vertices = &kernel.geometry.vertices;
+            for element in &kernel.elements {
+                for v in vertices {
+                    let image = element.apply(v);
+                    assert!(vertices.iter().any(|w| (w - image).norm() < 1e-9), "{}", kernel.geometry.name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_space_sizes_by_symmetry_level() {
+        let sizes = |kernel: GenerativeKernel| {
+            let space = kernel.generate_space();
+            (space.pitch_classes.len(), space.rhythmic_cells.len(), space.timbral_morphs.len(), space.symmetry_order)
+        };
+        assert_eq!(sizes(GenerativeKernel::trivial()), (3, 1, 3, 1));
+        assert_eq!(sizes(GenerativeKernel::cyclic(7).unwrap()), (8, 7, 14, 7));
+        assert_eq!(sizes(GenerativeKernel::dihedral(4).unwrap()), (6, 8, 12, 8));
+        assert_eq!(sizes(GenerativeKernel::tetrahedral()), (4, 4, 6, 12));
+        assert_eq!(sizes(GenerativeKernel::octahedral()), (6, 8, 12, 24));
+
+        let space = GenerativeKernel::tetrahedral().generate_space();
+        assert_eq!(space.timbre(7).index, 1);
+        assert!(space.composite_index(3, 3, 5) < 12);
+    }
+
+    #[test]
+    fn test_order_one_kernels_have_faces() {
+        for kernel in [GenerativeKernel::cyclic(1).unwrap(), GenerativeKernel::dihedral(1).unwrap()] {
+            assert_eq!(kernel.elements.len(), kernel.group.order());
+            assert!(kernel.verify().is_ok());
+            let space = kernel.generate_space();
+            assert_eq!(space.rhythm(0).index, 0);
+            assert!(space.get_timbre(5).is_some());
+        }
+        let empty = TransformationSpace { pitch_classes: vec![], rhythmic_cells: vec![], timbral_morphs: vec![], symmetry_order: 0 };
+        assert!(empty.get_pitch(0).is_none() && empty.get_rhythm(3).is_none());
+        assert_eq!(empty.composite_index(1, 2, 3), 0);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_icosahedron_accessor() {
+        let ico = GenerativeKernel::icosahedral().icosahedron().unwrap();
+        assert_eq!((ico.vertices.len(), ico.faces.len(), ico.edges.len()), (12, 20, 30));
+        assert!(GenerativeKernel::octahedral().icosahedron().is_none());
+    }
+
+    #[test]
+    fn test_oversized_groups_are_rejected() {
+        assert!(matches!(GenerativeKernel::new(SymmetryGroup::Abstract(168)), Err(TopologyError::GroupStructure { .. })));
+        assert!(matches!(GenerativeKernel::cyclic(20_000), Err(TopologyError::GroupStructure { .. })));
+        assert!(GenerativeKernel::dihedral(MAX_GROUP_ORDER / 2 + 1).is_err());
     }
 
     #[test]
@@ -515,5 +907,24 @@ mod tests {
         let dodeca = ico.dual();
 
         assert_eq!(dodeca.vertices.len(), 20); // 20 face centers of icosahedron
+        assert_eq!(dodeca.faces.len(), 12);
+        assert_eq!(dodeca.edges.len(), 30);
+        assert_eq!(dodeca.vertices.len() + dodeca.faces.len() - dodeca.edges.len(), 2);
+
+        // Every vertex has three neighbours and sits on three faces
+        let adjacency = dodeca.adjacency();
+        assert!(adjacency.iter().all(|n| n.len() == 3));
+        assert!((0..20).all(|v| dodeca.faces_at(v).len() == 3));
+
+        // Pentagon sides are edges of equal length; face j sits over vertex j
+        let side = (dodeca.vertices[dodeca.edges[0][0]] - dodeca.vertices[dodeca.edges[0][1]]).norm();
+        for (j, face) in dodeca.faces.iter().enumerate() {
+            for k in 0..5 {
+                let (a, b) = (face[k], face[(k + 1) % 5]);
+                assert!(adjacency[a].contains(&b));
+                assert!(((dodeca.vertices[a] - dodeca.vertices[b]).norm() - side).abs() < 1e-9);
+            }
+            assert!((dodeca.face_center(j) - ico.vertices[j]).norm() < 1e-9);
+        }
     }
 }
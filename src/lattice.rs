@@ -0,0 +1,481 @@
+//! Lattice Gauge Theory - discrete connections on the Concept Complex.
+//!
+//! Ideas are lattice sites, relations are links, and the kernel's rotation
+//! group supplies the parallel transporters. Each oriented edge u → v
+//! carries a group element U_uv, with U_vu = U_uv⁻¹.
+//!
+//! - Plaquette: the holonomy around a triangle, U_wu U_vw U_uv.
+//! - A cycle is flat when its holonomy is the identity. A flat triangle is
+//!   a coherent relation; a curved one is frustrated.
+//! - Wilson action: S = Σ_p (1 - tr U_p / 3), sampled with Metropolis at
+//!   inverse temperature β. Large β freezes the lattice into flat,
+//!   pure-gauge configurations; β = 0 lets every link wander freely.
+
+use crate::kernel::GroupElement;
+use crate::simplex::{cycle_basis, ConceptComplex};
+use crate::types::*;
+use nalgebra::Matrix3;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::BTreeMap;
+
+/// Holonomies closer than this to the identity count as flat
+pub const FLATNESS_TOLERANCE: f64 = 1e-9;
+
+/// A group element on every edge of a complex
+#[derive(Debug, Clone)]
+pub struct LatticeConnection {
+    /// U_uv for each edge (u, v) with u < v
+    pub links: BTreeMap<(usize, usize), GroupElement>,
+}
+
+/// Holonomy around one triangle of the complex
+#[derive(Debug, Clone)]
+pub struct Plaquette {
+    /// The triangle, walked in increasing vertex order
+    pub vertices: [usize; 3],
+    /// U_wu U_vw U_uv
+    pub holonomy: Matrix3<f64>,
+}
+
+/// Holonomy around a fundamental cycle of the 1-skeleton
+#[derive(Debug, Clone)]
+pub struct CycleHolonomy {
+    /// Closed walk of ideas (first not repeated)
+    pub vertices: Vec<usize>,
+    /// Product of the links around the walk
+    pub holonomy: Matrix3<f64>,
+}
+
+/// Rotation angle of a 3×3 rotation, from its trace
+fn rotation_angle(m: &Matrix3<f64>) -> f64 {
+    ((m.trace() - 1.0) / 2.0).clamp(-1.0, 1.0).acos()
+}
+
+fn is_identity(m: &Matrix3<f64>) -> bool {
+    (m - Matrix3::identity()).norm() < FLATNESS_TOLERANCE
+}
+
+impl Plaquette {
+    /// Angle of the plaquette rotation
+    pub fn angle(&self) -> f64 {
+        rotation_angle(&self.holonomy)
+    }
+
+    /// Check if the triangle is flat
+    pub fn is_flat(&self) -> bool {
+        is_identity(&self.holonomy)
+    }
+
+    /// Contribution to the Wilson action: 1 - tr U / 3
+    pub fn action(&self) -> f64 {
+        1.0 - self.holonomy.trace() / 3.0
+    }
+}
+
+impl CycleHolonomy {
+    /// Angle of the cycle's rotation
+    pub fn angle(&self) -> f64 {
+        rotation_angle(&self.holonomy)
+    }
+
+    /// Check if transport around the cycle returns unchanged
+    pub fn is_flat(&self) -> bool {
+        is_identity(&self.holonomy)
+    }
+}
+
+impl LatticeConnection {
+    /// Identity on every edge of the complex
+    pub fn trivial(complex: &ConceptComplex) -> Self {
+        let links = edges_of(complex).into_iter().map(|e| (e, GroupElement::identity())).collect();
+        Self { links }
+    }
+
+    /// Independent uniform choices from `elements` on every edge
+    ///
+    /// The same seed always draws the same links.
+    pub fn random(complex: &ConceptComplex, elements: &[GroupElement], seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let links = edges_of(complex).into_iter()
+            .map(|e| {
+                let g = if elements.is_empty() {
+                    GroupElement::identity()
+                } else {
+                    elements[rng.gen_range(0..elements.len())].clone()
+                };
+                (e, g)
+            })
+            .collect();
+        Self { links }
+    }
+
+    /// Set U_ab (U_ba becomes its inverse)
+    pub fn set_link(&mut self, a: usize, b: usize, element: GroupElement) -> Result<&mut Self, TopologyError> {
+        let key = (a.min(b), a.max(b));
+        let link = self.links.get_mut(&key)
+            .ok_or_else(|| TopologyError::not_found(format!("edge {}-{}", key.0, key.1)))?;
+        *link = if a < b { element } else { element.inverse() };
+        Ok(self)
+    }
+
+    /// Transporter from `a` to `b`, if they are joined
+    pub fn link(&self, a: usize, b: usize) -> Option<Matrix3<f64>> {
+        let element = self.links.get(&(a.min(b), a.max(b)))?;
+        Some(if a < b { element.matrix } else { element.matrix.transpose() })
+    }
+
+    /// Ordered product along a walk of ideas: U_{n-1,n} ⋯ U_{01}
+    pub fn path_holonomy(&self, walk: &[usize]) -> Option<Matrix3<f64>> {
+        walk.windows(2).try_fold(Matrix3::identity(), |u, w| Some(self.link(w[0], w[1])? * u))
+    }
+
+    /// Holonomy around a closed walk (the first vertex is not repeated)
+    pub fn loop_holonomy(&self, cycle: &[usize]) -> Option<Matrix3<f64>> {
+        let mut walk = cycle.to_vec();
+        walk.extend(cycle.first());
+        self.path_holonomy(&walk)
+    }
+
+    /// Plaquettes on every triangle of the complex
+    pub fn plaquettes(&self, complex: &ConceptComplex) -> Vec<Plaquette> {
+        triangles_of(complex).into_iter()
+            .filter_map(|t| {
+                let holonomy = self.loop_holonomy(&t)?;
+                Some(Plaquette { vertices: t, holonomy })
+            })
+            .collect()
+    }
+
+    /// Holonomies around a basis of cycles of the 1-skeleton
+    pub fn cycle_holonomies(&self, complex: &ConceptComplex) -> Vec<CycleHolonomy> {
+        let mut vertices: Vec<usize> = complex.vertices.keys().copied().collect();
+        vertices.sort_unstable();
+        let edges: Vec<(usize, usize)> = self.links.keys().copied().collect();
+        cycle_basis(&vertices, &edges).into_iter()
+            .filter_map(|cycle| {
+                let holonomy = self.loop_holonomy(&cycle)?;
+                Some(CycleHolonomy { vertices: cycle, holonomy })
+            })
+            .collect()
+    }
+
+    /// Cycles whose holonomy is not the identity
+    pub fn curved_cycles(&self, complex: &ConceptComplex) -> Vec<CycleHolonomy> {
+        self.cycle_holonomies(complex).into_iter().filter(|c| !c.is_flat()).collect()
+    }
+
+    /// Check if every cycle of the 1-skeleton is flat
+    pub fn is_flat(&self, complex: &ConceptComplex) -> bool {
+        self.cycle_holonomies(complex).iter().all(CycleHolonomy::is_flat)
+    }
+
+    /// Wilson action Σ_p (1 - tr U_p / 3)
+    pub fn wilson_action(&self, complex: &ConceptComplex) -> f64 {
+        self.plaquettes(complex).iter().map(Plaquette::action).sum()
+    }
+
+    /// Gauge transformation U_uv → g_v U_uv g_u⁻¹ (missing sites keep the identity)
+    pub fn gauge_transform(&self, gauge: &BTreeMap<usize, GroupElement>) -> Self {
+        let identity = GroupElement::identity();
+        let links = self.links.iter()
+            .map(|(&(u, v), link)| {
+                let g_u = gauge.get(&u).unwrap_or(&identity);
+                let g_v = gauge.get(&v).unwrap_or(&identity);
+                ((u, v), g_v.compose(link).compose(&g_u.inverse()))
+            })
+            .collect();
+        Self { links }
+    }
+}
+
+/// Edges of the complex closed under faces, as (u, v) with u < v
+fn edges_of(complex: &ConceptComplex) -> Vec<(usize, usize)> {
+    complex.chain_complex(1).cells.iter()
+        .filter(|c| c.dimension() == 1)
+        .map(|c| (c.vertices[0], c.vertices[1]))
+        .collect()
+}
+
+/// Triangles of the complex closed under faces, vertices increasing
+fn triangles_of(complex: &ConceptComplex) -> Vec<[usize; 3]> {
+    complex.chain_complex(2).cells.iter()
+        .filter(|c| c.dimension() == 2)
+        .map(|c| [c.vertices[0], c.vertices[1], c.vertices[2]])
+        .collect()
+}
+
+/// Summary of a Metropolis run
+#[derive(Debug, Clone)]
+pub struct MonteCarloRun {
+    /// Fraction of proposals accepted
+    pub acceptance_rate: f64,
+    /// Wilson action after every measured sweep
+    pub actions: Vec<f64>,
+    /// Mean of tr U_p / 3 over plaquettes and measured sweeps
+    pub mean_plaquette: f64,
+}
+
+impl MonteCarloRun {
+    /// Mean action over measured sweeps
+    pub fn mean_action(&self) -> f64 {
+        if self.actions.is_empty() {
+            0.0
+        } else {
+            self.actions.iter().sum::<f64>() / self.actions.len() as f64
+        }
+    }
+}
+
+/// Metropolis sampling of lattice connections with the Wilson action
+#[derive(Debug, Clone)]
+pub struct LatticeGauge {
+    /// Current configuration
+    pub connection: LatticeConnection,
+    /// Inverse temperature β
+    pub beta: f64,
+    /// Group the links take values in
+    pub elements: Vec<GroupElement>,
+    edges: Vec<(usize, usize)>,
+    triangles: Vec<[usize; 3]>,
+    /// Triangles containing each edge (indices into `triangles`)
+    edge_triangles: Vec<Vec<usize>>,
+    rng: StdRng,
+}
+
+impl LatticeGauge {
+    /// A cold (all-identity) start on the complex, with β = 1 and seed 0
+    pub fn new(complex: &ConceptComplex, elements: Vec<GroupElement>) -> Self {
+        let edges = edges_of(complex);
+        let triangles = triangles_of(complex);
+        let edge_triangles = edges.iter()
+            .map(|&(u, v)| {
+                triangles.iter().enumerate()
+                    .filter(|(_, t)| t.contains(&u) && t.contains(&v))
+                    .map(|(i, _)| i)
+                    .collect()
+            })
+            .collect();
+        Self {
+            connection: LatticeConnection::trivial(complex),
+            beta: 1.0,
+            elements,
+            edges,
+            triangles,
+            edge_triangles,
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+
+    /// Reseed the sampler; the same seed replays the same chain
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Set the inverse temperature
+    pub fn with_beta(mut self, beta: f64) -> Self {
+        self.beta = beta.max(0.0);
+        self
+    }
+
+    /// Start from independent random links instead of the identity
+    ///
+    /// Single-link updates can trap a hot start in a frustrated state at
+    /// large β; raise β gradually between runs to anneal.
+    pub fn hot_start(mut self) -> Self {
+        if !self.elements.is_empty() {
+            for link in self.connection.links.values_mut() {
+                *link = self.elements[self.rng.gen_range(0..self.elements.len())].clone();
+            }
+        }
+        self
+    }
+
+    fn triangle_action(&self, t: usize) -> f64 {
+        self.connection.loop_holonomy(&self.triangles[t])
+            .map_or(0.0, |u| 1.0 - u.trace() / 3.0)
+    }
+
+    /// Current Wilson action
+    pub fn action(&self) -> f64 {
+        (0..self.triangles.len()).map(|t| self.triangle_action(t)).sum()
+    }
+
+    /// Mean tr U_p / 3 over plaquettes (1 when flat)
+    pub fn mean_plaquette(&self) -> f64 {
+        if self.triangles.is_empty() {
+            return 1.0;
+        }
+        1.0 - self.action() / self.triangles.len() as f64
+    }
+
+    /// One Metropolis update per edge; returns the number accepted
+    pub fn sweep(&mut self) -> usize {
+        if self.elements.is_empty() {
+            return 0;
+        }
+        let mut accepted = 0;
+        for e in 0..self.edges.len() {
+            let key = self.edges[e];
+            let proposal = self.elements[self.rng.gen_range(0..self.elements.len())].clone();
+            let local = |gauge: &Self| -> f64 {
+                gauge.edge_triangles[e].iter().map(|&t| gauge.triangle_action(t)).sum()
+            };
+
+            let before = local(self);
+            let previous = self.connection.links.insert(key, proposal);
+            let delta = local(self) - before;
+
+            if delta <= 0.0 || self.rng.gen::<f64>() < (-self.beta * delta).exp() {
+                accepted += 1;
+            } else if let Some(previous) = previous {
+                self.connection.links.insert(key, previous);
+            }
+        }
+        accepted
+    }
+
+    /// Thermalize for `thermalization` sweeps, then measure `sweeps` more
+    pub fn run(&mut self, thermalization: usize, sweeps: usize) -> MonteCarloRun {
+        for _ in 0..thermalization {
+            self.sweep();
+        }
+
+        let mut accepted = 0;
+        let mut actions = Vec::with_capacity(sweeps);
+        let mut plaquette_total = 0.0;
+        for _ in 0..sweeps {
+            accepted += self.sweep();
+            actions.push(self.action());
+            plaquette_total += self.mean_plaquette();
+        }
+
+        let proposals = sweeps * self.edges.len();
+        MonteCarloRun {
+            acceptance_rate: if proposals == 0 { 0.0 } else { accepted as f64 / proposals as f64 },
+            actions,
+            mean_plaquette: if sweeps == 0 { self.mean_plaquette() } else { plaquette_total / sweeps as f64 },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernel::GenerativeKernel;
+    use crate::simplex::ComplexBuilder;
+    use nalgebra::Vector3;
+    use std::f64::consts::PI;
+
+    fn complex(ideas: usize, edges: &[(usize, usize)], triangles: &[(usize, usize, usize)]) -> ConceptComplex {
+        let mut builder = ComplexBuilder::new();
+        for i in 0..ideas {
+            builder.add_idea(format!("Idea {}", i));
+        }
+        for &(a, b) in edges {
+            builder.connect(a, b);
+        }
+        for &(a, b, c) in triangles {
+            builder.add_triangle(a, b, c);
+        }
+        builder.build()
+    }
+
+    /// Boundary of a tetrahedron: four triangles, every edge in two
+    fn sphere() -> ConceptComplex {
+        complex(4, &[], &[(0, 1, 2), (0, 1, 3), (0, 2, 3), (1, 2, 3)])
+    }
+
+    fn quarter_turn() -> GroupElement {
+        GroupElement::rotation(Vector3::z(), PI / 2.0, "C4")
+    }
+
+    #[test]
+    fn test_trivial_connection_is_flat() {
+        let sphere = sphere();
+        let connection = LatticeConnection::trivial(&sphere);
+        assert_eq!(connection.links.len(), 6);
+        assert_eq!(connection.plaquettes(&sphere).len(), 4);
+        assert!(connection.is_flat(&sphere));
+        assert!(connection.wilson_action(&sphere).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_twisted_link_curves_its_plaquettes() {
+        let sphere = sphere();
+        let mut connection = LatticeConnection::trivial(&sphere);
+        connection.set_link(2, 1, quarter_turn()).unwrap();
+        assert!((connection.link(1, 2).unwrap() - quarter_turn().matrix.transpose()).norm() < 1e-12);
+
+        // Edge 1-2 lies on two triangles; both see a quarter turn
+        let curved: Vec<_> = connection.plaquettes(&sphere).into_iter().filter(|p| !p.is_flat()).collect();
+        assert_eq!(curved.len(), 2);
+        assert!(curved.iter().all(|p| (p.angle() - PI / 2.0).abs() < 1e-9));
+        assert!(!connection.curved_cycles(&sphere).is_empty());
+        assert!(matches!(connection.set_link(0, 9, quarter_turn()), Err(TopologyError::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_hollow_cycle_holonomy() {
+        // A square of ideas with no triangles: only the cycle can be curved
+        let square = complex(4, &[(0, 1), (1, 2), (2, 3), (0, 3)], &[]);
+        let mut connection = LatticeConnection::trivial(&square);
+        assert!(connection.plaquettes(&square).is_empty());
+
+        connection.set_link(0, 1, quarter_turn()).unwrap();
+        let cycles = connection.cycle_holonomies(&square);
+        assert_eq!(cycles.len(), 1);
+        assert!((cycles[0].angle() - PI / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_gauge_invariance() {
+        let sphere = sphere();
+        let elements = GenerativeKernel::icosahedral().elements;
+        let connection = LatticeConnection::random(&sphere, &elements, 5);
+        let gauge: BTreeMap<usize, GroupElement> = (0..4).map(|v| (v, elements[7 * v + 3].clone())).collect();
+        let transformed = connection.gauge_transform(&gauge);
+
+        assert!((connection.wilson_action(&sphere) - transformed.wilson_action(&sphere)).abs() < 1e-9);
+
+        // Pure gauge: transforming the trivial connection leaves every cycle flat
+        let pure = LatticeConnection::trivial(&sphere).gauge_transform(&gauge);
+        assert!(pure.links.values().any(|g| g.order().unwrap() > 1));
+        assert!(pure.is_flat(&sphere));
+    }
+
+    #[test]
+    fn test_monte_carlo_orders_at_large_beta() {
+        let sphere = sphere();
+        let elements = GenerativeKernel::icosahedral().elements;
+
+        // β = 0: every proposal is accepted and the plaquettes disorder
+        let free = LatticeGauge::new(&sphere, elements.clone()).with_beta(0.0).run(5, 50);
+        assert!((free.acceptance_rate - 1.0).abs() < 1e-12);
+        assert!(free.mean_plaquette < 0.5, "mean plaquette {}", free.mean_plaquette);
+
+        // Large β: a cold start stays frozen in the flat configuration
+        let ordered = LatticeGauge::new(&sphere, elements).with_beta(30.0).run(20, 50);
+        assert!(ordered.mean_plaquette > 0.99, "mean plaquette {}", ordered.mean_plaquette);
+        assert!(ordered.mean_action() < free.mean_action());
+    }
+
+    #[test]
+    fn test_seeded_runs_replay() {
+        let sphere = sphere();
+        let elements = GenerativeKernel::icosahedral().elements;
+        let chain = |seed: u64| {
+            LatticeGauge::new(&sphere, elements.clone()).with_beta(2.0).with_seed(seed).hot_start().run(5, 20).actions
+        };
+        assert_eq!(chain(3), chain(3));
+        assert_ne!(chain(3), chain(4));
+
+        let links = |seed: u64| -> Vec<usize> {
+            LatticeConnection::random(&sphere, &elements, seed).links.values()
+                .map(|g| elements.iter().position(|h| (h.matrix - g.matrix).norm() < 1e-12).unwrap())
+                .collect()
+        };
+        assert_eq!(links(9), links(9));
+    }
+}
@@ -96,9 +96,33 @@ pub mod types;
 pub mod manifold;
 pub mod simplex;
 pub mod bundle;
+pub mod belief;
+pub mod population;
+pub mod gauge;
+pub mod estimation;
 pub mod kernel;
+pub mod group;
+pub mod breaking;
+pub mod midi;
+pub mod synth;
+pub mod tuning;
+pub mod theory;
+pub mod quaternion;
+pub mod geodesic;
+pub mod permutation;
+pub mod presentation;
 pub mod fhp;
+pub mod berry;
 pub mod gravity;
+pub mod io;
+pub mod mapper;
+pub mod homology;
+pub mod flag;
+pub mod sheaf;
+pub mod zigzag;
+pub mod centrality;
+pub mod morse;
+pub mod lattice;
 
 /// Prelude for convenient imports
 pub mod prelude {
@@ -123,19 +147,43 @@ pub mod prelude {
     };
     pub use crate::manifold::{CognitiveManifold, ManifoldBuilder, PotentialThought};
     pub use crate::simplex::{ComplexBuilder, ConceptComplex, HomologicalHole, Idea, Relation};
+    pub use crate::mapper::{Mapper, MapperCluster, MapperResult};
+    pub use crate::homology::{ChainComplex, PersistenceInterval};
+    pub use crate::flag::{CycleKind, DirectedComplexBuilder, DirectedFlagComplex};
+    pub use crate::sheaf::{CellularSheaf, SheafCohomology, SheafEdge, SheafTriangle};
+    pub use crate::zigzag::{ZigzagInterval, ZigzagSequence};
+    pub use crate::centrality::{Bottleneck, CentralityReport, SimplicialWalk};
+    pub use crate::morse::MorseReduction;
+    pub use crate::lattice::{CycleHolonomy, LatticeConnection, LatticeGauge, MonteCarloRun, Plaquette};
     pub use crate::bundle::{
-        ConnectionForm, FiberBundle, HolonomyResult, Perspective, PerspectivalSelf, WorldPoint,
+        ConnectionForm, CurvatureDiagnostic, CurvatureForm, FiberBundle, HolonomyResult, LieAlgebra,
+        Perspective, PerspectivalSelf, WorldPoint,
     };
+    pub use crate::belief::{BeliefChange, Evidence, Proposition};
+    pub use crate::population::{Agent, ConsensusPoint, Population};
+    pub use crate::gauge::{GaugeCovariance, GaugeTransformation, GaugedConnection};
+    pub use crate::estimation::{ConnectionEstimator, ConnectionFit, StepResidual};
     pub use crate::kernel::{
-        GenerativeKernel, GenerativeKernelTrait, GroupElement, Icosahedron, PitchClass,
-        RhythmicCell, SymmetryGroup, TimbralMorph, TransformationSpace,
+        Dodecahedron, GenerativeKernel, GenerativeKernelTrait, GroupElement, Icosahedron, PitchClass,
+        Polyhedron, RhythmicCell, SymmetryGroup, TimbralMorph, TransformationSpace,
         PHI_INV,
     };
+    pub use crate::group::{CharacterTable, ConjugacyClass, FiniteGroup, Subgroup, SubgroupLattice};
+    pub use crate::breaking::StrainModel;
+    pub use crate::midi::{MidiEvent, MidiFile, MidiMessage, MidiTrack, SequenceStep, Sequencer, Walk};
+    pub use crate::synth::{BitDepth, Synthesizer, Timbre, WavFile};
+    pub use crate::tuning::{Tuning, TuningMatch, MIDDLE_C};
+    pub use crate::theory::{PitchClassSet, PlrMove, PlrRealization, Quality, Tonnetz, TonnetzTriangle, Triad};
+    pub use crate::quaternion::{unit_quaternion, BinaryGroup};
+    pub use crate::geodesic::OrbitLabels;
+    pub use crate::permutation::{OrbitRoles, Permutation, PermutationGroup, PermutationKernel};
+    pub use crate::presentation::Presentation;
     pub use crate::fhp::{
         TauK, TauQubit, KuramotoNetwork, MultiScaleField, TemporalScale,
         Seed65, FragmentType, PathwayDistribution, CoherenceResult, BridgeResult,
         FUNDAMENTAL_FREQ, SEED_65_STRAIN,
     };
+    pub use crate::berry::{BerryPhase, ParameterLoop};
     pub use crate::gravity::{
         HarmonicSignature, HarmonicResidue, HarmonicField, HarmonicFieldState,
         PressureGradient, ElectricalFlow, GravitationalResidue,
@@ -152,15 +200,41 @@ pub use types::{
 };
 pub use manifold::{CognitiveManifold, ManifoldBuilder, PotentialThought};
 pub use simplex::{ComplexBuilder, ConceptComplex, HomologicalHole, Idea, Relation};
-pub use bundle::{ConnectionForm, FiberBundle, HolonomyResult, Perspective, PerspectivalSelf, WorldPoint};
+pub use mapper::{Mapper, MapperCluster, MapperResult};
+pub use homology::{ChainComplex, PersistenceInterval};
+pub use flag::{CycleKind, DirectedComplexBuilder, DirectedFlagComplex};
+pub use sheaf::{CellularSheaf, SheafCohomology, SheafEdge, SheafTriangle};
+pub use zigzag::{ZigzagInterval, ZigzagSequence};
+pub use centrality::{Bottleneck, CentralityReport, SimplicialWalk};
+pub use morse::MorseReduction;
+pub use lattice::{CycleHolonomy, LatticeConnection, LatticeGauge, MonteCarloRun, Plaquette};
+pub use bundle::{
+    ConnectionForm, CurvatureDiagnostic, CurvatureForm, FiberBundle, HolonomyResult, LieAlgebra, Perspective,
+    PerspectivalSelf, WorldPoint,
+};
+pub use belief::{BeliefChange, Evidence, Proposition};
+pub use population::{Agent, ConsensusPoint, Population};
+pub use gauge::{GaugeCovariance, GaugeTransformation, GaugedConnection};
+pub use estimation::{ConnectionEstimator, ConnectionFit, StepResidual};
 pub use kernel::{
-    GenerativeKernel, GenerativeKernelTrait, GroupElement, Icosahedron, PitchClass,
-    RhythmicCell, SymmetryGroup, TimbralMorph, TransformationSpace,
+    Dodecahedron, GenerativeKernel, GenerativeKernelTrait, GroupElement, Icosahedron, PitchClass,
+    Polyhedron, RhythmicCell, SymmetryGroup, TimbralMorph, TransformationSpace,
 };
+pub use group::{CharacterTable, ConjugacyClass, FiniteGroup, Subgroup, SubgroupLattice};
+pub use breaking::StrainModel;
+pub use midi::{MidiEvent, MidiFile, MidiMessage, MidiTrack, SequenceStep, Sequencer, Walk};
+pub use synth::{BitDepth, Synthesizer, Timbre, WavFile};
+pub use tuning::{Tuning, TuningMatch, MIDDLE_C};
+pub use theory::{PitchClassSet, PlrMove, PlrRealization, Quality, Tonnetz, TonnetzTriangle, Triad};
+pub use quaternion::{unit_quaternion, BinaryGroup};
+pub use geodesic::OrbitLabels;
+pub use permutation::{OrbitRoles, Permutation, PermutationGroup, PermutationKernel};
+pub use presentation::Presentation;
 pub use fhp::{
     TauK, TauQubit, KuramotoNetwork, MultiScaleField, TemporalScale,
     Seed65, FragmentType, PathwayDistribution, CoherenceResult, BridgeResult,
 };
+pub use berry::{BerryPhase, ParameterLoop};
 pub use gravity::{
     HarmonicSignature, HarmonicResidue, HarmonicField, HarmonicFieldState,
     PressureGradient, ElectricalFlow, GravitationalResidue,
@@ -0,0 +1,322 @@
+//! The Mapper - topological summaries of high-dimensional point clouds.
+//!
+//! A lens projects the cloud to a line, overlapping intervals cover the
+//! line, each preimage is clustered, and the nerve of the clusters becomes
+//! a Concept Complex.
+//!
+//! Each cluster is an Idea whose `members` are the point indices it holds.
+
+use crate::homology::combinations;
+use crate::simplex::{ConceptComplex, Idea, Relation};
+use std::collections::{BTreeSet, HashMap};
+
+/// Lens projecting a point onto a single coordinate axis
+pub fn coordinate_lens(axis: usize) -> impl Fn(&[f64]) -> f64 {
+    move |point| point.get(axis).copied().unwrap_or(0.0)
+}
+
+/// Lens measuring Euclidean distance from a reference point
+pub fn distance_lens(reference: Vec<f64>) -> impl Fn(&[f64]) -> f64 {
+    move |point| euclidean(point, &reference)
+}
+
+fn euclidean(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt()
+}
+
+/// A closed interval of the lens range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoverInterval {
+    /// Lower lens bound
+    pub lower: f64,
+    /// Upper lens bound
+    pub upper: f64,
+}
+
+impl CoverInterval {
+    /// Check if a lens value falls inside the interval
+    pub fn contains(&self, value: f64) -> bool {
+        value >= self.lower && value <= self.upper
+    }
+}
+
+/// A cluster of one interval's preimage - a vertex of the nerve
+#[derive(Debug, Clone)]
+pub struct MapperCluster {
+    /// Id of the corresponding Idea in the output complex
+    pub idea_id: usize,
+    /// Index of the cover interval
+    pub interval: usize,
+    /// Indices of the points in the cluster
+    pub members: Vec<usize>,
+}
+
+/// The Mapper pipeline: lens → cover → cluster → nerve
+#[derive(Debug, Clone)]
+pub struct Mapper {
+    /// Number of cover intervals
+    pub intervals: usize,
+    /// Fraction of each interval shared with its neighbour (0 ≤ overlap < 1)
+    pub overlap: f64,
+    /// Single-linkage radius: points closer than this share a cluster
+    pub cluster_radius: f64,
+    /// Highest nerve dimension to emit (1 = graph, 2 = triangles, ...)
+    pub max_dimension: usize,
+}
+
+impl Mapper {
+    /// Create a Mapper with `intervals` cover intervals overlapping by `overlap`
+    pub fn new(intervals: usize, overlap: f64) -> Self {
+        Self {
+            intervals: intervals.max(1),
+            overlap: overlap.clamp(0.0, 0.95),
+            cluster_radius: 1.0,
+            max_dimension: 2,
+        }
+    }
+
+    /// Set the single-linkage clustering radius
+    pub fn with_cluster_radius(mut self, radius: f64) -> Self {
+        self.cluster_radius = radius.max(0.0);
+        self
+    }
+
+    /// Set the highest nerve dimension
+    pub fn with_max_dimension(mut self, dimension: usize) -> Self {
+        self.max_dimension = dimension.max(1);
+        self
+    }
+
+    /// Cover the lens range with overlapping intervals of equal length
+    pub fn cover(&self, min: f64, max: f64) -> Vec<CoverInterval> {
+        let n = self.intervals as f64;
+        let span = (max - min).max(1e-12);
+        let length = span / (n - (n - 1.0) * self.overlap);
+        let step = length * (1.0 - self.overlap);
+
+        let mut cover: Vec<CoverInterval> = (0..self.intervals)
+            .map(|i| {
+                let lower = min + i as f64 * step;
+                CoverInterval { lower, upper: lower + length }
+            })
+            .collect();
+
+        // Guard the endpoints against rounding
+        if let Some(last) = cover.last_mut() {
+            last.upper = last.upper.max(max);
+        }
+        cover
+    }
+
+    /// Run the pipeline and return the nerve with its clusters
+    ///
+    /// Points whose lens value is NaN or infinite lie in no interval; they
+    /// are listed in `MapperResult::unmapped` instead.
+    pub fn run(&self, points: &[Vec<f64>], lens: impl Fn(&[f64]) -> f64) -> MapperResult {
+        let values: Vec<f64> = points.iter().map(|p| lens(p)).collect();
+        let (mapped, unmapped): (Vec<usize>, Vec<usize>) = (0..points.len()).partition(|&i| values[i].is_finite());
+        let min = mapped.iter().map(|&i| values[i]).fold(f64::INFINITY, f64::min);
+        let max = mapped.iter().map(|&i| values[i]).fold(f64::NEG_INFINITY, f64::max);
+
+        let cover = if mapped.is_empty() { Vec::new() } else { self.cover(min, max) };
+
+        // Cluster each preimage
+        let mut clusters: Vec<MapperCluster> = Vec::new();
+        for (interval, range) in cover.iter().enumerate() {
+            let preimage: Vec<usize> = mapped.iter()
+                .copied()
+                .filter(|&i| range.contains(values[i]))
+                .collect();
+
+            for members in self.single_linkage(points, &preimage) {
+                clusters.push(MapperCluster {
+                    idea_id: clusters.len(),
+                    interval,
+                    members,
+                });
+            }
+        }
+
+        let complex = self.nerve(&clusters, points.len());
+        MapperResult { complex, clusters, cover, unmapped }
+    }
+
+    /// Connected components of the radius graph on a subset of points
+    fn single_linkage(&self, points: &[Vec<f64>], subset: &[usize]) -> Vec<Vec<usize>> {
+        let mut parent: Vec<usize> = (0..subset.len()).collect();
+
+        fn find(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        for i in 0..subset.len() {
+            for j in (i + 1)..subset.len() {
+                if euclidean(&points[subset[i]], &points[subset[j]]) <= self.cluster_radius {
+                    let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+                    if ri != rj {
+                        parent[ri] = rj;
+                    }
+                }
+            }
+        }
+
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (i, &point) in subset.iter().enumerate() {
+            let root = find(&mut parent, i);
+            groups.entry(root).or_default().push(point);
+        }
+
+        let mut components: Vec<Vec<usize>> = groups.into_values().collect();
+        components.sort_by_key(|c| c[0]);
+        components
+    }
+
+    /// Nerve of the cluster cover: a simplex for every set of clusters sharing a point
+    fn nerve(&self, clusters: &[MapperCluster], n_points: usize) -> ConceptComplex {
+        let mut complex = ConceptComplex::new();
+
+        let mut containing: Vec<Vec<usize>> = vec![Vec::new(); n_points];
+        for cluster in clusters {
+            complex.add_idea(
+                Idea::new(cluster.idea_id, format!("interval_{}/cluster_{}", cluster.interval, cluster.idea_id))
+                    .with_members(cluster.members.clone()),
+            );
+            for &m in &cluster.members {
+                containing[m].push(cluster.idea_id);
+            }
+        }
+
+        let mut simplices: BTreeSet<Vec<usize>> = BTreeSet::new();
+        for ids in &containing {
+            for size in 2..=(self.max_dimension + 1).min(ids.len()) {
+                for subset in combinations(ids, size) {
+                    simplices.insert(subset);
+                }
+            }
+        }
+
+        for vertices in simplices {
+            // Jaccard overlap of the clusters involved
+            let sets: Vec<&Vec<usize>> = vertices.iter().map(|&v| &clusters[v].members).collect();
+            let shared = sets[0].iter().filter(|m| sets[1..].iter().all(|s| s.contains(m))).count();
+            let union: BTreeSet<usize> = sets.iter().flat_map(|s| s.iter().copied()).collect();
+            let weight = shared as f64 / union.len().max(1) as f64;
+            complex.add_relation(Relation::new(vertices).with_weight(weight));
+        }
+
+        complex.calculate_homology();
+        complex
+    }
+}
+
+impl Default for Mapper {
+    fn default() -> Self {
+        Self::new(10, 0.3)
+    }
+}
+
+/// Output of the Mapper pipeline
+#[derive(Debug, Clone)]
+pub struct MapperResult {
+    /// The nerve, one Idea per cluster
+    pub complex: ConceptComplex,
+    /// Clusters, indexed by Idea id
+    pub clusters: Vec<MapperCluster>,
+    /// The cover of the lens range
+    pub cover: Vec<CoverInterval>,
+    /// Points whose lens value was not finite, in no cluster
+    pub unmapped: Vec<usize>,
+}
+
+impl MapperResult {
+    /// Ideas (clusters) containing a given data point
+    pub fn ideas_for_point(&self, point: usize) -> Vec<usize> {
+        self.clusters.iter()
+            .filter(|c| c.members.contains(&point))
+            .map(|c| c.idea_id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn circle(n: usize) -> Vec<Vec<f64>> {
+        (0..n)
+            .map(|i| {
+                let t = 2.0 * PI * i as f64 / n as f64;
+                vec![t.cos(), t.sin(), 0.0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cover_spans_range() {
+        let cover = Mapper::new(4, 0.25).cover(0.0, 1.0);
+        assert_eq!(cover.len(), 4);
+        assert!((cover[0].lower - 0.0).abs() < 1e-12);
+        assert!((cover[3].upper - 1.0).abs() < 1e-12);
+        // Neighbours overlap
+        assert!(cover[0].upper > cover[1].lower);
+    }
+
+    #[test]
+    fn test_circle_has_loop() {
+        let points = circle(60);
+        let result = Mapper::new(5, 0.3)
+            .with_cluster_radius(0.3)
+            .run(&points, coordinate_lens(0));
+
+        let betti = &result.complex.betti_numbers;
+        assert_eq!(betti[0], 1);
+        assert_eq!(betti[1], 1);
+    }
+
+    #[test]
+    fn test_line_is_contractible() {
+        let points: Vec<Vec<f64>> = (0..40).map(|i| vec![i as f64 * 0.1, 0.0]).collect();
+        let result = Mapper::new(6, 0.4)
+            .with_cluster_radius(0.15)
+            .run(&points, coordinate_lens(0));
+
+        assert_eq!(result.complex.betti_numbers[0], 1);
+        assert_eq!(result.complex.betti_numbers[1], 0);
+    }
+
+    #[test]
+    fn test_ideas_carry_membership() {
+        let points = circle(30);
+        let result = Mapper::new(4, 0.3)
+            .with_cluster_radius(0.4)
+            .run(&points, distance_lens(vec![1.0, 0.0, 0.0]));
+
+        for (i, _) in points.iter().enumerate() {
+            assert!(!result.ideas_for_point(i).is_empty());
+        }
+        for cluster in &result.clusters {
+            assert_eq!(result.complex.vertices[&cluster.idea_id].members, cluster.members);
+        }
+    }
+
+    #[test]
+    fn test_non_finite_lens_values_are_unmapped() {
+        let mut points = circle(60);
+        points.push(vec![f64::NAN, 0.0, 0.0]);
+        points.push(vec![f64::INFINITY, 0.0, 0.0]);
+        let result = Mapper::new(5, 0.3)
+            .with_cluster_radius(0.3)
+            .run(&points, coordinate_lens(0));
+
+        assert_eq!(result.unmapped, vec![60, 61]);
+        assert!((result.cover[4].upper - 1.0).abs() < 1e-12);
+        assert!(result.ideas_for_point(60).is_empty());
+        assert!((0..60).all(|i| !result.ideas_for_point(i).is_empty()));
+        assert_eq!(result.complex.betti_numbers[1], 1);
+    }
+}
@@ -0,0 +1,795 @@
+//! MIDI Export - hearing the transformation space.
+//!
+//! A walk through the kernel's group drives a sequencer. Each group element
+//! g carries the seed face to a face g·f₀, whose three vertices are played
+//! as a trichord figure. The figure's durations come from the cell's
+//! `pulse_pattern`. The seed edge goes to g·e₀, and that morph's
+//! interpolation path becomes controller automation across the step.
+//!
+//! The walk is either the group elements in order or a seeded random walk
+//! on the Cayley graph, so the same seed always gives the same file. Output
+//! is a Standard MIDI File (format 1) with a tempo track, ready for a DAW.
+
+use crate::group::FiniteGroup;
+use crate::kernel::{GenerativeKernel, TimbralMorph};
+use crate::types::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::path::Path as FilePath;
+
+/// Default resolution, ticks per quarter note
+pub const TICKS_PER_QUARTER: u16 = 480;
+
+/// Controller used for timbral automation by default (brightness)
+pub const BRIGHTNESS_CC: u8 = 74;
+
+/// Slowest tempo the 24-bit tempo meta event can hold (0xFFFFFF µs per quarter)
+pub const MIN_TEMPO_BPM: f64 = 60_000_000.0 / 0xFF_FFFF as f64;
+
+/// A channel message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MidiMessage {
+    /// Start a note
+    NoteOn {
+        /// Channel, 0-15
+        channel: u8,
+        /// Key number, 0-127
+        key: u8,
+        /// Velocity, 1-127
+        velocity: u8,
+    },
+    /// Stop a note
+    NoteOff {
+        /// Channel, 0-15
+        channel: u8,
+        /// Key number, 0-127
+        key: u8,
+    },
+    /// Set a controller
+    ControlChange {
+        /// Channel, 0-15
+        channel: u8,
+        /// Controller number, 0-119
+        controller: u8,
+        /// Value, 0-127
+        value: u8,
+    },
+}
+
+impl MidiMessage {
+    /// Order of simultaneous events: releases, then controllers, then notes
+    fn priority(&self) -> u8 {
+        match self {
+            Self::NoteOff { .. } => 0,
+            Self::ControlChange { .. } => 1,
+            Self::NoteOn { .. } => 2,
+        }
+    }
+
+    /// Reject channels, keys and values outside their MIDI ranges
+    fn check(&self) -> Result<(), TopologyError> {
+        match *self {
+            Self::NoteOn { channel, key, velocity } => {
+                check_range("channel", channel, 15)?;
+                check_range("key", key, 127)?;
+                check_range("velocity", velocity, 127)
+            }
+            Self::NoteOff { channel, key } => {
+                check_range("channel", channel, 15)?;
+                check_range("key", key, 127)
+            }
+            Self::ControlChange { channel, controller, value } => {
+                check_range("channel", channel, 15)?;
+                check_range("controller", controller, 119)?;
+                check_range("controller value", value, 127)
+            }
+        }
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) -> Result<(), TopologyError> {
+        self.check()?;
+        match *self {
+            Self::NoteOn { channel, key, velocity } => out.extend([0x90 | channel, key, velocity]),
+            Self::NoteOff { channel, key } => out.extend([0x80 | channel, key, 0x40]),
+            Self::ControlChange { channel, controller, value } => out.extend([0xB0 | channel, controller, value]),
+        }
+        Ok(())
+    }
+}
+
+/// Reject a data byte above its MIDI maximum
+fn check_range(what: &str, value: u8, max: u8) -> Result<(), TopologyError> {
+    if value > max {
+        return Err(TopologyError::OutOfRange { what: what.into(), value: value.into(), max: max.into() });
+    }
+    Ok(())
+}
+
+/// A message at an absolute tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MidiEvent {
+    /// Absolute time in ticks
+    pub tick: u32,
+    /// The message
+    pub message: MidiMessage,
+}
+
+/// A named track of events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MidiTrack {
+    /// Track name (meta event 0x03)
+    pub name: String,
+    /// Events in any order; sorted when written
+    pub events: Vec<MidiEvent>,
+}
+
+impl MidiTrack {
+    /// An empty track
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), events: Vec::new() }
+    }
+
+    /// Add a note
+    ///
+    /// Fails if the channel, key or velocity does not fit its MIDI range, or
+    /// if the note would end past the last representable tick.
+    pub fn note(&mut self, channel: u8, key: u8, velocity: u8, start: u32, duration: u32) -> Result<&mut Self, TopologyError> {
+        let on = MidiMessage::NoteOn { channel, key, velocity: velocity.max(1) };
+        on.check()?;
+        let end = start.checked_add(duration.max(1))
+            .ok_or_else(|| TopologyError::malformed("midi", format!("note at tick {} lasting {} overflows", start, duration)))?;
+        self.events.push(MidiEvent { tick: start, message: on });
+        self.events.push(MidiEvent { tick: end, message: MidiMessage::NoteOff { channel, key } });
+        Ok(self)
+    }
+
+    /// Add a controller change
+    ///
+    /// Fails if the channel, controller or value does not fit its MIDI range.
+    pub fn control(&mut self, channel: u8, controller: u8, value: u8, tick: u32) -> Result<&mut Self, TopologyError> {
+        let message = MidiMessage::ControlChange { channel, controller, value };
+        message.check()?;
+        self.events.push(MidiEvent { tick, message });
+        Ok(self)
+    }
+
+    /// Events in playing order
+    pub fn sorted_events(&self) -> Vec<MidiEvent> {
+        let mut events = self.events.clone();
+        events.sort_by_key(|e| (e.tick, e.message.priority()));
+        events
+    }
+
+    /// Notes as (key, start, duration), pairing each note-on with the next note-off
+    pub fn notes(&self) -> Vec<(u8, u32, u32)> {
+        let mut open: Vec<(u8, u8, u32)> = Vec::new();
+        let mut notes = Vec::new();
+        for event in self.sorted_events() {
+            match event.message {
+                MidiMessage::NoteOn { channel, key, .. } => open.push((channel, key, event.tick)),
+                MidiMessage::NoteOff { channel, key } => {
+                    if let Some(i) = open.iter().position(|&(c, k, _)| c == channel && k == key) {
+                        let (_, _, start) = open.remove(i);
+                        notes.push((key, start, event.tick - start));
+                    }
+                }
+                MidiMessage::ControlChange { .. } => {}
+            }
+        }
+        notes.sort_by_key(|&(key, start, _)| (start, key));
+        notes
+    }
+
+    /// Tick of the last event
+    pub fn end_tick(&self) -> u32 {
+        self.events.iter().map(|e| e.tick).max().unwrap_or(0)
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, TopologyError> {
+        let mut data = Vec::new();
+        if !self.name.is_empty() {
+            write_meta(&mut data, 0, 0x03, self.name.as_bytes());
+        }
+        let mut last = 0;
+        for event in self.sorted_events() {
+            write_vlq(&mut data, event.tick - last);
+            event.message.encode(&mut data)?;
+            last = event.tick;
+        }
+        write_meta(&mut data, 0, 0x2F, &[]);
+        Ok(data)
+    }
+}
+
+/// A Standard MIDI File: a tempo and a set of tracks
+#[derive(Debug, Clone, PartialEq)]
+pub struct MidiFile {
+    /// Ticks per quarter note
+    pub ticks_per_quarter: u16,
+    /// Tempo in quarter notes per minute
+    pub tempo_bpm: f64,
+    /// Content tracks (the tempo track is implicit)
+    pub tracks: Vec<MidiTrack>,
+}
+
+impl MidiFile {
+    /// An empty file at 120 bpm
+    pub fn new(ticks_per_quarter: u16) -> Self {
+        Self { ticks_per_quarter: ticks_per_quarter.max(1), tempo_bpm: 120.0, tracks: Vec::new() }
+    }
+
+    /// Set the tempo
+    pub fn with_tempo(mut self, bpm: f64) -> Self {
+        self.tempo_bpm = bpm.clamp(MIN_TEMPO_BPM, 1000.0);
+        self
+    }
+
+    /// Add a track
+    pub fn with_track(mut self, track: MidiTrack) -> Self {
+        self.tracks.push(track);
+        self
+    }
+
+    /// Length in seconds
+    pub fn duration_seconds(&self) -> f64 {
+        let ticks = self.tracks.iter().map(MidiTrack::end_tick).max().unwrap_or(0);
+        ticks as f64 / self.ticks_per_quarter as f64 * 60.0 / self.tempo_bpm
+    }
+
+    /// Encode as format 1: a tempo track followed by the content tracks
+    ///
+    /// Fails if an event holds a channel, key or value outside its MIDI range.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, TopologyError> {
+        let mut out = Vec::new();
+        out.extend(b"MThd");
+        out.extend(6u32.to_be_bytes());
+        out.extend(1u16.to_be_bytes());
+        out.extend((self.tracks.len() as u16 + 1).to_be_bytes());
+        out.extend(self.ticks_per_quarter.to_be_bytes());
+
+        let mut tempo_track = Vec::new();
+        // The meta event holds 24 bits; slower tempos saturate
+        let micros = ((60_000_000.0 / self.tempo_bpm).round() as u32).min(0xFF_FFFF);
+        write_meta(&mut tempo_track, 0, 0x51, &micros.to_be_bytes()[1..]);
+        write_meta(&mut tempo_track, 0, 0x2F, &[]);
+
+        for data in std::iter::once(Ok(tempo_track)).chain(self.tracks.iter().map(MidiTrack::encode)) {
+            let data = data?;
+            let length = u32::try_from(data.len()).map_err(|_| {
+                TopologyError::malformed("midi", format!("track of {} bytes overflows its chunk size", data.len()))
+            })?;
+            out.extend(b"MTrk");
+            out.extend(length.to_be_bytes());
+            out.extend(data);
+        }
+        Ok(out)
+    }
+
+    /// Write a `.mid` file
+    pub fn save(&self, path: impl AsRef<FilePath>) -> std::io::Result<()> {
+        let bytes = self.to_bytes().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        std::fs::write(path, bytes)
+    }
+
+    /// Decode a Standard MIDI File
+    ///
+    /// Notes and controllers are kept; other messages are skipped. Tracks
+    /// with no channel messages (tempo tracks) are dropped, and the first
+    /// tempo found becomes the file tempo. SMPTE timing is rejected.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TopologyError> {
+        let mut reader = Reader { bytes, at: 0 };
+        if reader.take(4)? != b"MThd" {
+            return Err(TopologyError::malformed("midi", "missing MThd header"));
+        }
+        let header_length = reader.u32()? as usize;
+        let _format = reader.u16()?;
+        let track_count = reader.u16()?;
+        let division = reader.u16()?;
+        reader.take(header_length.saturating_sub(6))?;
+        if division & 0x8000 != 0 {
+            return Err(TopologyError::malformed("midi", "SMPTE time division is not supported"));
+        }
+
+        let mut file = Self::new(division);
+        let mut tempo = None;
+        for _ in 0..track_count {
+            if reader.take(4)? != b"MTrk" {
+                return Err(TopologyError::malformed("midi", "missing MTrk chunk"));
+            }
+            let length = reader.u32()? as usize;
+            let (track, track_tempo) = decode_track(reader.take(length)?)?;
+            tempo = tempo.or(track_tempo);
+            if !track.events.is_empty() {
+                file.tracks.push(track);
+            }
+        }
+        if let Some(micros) = tempo {
+            file.tempo_bpm = 60_000_000.0 / micros as f64;
+        }
+        Ok(file)
+    }
+}
+
+/// Write a variable-length quantity
+fn write_vlq(out: &mut Vec<u8>, value: u32) {
+    let mut groups = vec![(value & 0x7F) as u8];
+    let mut rest = value >> 7;
+    while rest > 0 {
+        groups.push((rest & 0x7F) as u8 | 0x80);
+        rest >>= 7;
+    }
+    out.extend(groups.iter().rev());
+}
+
+fn write_meta(out: &mut Vec<u8>, delta: u32, kind: u8, data: &[u8]) {
+    write_vlq(out, delta);
+    out.extend([0xFF, kind]);
+    write_vlq(out, data.len() as u32);
+    out.extend(data);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], TopologyError> {
+        let end = self.at.checked_add(n).filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| TopologyError::malformed("midi", format!("unexpected end of data at byte {}", self.at)))?;
+        let slice = &self.bytes[self.at..end];
+        self.at = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8, TopologyError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, TopologyError> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, TopologyError> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn vlq(&mut self) -> Result<u32, TopologyError> {
+        let mut value = 0u32;
+        for _ in 0..4 {
+            let b = self.byte()?;
+            value = (value << 7) | (b & 0x7F) as u32;
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(TopologyError::malformed("midi", "variable-length quantity longer than 4 bytes"))
+    }
+
+    fn done(&self) -> bool {
+        self.at >= self.bytes.len()
+    }
+}
+
+/// Decode one track chunk; returns the track and its first tempo (µs per quarter)
+fn decode_track(data: &[u8]) -> Result<(MidiTrack, Option<u32>), TopologyError> {
+    let mut reader = Reader { bytes: data, at: 0 };
+    let mut track = MidiTrack::new("");
+    let mut tempo = None;
+    let mut tick = 0u32;
+    let mut running_status = None;
+
+    while !reader.done() {
+        tick = tick.checked_add(reader.vlq()?)
+            .ok_or_else(|| TopologyError::malformed("midi", "event time overflows the track"))?;
+        let mut status = reader.byte()?;
+        match status {
+            0xFF => {
+                let kind = reader.byte()?;
+                let length = reader.vlq()? as usize;
+                let payload = reader.take(length)?;
+                match kind {
+                    0x03 => track.name = String::from_utf8_lossy(payload).into_owned(),
+                    0x51 if payload.len() == 3 => {
+                        tempo = tempo.or(Some(u32::from_be_bytes([0, payload[0], payload[1], payload[2]])))
+                    }
+                    0x2F => break,
+                    _ => {}
+                }
+                continue;
+            }
+            0xF0 | 0xF7 => {
+                let length = reader.vlq()? as usize;
+                reader.take(length)?;
+                continue;
+            }
+            _ => {}
+        }
+
+        // Channel message, possibly in running status
+        let first = if status & 0x80 == 0 {
+            let data = status;
+            status = running_status.ok_or_else(|| TopologyError::malformed("midi", "data byte without a status"))?;
+            data
+        } else {
+            running_status = Some(status);
+            reader.byte()?
+        };
+        let channel = status & 0x0F;
+        let message = match status & 0xF0 {
+            0x80 => Some(MidiMessage::NoteOff { channel, key: first }),
+            0x90 => {
+                let velocity = reader.byte()?;
+                Some(if velocity == 0 {
+                    MidiMessage::NoteOff { channel, key: first }
+                } else {
+                    MidiMessage::NoteOn { channel, key: first, velocity }
+                })
+            }
+            // Controllers 120-127 are channel mode messages, which are skipped
+            0xB0 => {
+                let value = reader.byte()?;
+                (first < 120).then_some(MidiMessage::ControlChange { channel, controller: first, value })
+            }
+            0xA0 | 0xE0 => {
+                reader.byte()?;
+                None
+            }
+            0xC0 | 0xD0 => None,
+            _ => return Err(TopologyError::malformed("midi", format!("unknown status byte {:#04x}", status))),
+        };
+        if status & 0xF0 == 0x80 {
+            reader.byte()?; // release velocity
+        }
+        if let Some(message) = message {
+            message.check().map_err(|e| TopologyError::malformed("midi", e.to_string()))?;
+            track.events.push(MidiEvent { tick, message });
+        }
+    }
+    Ok((track, tempo))
+}
+
+/// How the sequencer moves through the group
+#[derive(Debug, Clone, PartialEq)]
+pub enum Walk {
+    /// Every element once, in the kernel's order
+    Elements,
+    /// A random walk on the Cayley graph
+    Cayley {
+        /// Generator indices (empty: all elements of the largest order)
+        generators: Vec<usize>,
+        /// Number of steps
+        steps: usize,
+        /// Random seed; the same seed gives the same walk
+        seed: u64,
+    },
+}
+
+impl Walk {
+    /// A random Cayley-graph walk on the default generators
+    pub fn random(steps: usize, seed: u64) -> Self {
+        Self::Cayley { generators: Vec::new(), steps, seed }
+    }
+}
+
+/// Turns walks through a kernel's group into MIDI
+#[derive(Debug, Clone)]
+pub struct Sequencer<'a> {
+    kernel: &'a GenerativeKernel,
+    /// MIDI key of pitch class 0, C (60 = middle C)
+    pub register: u8,
+    /// Note velocity
+    pub velocity: u8,
+    /// MIDI channel, 0-15
+    pub channel: u8,
+    /// Controller that carries the timbral morph
+    pub controller: u8,
+    /// Tempo in quarter notes per minute
+    pub tempo_bpm: f64,
+    /// Ticks per quarter note
+    pub ticks_per_quarter: u16,
+}
+
+impl<'a> Sequencer<'a> {
+    /// A sequencer at middle C, 120 bpm, channel 0, brightness automation
+    pub fn new(kernel: &'a GenerativeKernel) -> Self {
+        Self {
+            kernel,
+            register: 60,
+            velocity: 96,
+            channel: 0,
+            controller: BRIGHTNESS_CC,
+            tempo_bpm: 120.0,
+            ticks_per_quarter: TICKS_PER_QUARTER,
+        }
+    }
+
+    /// Set the register (MIDI key of pitch class 0)
+    pub fn with_register(mut self, register: u8) -> Self {
+        self.register = register.min(115);
+        self
+    }
+
+    /// Set the tempo
+    pub fn with_tempo(mut self, bpm: f64) -> Self {
+        self.tempo_bpm = bpm.clamp(MIN_TEMPO_BPM, 1000.0);
+        self
+    }
+
+    /// Set the channel
+    pub fn with_channel(mut self, channel: u8) -> Self {
+        self.channel = channel & 0x0F;
+        self
+    }
+
+    /// Set the controller used for timbre
+    pub fn with_controller(mut self, controller: u8) -> Self {
+        self.controller = controller & 0x7F;
+        self
+    }
+
+    /// Set the velocity
+    pub fn with_velocity(mut self, velocity: u8) -> Self {
+        self.velocity = velocity.clamp(1, 127);
+        self
+    }
+
+    /// Element indices visited by a walk
+    pub fn walk(&self, walk: &Walk) -> Result<Vec<usize>, TopologyError> {
+        let group = self.kernel.group_structure()?;
+        Ok(match walk {
+            Walk::Elements => (0..group.order()).collect(),
+            Walk::Cayley { generators, steps, seed } => {
+                let generators = if generators.is_empty() { default_generators(&group) } else { generators.clone() };
+                if let Some(&bad) = generators.iter().find(|&&g| g >= group.order()) {
+                    return Err(TopologyError::not_found(format!(
+                        "generator {} in a group of order {}",
+                        bad,
+                        group.order()
+                    )));
+                }
+                let mut rng = StdRng::seed_from_u64(*seed);
+                let mut current = group.identity();
+                let mut visited = Vec::with_capacity(*steps);
+                for _ in 0..*steps {
+                    visited.push(current);
+                    if !generators.is_empty() {
+                        current = group.multiply(generators[rng.gen_range(0..generators.len())], current);
+                    }
+                }
+                visited
+            }
+        })
+    }
+
+    /// The musical content of each step of a walk
+    pub fn steps(&self, walk: &Walk) -> Result<Vec<SequenceStep>, TopologyError> {
+        let space = self.kernel.generate_space();
+        let geometry = &self.kernel.geometry;
+        let (Some(seed_face), Some(seed_edge)) = (geometry.faces.first(), geometry.edges.first()) else {
+            return Err(TopologyError::invalid_structure(format!("{} has no faces to play", geometry.name)));
+        };
+        let sorted = |cell: &[usize]| {
+            let mut cell = cell.to_vec();
+            cell.sort_unstable();
+            cell
+        };
+
+        let mut steps = Vec::new();
+        for element in self.walk(walk)? {
+            let permutation = self.kernel.vertex_permutation(&self.kernel.elements[element])
+                .ok_or_else(|| TopologyError::group_structure(format!("element {} does not permute the vertices", element)))?;
+            let face: Vec<usize> = seed_face.iter().map(|&v| permutation[v]).collect();
+            let edge = sorted(&[permutation[seed_edge[0]], permutation[seed_edge[1]]]);
+
+            // The cell whose face is g·f₀ supplies the durations
+            let cell = space.rhythmic_cells.iter()
+                .find(|c| sorted(&c.vertices) == sorted(&face))
+                .ok_or_else(|| TopologyError::group_structure(format!("no rhythmic cell on face {:?}", face)))?;
+            let pulses = (0..face.len())
+                .map(|k| cell.pulse_pattern.get(k % cell.pulse_pattern.len().max(1)).copied().unwrap_or(1.0))
+                .collect();
+            let keys = face.iter()
+                .map(|&vertex| self.register.saturating_add(space.pitch_classes[vertex].pitch as u8).min(127))
+                .collect();
+            let morph = space.timbral_morphs.iter()
+                .find(|m| sorted(&[m.source, m.target]) == edge)
+                .cloned()
+                .ok_or_else(|| TopologyError::group_structure(format!("no timbral morph on edge {:?}", edge)))?;
+            steps.push(SequenceStep { element, keys, pulses, morph });
+        }
+        Ok(steps)
+    }
+
+    /// Render a walk as a one-track MIDI file
+    pub fn sequence(&self, walk: &Walk) -> Result<MidiFile, TopologyError> {
+        let beat = self.ticks_per_quarter as f64;
+        let mut track = MidiTrack::new(format!("{:?} kernel", self.kernel.group));
+        let mut tick = 0u32;
+        for step in self.steps(walk)? {
+            let step_start = tick;
+            for (&key, &pulse) in step.keys.iter().zip(&step.pulses) {
+                let duration = (pulse * beat).round().max(1.0) as u32;
+                track.note(self.channel, key, self.velocity, tick, duration)?;
+                tick += duration;
+            }
+
+            // The morph g·e₀ sweeps the controller across the step
+            let points = step.morph.interpolation_path.len().max(1);
+            for (k, point) in step.morph.interpolation_path.iter().enumerate() {
+                let at = step_start + ((tick - step_start) as f64 * k as f64 / points as f64) as u32;
+                let value = ((point.z.clamp(-1.0, 1.0) + 1.0) / 2.0 * 127.0).round() as u8;
+                track.control(self.channel, self.controller, value, at)?;
+            }
+        }
+
+        Ok(MidiFile::new(self.ticks_per_quarter).with_tempo(self.tempo_bpm).with_track(track))
+    }
+}
+
+/// One step of a walk: a trichord figure under one timbral morph
+#[derive(Debug, Clone)]
+pub struct SequenceStep {
+    /// Group element index
+    pub element: usize,
+    /// MIDI keys of the figure, in playing order
+    pub keys: Vec<u8>,
+    /// Duration of each note, in beats
+    pub pulses: Vec<f64>,
+    /// The morph g·e₀ that shapes the step's timbre
+    pub morph: TimbralMorph,
+}
+
+impl SequenceStep {
+    /// Length of the step in beats
+    pub fn beats(&self) -> f64 {
+        self.pulses.iter().sum()
+    }
+}
+
+/// All elements of the largest order, plus the highest-order elements
+/// still missing until they generate the whole group
+///
+/// The largest-order elements alone fall short for D_n (n ≥ 3), whose
+/// n-fold turns never reach the flips.
+fn default_generators(group: &FiniteGroup) -> Vec<usize> {
+    let orders: Vec<usize> = (0..group.order()).map(|a| group.element_order(a)).collect();
+    let largest = orders.iter().copied().max().unwrap_or(1);
+    let mut generators: Vec<usize> = (0..group.order()).filter(|&a| orders[a] == largest && largest > 1).collect();
+    let mut candidates: Vec<usize> = (0..group.order()).filter(|&a| orders[a] > 1).collect();
+    candidates.sort_by_key(|&a| std::cmp::Reverse(orders[a]));
+    for a in candidates {
+        let reached = group.closure(&generators).members;
+        if reached.len() == group.order() {
+            break;
+        }
+        if reached.binary_search(&a).is_err() {
+            generators.push(a);
+        }
+    }
+    generators
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_variable_length_quantities() {
+        let cases: [(u32, &[u8]); 5] = [
+            (0, &[0x00]),
+            (0x40, &[0x40]),
+            (0x7F, &[0x7F]),
+            (0x80, &[0x81, 0x00]),
+            (0x0FFF_FFFF, &[0xFF, 0xFF, 0xFF, 0x7F]),
+        ];
+        for (value, expected) in cases {
+            let mut out = Vec::new();
+            write_vlq(&mut out, value);
+            assert_eq!(out, expected);
+            assert_eq!(Reader { bytes: &out, at: 0 }.vlq().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let mut track = MidiTrack::new("lead");
+        track.note(0, 60, 100, 0, 480).unwrap().note(0, 64, 90, 480, 240).unwrap().control(0, 74, 127, 240).unwrap();
+        let file = MidiFile::new(480).with_tempo(90.0).with_track(track.clone());
+
+        let bytes = file.to_bytes().unwrap();
+        assert_eq!(&bytes[..4], b"MThd");
+        assert_eq!(&bytes[8..14], &[0, 1, 0, 2, 0x01, 0xE0]);
+
+        let decoded = MidiFile::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.tracks.len(), 1);
+        assert_eq!(decoded.tracks[0].name, "lead");
+        assert_eq!(decoded.tracks[0].sorted_events(), track.sorted_events());
+        assert!((decoded.tempo_bpm - 90.0).abs() < 1e-3);
+        assert!((file.duration_seconds() - 1.0).abs() < 1e-12);
+
+        // 1 bpm does not fit in 24 bits; the slowest tempo that does is kept
+        let slow = MidiFile::from_bytes(&MidiFile::new(480).with_tempo(1.0).to_bytes().unwrap()).unwrap();
+        assert!((slow.tempo_bpm - MIN_TEMPO_BPM).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_malformed_files_rejected() {
+        assert!(matches!(MidiFile::from_bytes(b"RIFF"), Err(TopologyError::MalformedInput { .. })));
+        let mut truncated = MidiFile::new(96).with_track(MidiTrack::new("x")).to_bytes().unwrap();
+        truncated.truncate(truncated.len() - 2);
+        assert!(MidiFile::from_bytes(&truncated).is_err());
+
+        // Seventeen maximal deltas run past u32::MAX ticks
+        let mut events: Vec<u8> = [0xFF, 0xFF, 0xFF, 0x7F, 0xB0, 0x07, 0x00].repeat(17);
+        events.extend_from_slice(&[0x00, 0xFF, 0x2F, 0x00]);
+        let mut bytes = b"MThd\0\0\0\x06\0\0\0\x01\0\x60MTrk".to_vec();
+        bytes.extend_from_slice(&(events.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&events);
+        assert!(matches!(MidiFile::from_bytes(&bytes), Err(TopologyError::MalformedInput { .. })));
+
+        let mut track = MidiTrack::new("late");
+        assert!(track.note(0, 60, 100, u32::MAX - 10, 480).is_err());
+        assert!(matches!(track.note(0, 200, 100, 0, 480), Err(TopologyError::OutOfRange { value: 200, max: 127, .. })));
+        assert!(matches!(track.note(16, 60, 100, 0, 480), Err(TopologyError::OutOfRange { .. })));
+        assert!(track.note(0, 60, 128, 0, 480).is_err());
+        assert!(track.control(0, 120, 0, 0).is_err());
+        assert!(track.events.is_empty());
+
+        // Hand-built events are checked when the file is written
+        track.events.push(MidiEvent { tick: 0, message: MidiMessage::NoteOff { channel: 0, key: 0x90 } });
+        assert!(matches!(MidiFile::new(96).with_track(track).to_bytes(), Err(TopologyError::OutOfRange { .. })));
+    }
+
+    #[test]
+    fn test_element_walk_plays_every_face() {
+        let kernel = GenerativeKernel::icosahedral();
+        let file = Sequencer::new(&kernel).with_register(48).sequence(&Walk::Elements).unwrap();
+        let notes = file.tracks[0].notes();
+
+        // 60 steps of a trichord figure, durations from the golden pulse
+        assert_eq!(notes.len(), 180);
+        assert!(notes.iter().all(|&(key, _, _)| (48..60).contains(&key)));
+        assert_eq!(notes[0].2, 480);
+        assert_eq!(notes[1].2, (crate::kernel::PHI_INV * 480.0).round() as u32);
+
+        let controls = file.tracks[0].events.iter()
+            .filter(|e| matches!(e.message, MidiMessage::ControlChange { controller: BRIGHTNESS_CC, .. }))
+            .count();
+        assert_eq!(controls, 180);
+    }
+
+    #[test]
+    fn test_cayley_walk_is_reproducible() {
+        let kernel = GenerativeKernel::icosahedral();
+        let sequencer = Sequencer::new(&kernel).with_tempo(100.0);
+        let a = sequencer.sequence(&Walk::random(32, 7)).unwrap().to_bytes().unwrap();
+        let b = sequencer.sequence(&Walk::random(32, 7)).unwrap().to_bytes().unwrap();
+        let c = sequencer.sequence(&Walk::random(32, 8)).unwrap().to_bytes().unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        // Consecutive steps differ by a generator
+        let group = kernel.group_structure().unwrap();
+        let path = sequencer.walk(&Walk::random(16, 3)).unwrap();
+        for pair in path.windows(2) {
+            let step = group.multiply(pair[1], group.inverse(pair[0]));
+            assert_eq!(group.element_order(step), 5);
+        }
+
+        let bad = Walk::Cayley { generators: vec![60], steps: 4, seed: 0 };
+        assert!(matches!(sequencer.walk(&bad), Err(TopologyError::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_default_walk_reaches_every_element() {
+        for kernel in [GenerativeKernel::dihedral(5).unwrap(), GenerativeKernel::dihedral(2).unwrap(), GenerativeKernel::octahedral()] {
+            let path = Sequencer::new(&kernel).walk(&Walk::random(500, 1)).unwrap();
+            let visited: BTreeSet<usize> = path.into_iter().collect();
+            assert_eq!(visited.len(), kernel.group.order(), "{}", kernel.geometry.name);
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Discrete Morse Theory - collapsing the Concept Complex to its essentials.
+//!
+//! An acyclic matching pairs each simplex with a face or coface it can be
+//! cancelled against. Unmatched (critical) simplices span the Morse
+//! complex, which has the same homology with far fewer cells.
+//!
+//! The matching is built greedily:
+//!
+//! 1. Elementary collapses remove free faces from the top down.
+//! 2. Coreductions work on what remains from the bottom up, declaring a
+//!    cell critical only when no pair is available.
+//!
+//! Morse boundaries follow gradient paths over GF(2).
+
+use crate::homology::ChainComplex;
+use crate::simplex::ConceptComplex;
+use std::collections::{BTreeSet, VecDeque};
+
+/// Cell count above which `calculate_homology` reduces before computing
+pub const MORSE_THRESHOLD: usize = 512;
+
+/// An acyclic matching and the Morse complex it induces
+#[derive(Debug, Clone)]
+pub struct MorseReduction {
+    /// Matched (face, coface) pairs, as cell indices of the source complex
+    pub pairs: Vec<(usize, usize)>,
+    /// Critical cells, as cell indices of the source complex
+    pub critical: Vec<usize>,
+    /// The Morse complex: one cell per critical cell
+    pub morse: ChainComplex,
+    /// Number of cells in the source complex
+    pub source_cells: usize,
+}
+
+impl MorseReduction {
+    /// Reduce a chain complex (boundaries must refer to existing cells)
+    pub fn of(complex: &ChainComplex) -> Self {
+        let n = complex.len();
+        let mut cofaces: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for (c, cell) in complex.cells.iter().enumerate() {
+            for &f in &cell.boundary {
+                cofaces[f].push(c);
+            }
+        }
+
+        let mut alive = vec![true; n];
+        let mut live_faces: Vec<usize> = complex.cells.iter().map(|c| c.boundary.len()).collect();
+        let mut live_cofaces: Vec<usize> = cofaces.iter().map(Vec::len).collect();
+        let mut pairs = Vec::new();
+        let mut critical = Vec::new();
+
+        let remove = |c: usize, alive: &mut Vec<bool>, live_faces: &mut Vec<usize>, live_cofaces: &mut Vec<usize>| {
+            alive[c] = false;
+            for &f in &complex.cells[c].boundary {
+                live_cofaces[f] -= 1;
+            }
+            for &g in &cofaces[c] {
+                live_faces[g] -= 1;
+            }
+        };
+
+        // Elementary collapses: a face with a single live coface
+        let mut free: VecDeque<usize> = (0..n).filter(|&c| live_cofaces[c] == 1).collect();
+        while let Some(face) = free.pop_front() {
+            if !alive[face] || live_cofaces[face] != 1 {
+                continue;
+            }
+            let Some(&coface) = cofaces[face].iter().find(|&&g| alive[g]) else { continue };
+            pairs.push((face, coface));
+            remove(coface, &mut alive, &mut live_faces, &mut live_cofaces);
+            remove(face, &mut alive, &mut live_faces, &mut live_cofaces);
+            for &f in complex.cells[coface].boundary.iter().chain(&complex.cells[face].boundary) {
+                if alive[f] && live_cofaces[f] == 1 {
+                    free.push_back(f);
+                }
+            }
+        }
+
+        // Coreductions: a cell with a single live face
+        let mut by_dimension: Vec<usize> = (0..n).collect();
+        by_dimension.sort_by_key(|&c| (complex.cells[c].dimension(), c));
+        let mut next_critical = 0;
+        let mut coreducible: VecDeque<usize> = VecDeque::new();
+        loop {
+            while let Some(coface) = coreducible.pop_front() {
+                if !alive[coface] || live_faces[coface] != 1 {
+                    continue;
+                }
+                let Some(&face) = complex.cells[coface].boundary.iter().find(|&&f| alive[f]) else { continue };
+                pairs.push((face, coface));
+                remove(face, &mut alive, &mut live_faces, &mut live_cofaces);
+                remove(coface, &mut alive, &mut live_faces, &mut live_cofaces);
+                for &g in cofaces[face].iter().chain(&cofaces[coface]) {
+                    if alive[g] && live_faces[g] == 1 {
+                        coreducible.push_back(g);
+                    }
+                }
+            }
+
+            // No pair available: the lowest live cell becomes critical
+            while next_critical < n && !alive[by_dimension[next_critical]] {
+                next_critical += 1;
+            }
+            let Some(&cell) = by_dimension.get(next_critical) else { break };
+            critical.push(cell);
+            remove(cell, &mut alive, &mut live_faces, &mut live_cofaces);
+            for &g in &cofaces[cell] {
+                if alive[g] && live_faces[g] == 1 {
+                    coreducible.push_back(g);
+                }
+            }
+        }
+
+        critical.sort_by_key(|&c| (complex.cells[c].dimension(), c));
+        let morse = Self::morse_complex(complex, &pairs, &critical);
+        Self { pairs, critical, morse, source_cells: n }
+    }
+
+    /// Build the Morse complex by following gradient paths
+    fn morse_complex(complex: &ChainComplex, pairs: &[(usize, usize)], critical: &[usize]) -> ChainComplex {
+        let n = complex.len();
+        let mut up: Vec<Option<usize>> = vec![None; n];
+        let mut is_coface = vec![false; n];
+        for &(face, coface) in pairs {
+            up[face] = Some(coface);
+            is_coface[coface] = true;
+        }
+        let mut position: Vec<Option<usize>> = vec![None; n];
+
+        let mut morse = ChainComplex::new();
+        for &c in critical {
+            let mut chain: BTreeSet<usize> = BTreeSet::new();
+            for &f in &complex.cells[c].boundary {
+                toggle(&mut chain, f);
+            }
+
+            // Flow every non-critical face down to critical cells
+            while let Some(&x) = chain.iter().find(|x| position[**x].is_none()) {
+                if is_coface[x] {
+                    chain.remove(&x);
+                } else if let Some(coface) = up[x] {
+                    for &f in &complex.cells[coface].boundary {
+                        toggle(&mut chain, f);
+                    }
+                } else {
+                    // A critical face not yet placed cannot occur in dimension order
+                    chain.remove(&x);
+                }
+            }
+
+            let boundary = chain.iter().filter_map(|&x| position[x]).collect();
+            position[c] = Some(morse.add_cell(complex.cells[c].vertices.clone(), 0.0, boundary));
+        }
+        morse
+    }
+
+    /// Critical simplices (vertex ids), lowest dimension first
+    pub fn critical_simplices(&self) -> Vec<Vec<usize>> {
+        self.morse.cells.iter().map(|c| c.vertices.clone()).collect()
+    }
+
+    /// Ideas taking part in some critical simplex
+    pub fn essential_ideas(&self) -> BTreeSet<usize> {
+        self.morse.cells.iter().flat_map(|c| c.vertices.iter().copied()).collect()
+    }
+
+    /// Fraction of cells that survive the reduction
+    pub fn compression_ratio(&self) -> f64 {
+        if self.source_cells == 0 {
+            1.0
+        } else {
+            self.morse.len() as f64 / self.source_cells as f64
+        }
+    }
+
+    /// Betti numbers, computed on the Morse complex
+    pub fn betti_numbers(&self) -> Vec<usize> {
+        self.morse.betti_numbers()
+    }
+}
+
+fn toggle(chain: &mut BTreeSet<usize>, cell: usize) {
+    if !chain.remove(&cell) {
+        chain.insert(cell);
+    }
+}
+
+impl ConceptComplex {
+    /// Morse reduction of the complex closed under faces
+    pub fn morse_reduction(&self) -> MorseReduction {
+        let top = self.faces.iter().map(|f| f.dimension).max().unwrap_or(0);
+        MorseReduction::of(&self.chain_complex(top))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex::ComplexBuilder;
+
+    fn reduce(simplices: &[Vec<usize>]) -> (ChainComplex, MorseReduction) {
+        let mut complex = ChainComplex::new();
+        for simplex in simplices {
+            complex.add_simplex(simplex.clone(), 0.0);
+        }
+        let reduction = MorseReduction::of(&complex);
+        (complex, reduction)
+    }
+
+    /// 3×3 grid torus, two triangles per square
+    fn torus() -> Vec<Vec<usize>> {
+        let id = |i: usize, j: usize| 3 * (i % 3) + j % 3;
+        let mut triangles = Vec::new();
+        for i in 0..3 {
+            for j in 0..3 {
+                let mut lower = vec![id(i, j), id(i + 1, j), id(i + 1, j + 1)];
+                let mut upper = vec![id(i, j), id(i, j + 1), id(i + 1, j + 1)];
+                lower.sort_unstable();
+                upper.sort_unstable();
+                triangles.push(lower);
+                triangles.push(upper);
+            }
+        }
+        triangles
+    }
+
+    #[test]
+    fn test_filled_triangle_collapses_to_point() {
+        let (_, reduction) = reduce(&[vec![0, 1, 2]]);
+        assert_eq!(reduction.critical.len(), 1);
+        assert_eq!(reduction.pairs.len(), 3);
+        assert_eq!(reduction.betti_numbers(), vec![1]);
+    }
+
+    #[test]
+    fn test_hollow_triangle_keeps_loop() {
+        let (_, reduction) = reduce(&[vec![0, 1], vec![1, 2], vec![0, 2]]);
+        assert_eq!(reduction.critical_simplices().len(), 2);
+        assert_eq!(reduction.betti_numbers(), vec![1, 1]);
+    }
+
+    #[test]
+    fn test_sphere() {
+        let (_, reduction) = reduce(&[vec![0, 1, 2], vec![0, 1, 3], vec![0, 2, 3], vec![1, 2, 3]]);
+        assert_eq!(reduction.morse.cell_counts(), vec![1, 0, 1]);
+        assert_eq!(reduction.betti_numbers(), vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn test_torus_matches_full_homology() {
+        let (complex, reduction) = reduce(&torus());
+        assert_eq!(complex.betti_numbers(), vec![1, 2, 1]);
+        assert_eq!(reduction.betti_numbers(), vec![1, 2, 1]);
+        assert!(reduction.compression_ratio() < 0.25);
+        assert_eq!(2 * reduction.pairs.len() + reduction.critical.len(), complex.len());
+    }
+
+    #[test]
+    fn test_large_complex_uses_morse_path() {
+        // A long cycle with a filled fan hanging off one idea
+        let mut builder = ComplexBuilder::new();
+        let n = 400;
+        for i in 0..n + 3 {
+            builder.add_idea(format!("Idea {}", i));
+        }
+        for i in 0..n {
+            builder.connect(i, (i + 1) % n);
+        }
+        builder.add_triangle(0, n, n + 1).add_triangle(0, n + 1, n + 2);
+        let mut complex = builder.build();
+        assert!(complex.chain_complex(2).len() > MORSE_THRESHOLD);
+
+        assert_eq!(complex.calculate_homology(), &vec![1, 1, 0]);
+        let reduction = complex.morse_reduction();
+        assert_eq!(reduction.morse.len(), 2);
+        assert!(!reduction.essential_ideas().is_empty());
+    }
+}
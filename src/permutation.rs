@@ -0,0 +1,566 @@
+//! Permutation Groups - user-defined kernels beyond the rotation groups.
+//!
+//! A kernel need not be a group of rotations: any finite group acting on
+//! points will do. Groups are given by permutation generators.
+//! Schreier–Sims turns them into a base and strong generating set, which
+//! gives the order, membership and the element list. A `Presentation`
+//! reaches the same place through coset enumeration.
+//!
+//! `PermutationKernel` maps orbits to roles:
+//!
+//! - pitch classes: the orbit of a seed point
+//! - rhythmic cells: the orbit of a seed set of points
+//! - timbral morphs: the orbit of a seed pair
+//!
+//! For example, PSL(2,7) acting on the 8 points of the projective line
+//! gives 8 pitches, 56 cells and 28 morphs.
+
+use crate::group::MAX_GROUP_ORDER;
+use crate::kernel::{
+    equator, GenerativeKernelTrait, PitchClass, RhythmicCell, SymmetryGroup, TimbralMorph,
+    TransformationSpace, PHI_INV,
+};
+use crate::tuning::NOTE_NAMES;
+use crate::types::*;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+
+/// A permutation of the points 0..degree
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Permutation {
+    images: Vec<usize>,
+}
+
+impl Permutation {
+    /// The identity on `degree` points
+    pub fn identity(degree: usize) -> Self {
+        Self { images: (0..degree).collect() }
+    }
+
+    /// A permutation from the image of each point
+    pub fn new(images: Vec<usize>) -> Result<Self, TopologyError> {
+        let mut seen = vec![false; images.len()];
+        for &image in &images {
+            if image >= images.len() || std::mem::replace(&mut seen[image], true) {
+                return Err(TopologyError::malformed("permutation", format!("{:?} is not a bijection", images)));
+            }
+        }
+        Ok(Self { images })
+    }
+
+    /// A permutation from disjoint cycles, e.g. `[[0, 1, 2], [3, 4]]`
+    pub fn from_cycles(degree: usize, cycles: &[Vec<usize>]) -> Result<Self, TopologyError> {
+        let mut images: Vec<usize> = (0..degree).collect();
+        let mut used = BTreeSet::new();
+        for cycle in cycles {
+            for (k, &point) in cycle.iter().enumerate() {
+                if point >= degree || !used.insert(point) {
+                    return Err(TopologyError::malformed("permutation", format!("cycle {:?} repeats a point or leaves 0..{}", cycle, degree)));
+                }
+                images[point] = cycle[(k + 1) % cycle.len()];
+            }
+        }
+        Ok(Self { images })
+    }
+
+    /// Number of points
+    pub fn degree(&self) -> usize {
+        self.images.len()
+    }
+
+    /// Image of a point
+    pub fn apply(&self, point: usize) -> usize {
+        self.images[point]
+    }
+
+    /// Image of every point
+    pub fn images(&self) -> &[usize] {
+        &self.images
+    }
+
+    /// Apply this permutation, then another
+    pub fn then(&self, other: &Permutation) -> Permutation {
+        Self { images: self.images.iter().map(|&i| other.images[i]).collect() }
+    }
+
+    /// The inverse permutation
+    pub fn inverse(&self) -> Permutation {
+        let mut images = vec![0; self.images.len()];
+        for (point, &image) in self.images.iter().enumerate() {
+            images[image] = point;
+        }
+        Self { images }
+    }
+
+    /// Check if every point is fixed
+    pub fn is_identity(&self) -> bool {
+        self.images.iter().enumerate().all(|(i, &j)| i == j)
+    }
+
+    /// Cycles of length two or more, each starting at its smallest point
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.degree()];
+        let mut cycles = Vec::new();
+        for start in 0..self.degree() {
+            if seen[start] || self.images[start] == start {
+                continue;
+            }
+            let mut cycle = vec![start];
+            seen[start] = true;
+            let mut point = self.images[start];
+            while point != start {
+                seen[point] = true;
+                cycle.push(point);
+                point = self.images[point];
+            }
+            cycles.push(cycle);
+        }
+        cycles
+    }
+
+    /// Smallest n with pⁿ = 1 (the lcm of the cycle lengths)
+    pub fn order(&self) -> usize {
+        fn gcd(a: usize, b: usize) -> usize {
+            if b == 0 { a } else { gcd(b, a % b) }
+        }
+        self.cycles().iter().map(|c| c.len()).fold(1, |l, n| l / gcd(l, n) * n)
+    }
+
+    /// Image of a set of points, sorted
+    pub fn apply_set(&self, points: &[usize]) -> Vec<usize> {
+        let mut image: Vec<usize> = points.iter().map(|&p| self.images[p]).collect();
+        image.sort_unstable();
+        image
+    }
+}
+
+impl fmt::Display for Permutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cycles = self.cycles();
+        if cycles.is_empty() {
+            return write!(f, "()");
+        }
+        for cycle in cycles {
+            let points: Vec<String> = cycle.iter().map(|p| p.to_string()).collect();
+            write!(f, "({})", points.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// One level of a stabilizer chain
+#[derive(Debug, Clone)]
+struct Level {
+    /// The base point
+    point: usize,
+    /// Strong generators fixing every earlier base point
+    generators: Vec<Permutation>,
+    /// For each point of the orbit, an element taking the base point there
+    transversal: BTreeMap<usize, Permutation>,
+}
+
+impl Level {
+    fn new(point: usize, degree: usize) -> Self {
+        let mut transversal = BTreeMap::new();
+        transversal.insert(point, Permutation::identity(degree));
+        Self { point, generators: Vec::new(), transversal }
+    }
+
+    /// Rebuild the orbit of the base point and its transversal
+    fn rebuild(&mut self) {
+        let degree = self.transversal[&self.point].degree();
+        self.transversal = BTreeMap::from([(self.point, Permutation::identity(degree))]);
+        let mut queue = VecDeque::from([self.point]);
+        while let Some(u) = queue.pop_front() {
+            for s in &self.generators {
+                let v = s.apply(u);
+                if !self.transversal.contains_key(&v) {
+                    let rep = self.transversal[&u].then(s);
+                    self.transversal.insert(v, rep);
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+}
+
+/// A permutation group with a base and strong generating set
+#[derive(Debug, Clone)]
+pub struct PermutationGroup {
+    degree: usize,
+    /// The generators it was built from
+    pub generators: Vec<Permutation>,
+    levels: Vec<Level>,
+}
+
+impl PermutationGroup {
+    /// The group generated by permutations of `degree` points (Schreier–Sims)
+    pub fn new(degree: usize, generators: Vec<Permutation>) -> Result<Self, TopologyError> {
+        if let Some(g) = generators.iter().find(|g| g.degree() != degree) {
+            return Err(TopologyError::DimensionMismatch { expected: degree, found: g.degree() });
+        }
+        let mut group = Self { degree, generators, levels: Vec::new() };
+        let strong: Vec<Permutation> = group.generators.iter().filter(|g| !g.is_identity()).cloned().collect();
+        for g in strong {
+            if group.sift(&g, 0).0.is_identity() {
+                continue;
+            }
+            group.add_strong_generator(g, 0);
+            group.schreier_sims();
+        }
+        Ok(group)
+    }
+
+    /// A group from generators in cycle notation
+    pub fn from_cycles(degree: usize, generators: &[Vec<Vec<usize>>]) -> Result<Self, TopologyError> {
+        let generators = generators.iter()
+            .map(|cycles| Permutation::from_cycles(degree, cycles))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(degree, generators)
+    }
+
+    /// Add a generator fixing the base points before `from` to levels from..
+    fn add_strong_generator(&mut self, g: Permutation, from: usize) {
+        let mut level = from;
+        loop {
+            if level == self.levels.len() {
+                let Some(point) = (0..self.degree).find(|&p| g.apply(p) != p) else { return };
+                self.levels.push(Level::new(point, self.degree));
+            }
+            self.levels[level].generators.push(g.clone());
+            self.levels[level].rebuild();
+            if g.apply(self.levels[level].point) != self.levels[level].point {
+                return;
+            }
+            level += 1;
+        }
+    }
+
+    /// Strip an element through the chain from a level
+    ///
+    /// Returns the residue and the level where sifting stopped.
+    fn sift(&self, g: &Permutation, from: usize) -> (Permutation, usize) {
+        let mut h = g.clone();
+        for (i, level) in self.levels.iter().enumerate().skip(from) {
+            let u = h.apply(level.point);
+            match level.transversal.get(&u) {
+                Some(rep) => h = h.then(&rep.inverse()),
+                None => return (h, i),
+            }
+        }
+        (h, self.levels.len())
+    }
+
+    /// Complete the chain: every Schreier generator must sift to the identity
+    fn schreier_sims(&mut self) {
+        let mut i = self.levels.len();
+        while i > 0 {
+            let level = i - 1;
+            let mut residue = None;
+            'search: for (&u, rep) in &self.levels[level].transversal {
+                for s in &self.levels[level].generators {
+                    let image = &self.levels[level].transversal[&s.apply(u)];
+                    let schreier = rep.then(s).then(&image.inverse());
+                    let (h, stopped) = self.sift(&schreier, level + 1);
+                    if !h.is_identity() {
+                        residue = Some((h, stopped));
+                        break 'search;
+                    }
+                }
+            }
+            match residue {
+                Some((h, stopped)) => {
+                    self.add_strong_generator(h, level + 1);
+                    i = stopped.min(self.levels.len() - 1) + 1;
+                }
+                None => i -= 1,
+            }
+        }
+    }
+
+    /// Number of points acted on
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Number of elements (product of the basic orbit lengths)
+    pub fn order(&self) -> usize {
+        self.levels.iter().fold(1usize, |order, level| order.saturating_mul(level.transversal.len()))
+    }
+
+    /// The base points
+    pub fn base(&self) -> Vec<usize> {
+        self.levels.iter().map(|level| level.point).collect()
+    }
+
+    /// Check membership
+    pub fn contains(&self, g: &Permutation) -> bool {
+        g.degree() == self.degree && self.sift(g, 0).0.is_identity()
+    }
+
+    /// Every element, identity first, then in sorted order
+    pub fn elements(&self) -> Result<Vec<Permutation>, TopologyError> {
+        if self.order() > MAX_GROUP_ORDER {
+            return Err(TopologyError::group_structure(format!("order {} exceeds {} elements", self.order(), MAX_GROUP_ORDER)));
+        }
+        let mut elements = vec![Permutation::identity(self.degree)];
+        for level in self.levels.iter().rev() {
+            elements = elements.iter()
+                .flat_map(|h| level.transversal.values().map(move |t| h.then(t)))
+                .collect();
+        }
+        elements.sort();
+        let identity = elements.iter().position(|e| e.is_identity()).unwrap_or(0);
+        let e = elements.remove(identity);
+        elements.insert(0, e);
+        Ok(elements)
+    }
+
+    /// The orbit of a set of points, each image sorted
+    pub fn orbit_of_set(&self, points: &[usize]) -> Vec<Vec<usize>> {
+        let mut start: Vec<usize> = points.iter().copied().filter(|&p| p < self.degree).collect();
+        start.sort_unstable();
+        start.dedup();
+        let mut orbit = BTreeSet::from([start.clone()]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(set) = queue.pop_front() {
+            for g in &self.generators {
+                let image = g.apply_set(&set);
+                if orbit.insert(image.clone()) {
+                    queue.push_back(image);
+                }
+            }
+        }
+        orbit.into_iter().collect()
+    }
+
+    /// The orbits on points
+    pub fn orbits(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.degree];
+        let mut orbits = Vec::new();
+        for p in 0..self.degree {
+            if !seen[p] {
+                let orbit: Vec<usize> = self.orbit_of_set(&[p]).into_iter().flatten().collect();
+                orbit.iter().for_each(|&q| seen[q] = true);
+                orbits.push(orbit);
+            }
+        }
+        orbits
+    }
+
+    /// Check if there is a single orbit on points
+    pub fn is_transitive(&self) -> bool {
+        self.orbits().len() <= 1
+    }
+}
+
+/// Seeds whose orbits become pitch classes, rhythmic cells and timbral morphs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrbitRoles {
+    /// Seed point for pitch classes
+    pub pitch: usize,
+    /// Seed set for rhythmic cells
+    pub rhythm: Vec<usize>,
+    /// Seed pair for timbral morphs
+    pub timbre: [usize; 2],
+}
+
+impl Default for OrbitRoles {
+    fn default() -> Self {
+        Self { pitch: 0, rhythm: vec![0, 1, 2], timbre: [0, 1] }
+    }
+}
+
+/// A generative kernel from any finite permutation group
+#[derive(Debug, Clone)]
+pub struct PermutationKernel {
+    /// Name of the group, e.g. "PSL(2,7)"
+    pub name: String,
+    /// The group with its stabilizer chain
+    pub group: PermutationGroup,
+    /// Every element, identity first
+    pub elements: Vec<Permutation>,
+    /// Which orbits play which role
+    pub roles: OrbitRoles,
+    /// Coherence strain (how far from equilibrium)
+    pub strain: f64,
+}
+
+impl PermutationKernel {
+    /// A kernel from a group; fails if the group is too large to list
+    pub fn new(name: impl Into<String>, group: PermutationGroup) -> Result<Self, TopologyError> {
+        let elements = group.elements()?;
+        Ok(Self { name: name.into(), group, elements, roles: OrbitRoles::default(), strain: 0.0 })
+    }
+
+    /// A kernel generated by permutations in cycle notation
+    pub fn from_cycles(name: impl Into<String>, degree: usize, generators: &[Vec<Vec<usize>>]) -> Result<Self, TopologyError> {
+        Self::new(name, PermutationGroup::from_cycles(degree, generators)?)
+    }
+
+    /// PSL(2,7) (order 168) on the projective line {0, …, 6, ∞ = 7}
+    ///
+    /// Generated by x ↦ x + 1 and x ↦ −1/x.
+    pub fn psl27() -> Self {
+        let shift = vec![(0..7).collect()];
+        let flip = vec![vec![0, 7], vec![1, 6], vec![2, 3], vec![4, 5]];
+        Self::from_cycles("PSL(2,7)", 8, &[shift, flip]).expect("x ↦ x + 1 and x ↦ −1/x are permutations of 8 points")
+    }
+
+    /// Use other seeds for the roles
+    pub fn with_roles(mut self, roles: OrbitRoles) -> Self {
+        self.roles = roles;
+        self
+    }
+
+    /// Number of elements
+    pub fn order(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Apply kernel strain (deviation from equilibrium)
+    pub fn apply_strain(&mut self, strain: f64) {
+        self.strain = strain.clamp(0.0, 1.0);
+    }
+}
+
+impl GenerativeKernelTrait for PermutationKernel {
+    /// Points sit evenly on the equator and spread evenly over the octave
+    fn generate_space(&self) -> TransformationSpace {
+        let positions = equator(self.group.degree());
+        let pitch_orbit = self.group.orbit_of_set(&[self.roles.pitch]);
+        let m = pitch_orbit.len();
+        let pitch_classes = pitch_orbit.iter().flatten().enumerate()
+            .map(|(i, &point)| {
+                let pitch = (12 * i + m / 2) / m % 12;
+                PitchClass { index: point, pitch, name: NOTE_NAMES[pitch].to_string(), position: positions[point] }
+            })
+            .collect();
+
+        let rhythmic_cells = self.group.orbit_of_set(&self.roles.rhythm).into_iter().enumerate()
+            .map(|(index, vertices)| {
+                let pulse_pattern = (0..vertices.len()).map(|k| if k == 0 { 1.0 } else { PHI_INV }).collect();
+                RhythmicCell { index, vertices, pulse_pattern }
+            })
+            .collect();
+
+        let timbral_morphs = self.group.orbit_of_set(&self.roles.timbre).into_iter()
+            .filter(|pair| pair.len() == 2)
+            .enumerate()
+            .map(|(index, pair)| {
+                let (v0, v1) = (positions[pair[0]], positions[pair[1]]);
+                TimbralMorph { index, source: pair[0], target: pair[1], interpolation_path: vec![v0, (v0 + v1) / 2.0, v1] }
+            })
+            .collect();
+
+        TransformationSpace { pitch_classes, rhythmic_cells, timbral_morphs, symmetry_order: self.order() }
+    }
+
+    fn strain(&self) -> f64 {
+        self.strain
+    }
+
+    fn is_coherent(&self) -> bool {
+        (self.strain - PHI_INV).abs() < 0.01 || self.strain < 0.01
+    }
+
+    fn symmetry_group(&self) -> SymmetryGroup {
+        SymmetryGroup::Abstract(self.order())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theory::PitchClassSet;
+
+    fn order_census(elements: &[Permutation]) -> BTreeMap<usize, usize> {
+        let mut census = BTreeMap::new();
+        for e in elements {
+            *census.entry(e.order()).or_insert(0) += 1;
+        }
+        census
+    }
+
+    #[test]
+    fn test_permutation_arithmetic() {
+        let p = Permutation::from_cycles(5, &[vec![0, 1, 2], vec![3, 4]]).unwrap();
+        assert_eq!(p.order(), 6);
+        assert_eq!(p.to_string(), "(0 1 2)(3 4)");
+        assert!(p.then(&p.inverse()).is_identity());
+        assert_eq!(p.then(&p).apply(0), 2);
+        assert_eq!(Permutation::identity(3).to_string(), "()");
+        assert!(Permutation::new(vec![0, 0, 1]).is_err());
+        assert!(Permutation::from_cycles(3, &[vec![0, 1], vec![1, 2]]).is_err());
+    }
+
+    #[test]
+    fn test_schreier_sims_orders() {
+        let cycle = |n: usize| vec![(0..n).collect::<Vec<_>>()];
+        let s5 = PermutationGroup::from_cycles(5, &[cycle(5), vec![vec![0, 1]]]).unwrap();
+        assert_eq!(s5.order(), 120);
+        let a5 = PermutationGroup::from_cycles(5, &[vec![vec![0, 1, 2]], vec![vec![2, 3, 4]]]).unwrap();
+        assert_eq!(a5.order(), 60);
+        assert!(!a5.contains(&Permutation::from_cycles(5, &[vec![0, 1]]).unwrap()));
+        assert!(a5.contains(&Permutation::from_cycles(5, &[vec![0, 1], vec![2, 3]]).unwrap()));
+
+        // The Mathieu group M11, and S8 without listing it
+        let m11 = PermutationGroup::from_cycles(11, &[cycle(11), vec![vec![2, 6, 10, 7], vec![3, 9, 4, 5]]]).unwrap();
+        assert_eq!(m11.order(), 7920);
+        let s8 = PermutationGroup::from_cycles(8, &[cycle(8), vec![vec![0, 1]]]).unwrap();
+        assert_eq!(s8.order(), 40320);
+        assert!(s8.elements().is_err());
+        assert_eq!(s5.elements().unwrap().len(), 120);
+
+        assert!(matches!(
+            PermutationGroup::new(4, vec![Permutation::identity(5)]),
+            Err(TopologyError::DimensionMismatch { expected: 4, found: 5 })
+        ));
+    }
+
+    #[test]
+    fn test_psl27_kernel() {
+        let kernel = PermutationKernel::psl27();
+        assert_eq!(kernel.order(), 168);
+        assert!(kernel.elements[0].is_identity());
+        let expected = BTreeMap::from([(1, 1), (2, 21), (3, 56), (4, 42), (7, 48)]);
+        assert_eq!(order_census(&kernel.elements), expected);
+        assert!(kernel.group.is_transitive());
+
+        // 3-homogeneous on the projective line: every triple and pair is one orbit
+        let space = kernel.generate_space();
+        assert_eq!(space.pitch_classes.len(), 8);
+        assert_eq!(space.rhythmic_cells.len(), 56);
+        assert_eq!(space.timbral_morphs.len(), 28);
+        assert_eq!(space.symmetry_order, 168);
+        assert_eq!(kernel.symmetry_group(), SymmetryGroup::Abstract(168));
+        assert_eq!(kernel.symmetry_group().order(), 168);
+    }
+
+    #[test]
+    fn test_roles_follow_orbits() {
+        // Z4 turning two squares at once: the pitch orbit is one square
+        let kernel = PermutationKernel::from_cycles("Z4", 8, &[vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]]]).unwrap()
+            .with_roles(OrbitRoles { pitch: 4, rhythm: vec![0, 2], timbre: [0, 4] });
+        assert_eq!(kernel.group.orbits().len(), 2);
+        let space = kernel.generate_space();
+        let points: Vec<usize> = space.pitch_classes.iter().map(|p| p.index).collect();
+        assert_eq!(points, vec![4, 5, 6, 7]);
+        assert_eq!(space.rhythmic_cells.len(), 2);
+        assert_eq!(space.timbral_morphs.len(), 4);
+        assert!(kernel.is_coherent());
+    }
+
+    #[test]
+    fn test_trichords_off_the_first_orbit() {
+        // Pitch classes live on points 4..8 only; cells are looked up by point
+        let kernel = PermutationKernel::from_cycles("Z4", 8, &[vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]]]).unwrap()
+            .with_roles(OrbitRoles { pitch: 4, rhythm: vec![4, 6], timbre: [0, 4] });
+        let space = kernel.generate_space();
+        let cell = &space.rhythmic_cells[0];
+        assert_eq!(cell.vertices, vec![4, 6]);
+        assert_eq!(space.trichord(cell), PitchClassSet::new([0, 6]));
+        // Points outside the pitch orbit carry no pitch
+        assert_eq!(space.pitch_set(&[0, 1, 5]), PitchClassSet::new([3]));
+    }
+}
@@ -0,0 +1,335 @@
+//! Populations - many perspectival selves in one shared world.
+//!
+//! Every agent is a section of the same fiber bundle. Agents walk their
+//! own journeys, and parallel transport carries each perspective along. To
+//! compare two people, both perspectives are transported along straight
+//! lines to a common point. Whatever difference remains there comes from
+//! their histories (and from where they meet).
+//!
+//! A consensus point is a base point where the total pairwise divergence
+//! of the transported perspectives is smallest.
+
+use crate::bundle::{FiberBundle, Perspective, WorldPoint};
+use crate::types::*;
+use nalgebra::{DMatrix, DVector};
+
+/// One self in the population
+#[derive(Debug, Clone)]
+pub struct Agent {
+    /// Name of the agent
+    pub name: String,
+    /// Where the agent is now
+    pub position: Coordinate,
+    /// The agent's current perspective (fiber point at `position`)
+    pub perspective: Perspective,
+    /// Stops along the agent's journeys, starting where it emerged
+    pub history: Vec<(WorldPoint, Perspective)>,
+}
+
+/// A base point where the population comes closest to agreement
+#[derive(Debug, Clone)]
+pub struct ConsensusPoint {
+    /// The base point
+    pub point: Coordinate,
+    /// Total pairwise divergence of the transported perspectives
+    pub disagreement: f64,
+    /// Mean transported perspective state
+    pub mean_state: DVector<f64>,
+}
+
+/// Several selves sharing one fiber bundle
+#[derive(Debug, Clone)]
+pub struct Population {
+    /// The shared bundle: base, fiber and connection
+    pub bundle: FiberBundle,
+    agents: Vec<Agent>,
+}
+
+impl Population {
+    /// An empty population over a shared bundle
+    pub fn new(bundle: FiberBundle) -> Self {
+        Self { bundle, agents: Vec::new() }
+    }
+
+    /// Add a self at a world point; returns its index
+    pub fn add_self(
+        &mut self,
+        name: impl Into<String>,
+        world: WorldPoint,
+        perspective: Perspective,
+    ) -> Result<usize, TopologyError> {
+        self.check_base(&world.coordinates)?;
+        if perspective.state.len() != self.bundle.fiber_dimension {
+            return Err(TopologyError::DimensionMismatch {
+                expected: self.bundle.fiber_dimension,
+                found: perspective.state.len(),
+            });
+        }
+        self.agents.push(Agent {
+            name: name.into(),
+            position: world.coordinates.clone(),
+            perspective: perspective.clone(),
+            history: vec![(world, perspective)],
+        });
+        Ok(self.agents.len() - 1)
+    }
+
+    /// Reject base points that do not live in the bundle's base
+    fn check_base(&self, point: &Coordinate) -> Result<(), TopologyError> {
+        if point.dimension != self.bundle.base_dimension {
+            return Err(TopologyError::DimensionMismatch { expected: self.bundle.base_dimension, found: point.dimension });
+        }
+        Ok(())
+    }
+
+    /// Number of agents
+    pub fn len(&self) -> usize {
+        self.agents.len()
+    }
+
+    /// Check if there are no agents
+    pub fn is_empty(&self) -> bool {
+        self.agents.is_empty()
+    }
+
+    /// Get an agent by index
+    pub fn agent(&self, index: usize) -> Option<&Agent> {
+        self.agents.get(index)
+    }
+
+    /// Move an agent along a path, transporting its perspective
+    ///
+    /// If the path does not start at the agent's position, the agent first
+    /// walks straight to its start.
+    pub fn journey(&mut self, index: usize, path: &Path) -> Result<(), TopologyError> {
+        for point in &path.points {
+            self.check_base(point)?;
+        }
+        let agent = self.agents.get_mut(index).ok_or_else(|| TopologyError::not_found(format!("agent {}", index)))?;
+        let Some(end) = path.points.last() else { return Ok(()) };
+
+        let mut points = path.points.clone();
+        if points[0].distance_squared(&agent.position) > 1e-18 {
+            points.insert(0, agent.position.clone());
+        }
+        let transport = self.bundle.connection.transport_matrix(&Path::new(points));
+
+        agent.perspective.apply_transport(&transport)?;
+        agent.position = end.clone();
+        let stop = WorldPoint::new(
+            end.position.as_slice().to_vec(),
+            format!("{}_step_{}", agent.name, agent.history.len()),
+        );
+        agent.history.push((stop, agent.perspective.clone()));
+        Ok(())
+    }
+
+    /// An agent's perspective carried straight to another base point
+    pub fn perspective_at(&self, index: usize, point: &Coordinate) -> Result<Perspective, TopologyError> {
+        self.check_base(point)?;
+        let agent = self.agents.get(index).ok_or_else(|| TopologyError::not_found(format!("agent {}", index)))?;
+        let transport = self.bundle.connection.segment_transport(&agent.position.position, &point.position);
+
+        let mut perspective = agent.perspective.clone();
+        perspective.apply_transport(&transport)?;
+        Ok(perspective)
+    }
+
+    /// Distance between two agents' perspectives after transport to `point`
+    pub fn divergence(&self, a: usize, b: usize, point: &Coordinate) -> Result<f64, TopologyError> {
+        Ok(self.perspective_at(a, point)?.distance(&self.perspective_at(b, point)?))
+    }
+
+    /// All pairwise divergences at `point` (empty off the base)
+    pub fn divergence_matrix(&self, point: &Coordinate) -> DMatrix<f64> {
+        let states = self.states_at(point);
+        let n = states.len();
+        DMatrix::from_fn(n, n, |i, j| (&states[i] - &states[j]).norm())
+    }
+
+    /// Total pairwise divergence at `point` (zero off the base)
+    pub fn disagreement(&self, point: &Coordinate) -> f64 {
+        let states = self.states_at(point);
+        let mut total = 0.0;
+        for (i, a) in states.iter().enumerate() {
+            for b in &states[i + 1..] {
+                total += (a - b).norm();
+            }
+        }
+        total
+    }
+
+    fn states_at(&self, point: &Coordinate) -> Vec<DVector<f64>> {
+        (0..self.agents.len())
+            .filter_map(|i| self.perspective_at(i, point).ok())
+            .map(|p| p.state)
+            .collect()
+    }
+
+    /// Local minima of the disagreement, best first
+    ///
+    /// A compass search starts from every agent and from their centroid.
+    /// Each search only pins its minimum down to about `tolerance`, so
+    /// minima closer than ten times `tolerance` (at least 1e-5) are merged.
+    pub fn consensus_points(&self, tolerance: f64) -> Vec<ConsensusPoint> {
+        if self.agents.is_empty() {
+            return Vec::new();
+        }
+
+        let dimension = self.agents[0].position.dimension;
+        let mut centroid = DVector::zeros(dimension);
+        for agent in &self.agents {
+            centroid += &agent.position.position / self.agents.len() as f64;
+        }
+        let spread = self.agents.iter()
+            .map(|a| (&a.position.position - &centroid).norm())
+            .fold(0.0, f64::max)
+            .max(1.0);
+
+        let mut starts = vec![centroid];
+        starts.extend(self.agents.iter().map(|a| a.position.position.clone()));
+
+        let mut found: Vec<ConsensusPoint> = Vec::new();
+        for start in starts {
+            let point = self.descend(start, spread / 2.0, tolerance);
+            if found.iter().any(|c| c.point.distance_squared(&point).sqrt() < tolerance.max(1e-6) * 10.0) {
+                continue;
+            }
+            let states = self.states_at(&point);
+            let mean_state = states.iter().fold(DVector::zeros(self.bundle.fiber_dimension), |m, s| m + s)
+                / states.len() as f64;
+            found.push(ConsensusPoint { disagreement: self.disagreement(&point), point, mean_state });
+        }
+
+        found.sort_by(|a, b| a.disagreement.total_cmp(&b.disagreement));
+        found
+    }
+
+    /// The best consensus point
+    pub fn consensus_point(&self) -> Option<ConsensusPoint> {
+        self.consensus_points(1e-6).into_iter().next()
+    }
+
+    /// Compass search: try ± steps along each axis, halve the step when stuck
+    fn descend(&self, start: DVector<f64>, initial_step: f64, tolerance: f64) -> Coordinate {
+        let mut x = Coordinate::new(start.as_slice().to_vec());
+        let mut value = self.disagreement(&x);
+        let mut step = initial_step;
+
+        for _ in 0..10_000 {
+            if step < tolerance {
+                break;
+            }
+            let mut improved = false;
+            'directions: for k in 0..x.dimension {
+                for sign in [1.0, -1.0] {
+                    let mut y = x.clone();
+                    y.position[k] += sign * step;
+                    let candidate = self.disagreement(&y);
+                    if candidate < value - 1e-15 {
+                        x = y;
+                        value = candidate;
+                        improved = true;
+                        break 'directions;
+                    }
+                }
+            }
+            if !improved {
+                step /= 2.0;
+            }
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_loop() -> Path {
+        Path::closed(vec![
+            Coordinate::new(vec![0.0, 0.0]),
+            Coordinate::new(vec![1.0, 0.0]),
+            Coordinate::new(vec![1.0, 1.0]),
+            Coordinate::new(vec![0.0, 1.0]),
+        ])
+    }
+
+    fn pair(curvature: f64) -> Population {
+        let mut population = Population::new(FiberBundle::curved(2, 2, curvature));
+        for name in ["Ada", "Basho"] {
+            population
+                .add_self(name, WorldPoint::new(vec![0.0, 0.0], "square"), Perspective::new(2).with_state(vec![1.0, 0.0]))
+                .unwrap();
+        }
+        population
+    }
+
+    #[test]
+    fn test_histories_create_divergence() {
+        let mut population = pair(0.5);
+        population.journey(0, &unit_loop()).unwrap();
+
+        // Same start, same place, different history: the loop's holonomy remains
+        let origin = Coordinate::new(vec![0.0, 0.0]);
+        let divergence = population.divergence(0, 1, &origin).unwrap();
+        assert!((divergence - 2.0 * (0.25f64).sin()).abs() < 1e-9);
+        assert_eq!(population.agent(0).unwrap().history.len(), 2);
+    }
+
+    #[test]
+    fn test_flat_world_has_no_divergence() {
+        let mut population = pair(0.0);
+        population.journey(0, &unit_loop()).unwrap();
+        population.journey(1, &Path::new(vec![Coordinate::new(vec![3.0, -2.0])])).unwrap();
+
+        let matrix = population.divergence_matrix(&Coordinate::new(vec![5.0, 5.0]));
+        assert!(matrix.norm() < 1e-9);
+        assert_eq!(population.agent(1).unwrap().position, Coordinate::new(vec![3.0, -2.0]));
+    }
+
+    #[test]
+    fn test_consensus_on_the_axis() {
+        // Radial transport is trivial here, so agents at (±1, 0) agree anywhere on y = 0
+        let mut population = Population::new(FiberBundle::curved(2, 2, 1.0));
+        for x in [1.0, -1.0] {
+            population
+                .add_self("agent", WorldPoint::new(vec![x, 0.0], "start"), Perspective::new(2).with_state(vec![1.0, 0.0]))
+                .unwrap();
+        }
+
+        let off_axis = Coordinate::new(vec![0.0, 1.0]);
+        assert!(population.disagreement(&off_axis) > 0.1);
+
+        let consensus = population.consensus_point().unwrap();
+        assert!(consensus.disagreement < 1e-6);
+        assert!(consensus.point.position[1].abs() < 1e-5);
+        assert!((consensus.mean_state.norm() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_fiber_dimension_checked() {
+        let mut population = Population::new(FiberBundle::trivial(2, 3));
+        let result = population.add_self("mismatch", WorldPoint::new(vec![0.0, 0.0], "here"), Perspective::new(2));
+        assert!(matches!(result, Err(TopologyError::DimensionMismatch { expected: 3, found: 2 })));
+        assert!(population.is_empty());
+    }
+
+    #[test]
+    fn test_base_dimension_checked() {
+        let mut population = pair(0.5);
+        let result = population.add_self("adrift", WorldPoint::new(vec![0.0, 0.0, 0.0], "here"), Perspective::new(2));
+        assert!(matches!(result, Err(TopologyError::DimensionMismatch { expected: 2, found: 3 })));
+
+        let elsewhere = Coordinate::new(vec![1.0]);
+        assert!(matches!(population.perspective_at(0, &elsewhere), Err(TopologyError::DimensionMismatch { .. })));
+        assert!(population.divergence(0, 1, &elsewhere).is_err());
+        assert!(population.journey(0, &Path::new(vec![elsewhere.clone()])).is_err());
+        assert_eq!(population.disagreement(&elsewhere), 0.0);
+        assert_eq!(population.len(), 2);
+
+        let here = Coordinate::new(vec![0.0, 0.0]);
+        assert!(matches!(population.perspective_at(5, &here), Err(TopologyError::NotFound { .. })));
+        assert!(matches!(population.journey(5, &Path::new(vec![here])), Err(TopologyError::NotFound { .. })));
+    }
+}
@@ -0,0 +1,412 @@
+//! Presentations - groups from generators and relations.
+//!
+//! A presentation names generators (lower-case letters) and relators that
+//! equal the identity. Relators are words:
+//!
+//! - `a` is a generator and `A` its inverse
+//! - `x^n` raises a letter, a bracket or a commutator to a power (n may be negative)
+//! - `(…)` groups a word
+//! - `[x,y]` is the commutator x⁻¹y⁻¹xy
+//!
+//! Todd–Coxeter enumeration (HLT with coincidences) lists the cosets of a
+//! subgroup. The generators act on them by permutations, which gives a
+//! `PermutationGroup`. Over the trivial subgroup this is the regular
+//! representation, so the group's order is the number of cosets. For
+//! example, ⟨a, b | a², b³, (ab)⁷, [a,b]⁴⟩ is PSL(2,7) of order 168.
+
+use crate::permutation::{Permutation, PermutationGroup, PermutationKernel};
+use crate::types::*;
+
+/// Most cosets the enumeration may define before giving up
+pub const MAX_COSETS: usize = 500_000;
+
+/// Longest word a relator or subgroup generator may expand to
+pub const MAX_WORD_LENGTH: usize = 1_000_000;
+
+/// An undefined coset table entry
+const UNDEFINED: usize = usize::MAX;
+
+/// A word as coset-table columns: 2g for generator g, 2g + 1 for its inverse
+type Word = Vec<usize>;
+
+/// Inverse of a word
+fn invert(word: &[usize]) -> Word {
+    word.iter().rev().map(|&x| x ^ 1).collect()
+}
+
+/// A finite presentation ⟨generators | relators⟩
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Presentation {
+    /// Generator names
+    pub generators: Vec<char>,
+    /// Relators, as letters (2g for a generator, 2g + 1 for its inverse)
+    pub relators: Vec<Vec<usize>>,
+}
+
+impl Presentation {
+    /// Parse generators such as "ab" and relators such as "a^2", "(ab)^7", "[a,b]^4"
+    pub fn parse(generators: &str, relators: &[&str]) -> Result<Self, TopologyError> {
+        let names: Vec<char> = generators.chars().filter(|c| !c.is_whitespace()).collect();
+        for (i, &c) in names.iter().enumerate() {
+            if !c.is_ascii_lowercase() || names[..i].contains(&c) {
+                return Err(TopologyError::malformed("presentation", format!("generator '{}' must be a new lower-case letter", c)));
+            }
+        }
+        let mut presentation = Self { generators: names, relators: Vec::new() };
+        for relator in relators {
+            let word = presentation.word(relator)?;
+            presentation.relators.push(word);
+        }
+        Ok(presentation)
+    }
+
+    /// Parse a word over the generators
+    pub fn word(&self, text: &str) -> Result<Vec<usize>, TopologyError> {
+        let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+        let mut position = 0;
+        let word = self.sequence(&chars, &mut position)?;
+        match chars.get(position) {
+            None => Ok(reduce(word)),
+            Some(c) => Err(TopologyError::malformed("presentation", format!("unexpected '{}' in \"{}\"", c, text))),
+        }
+    }
+
+    /// A run of factors, up to a closing bracket or comma
+    fn sequence(&self, chars: &[char], position: &mut usize) -> Result<Word, TopologyError> {
+        let mut word = Word::new();
+        while let Some(&c) = chars.get(*position) {
+            let factor = match c {
+                ')' | ']' | ',' => break,
+                '(' => {
+                    *position += 1;
+                    let inner = self.sequence(chars, position)?;
+                    self.expect(chars, position, ')')?;
+                    inner
+                }
+                '[' => {
+                    *position += 1;
+                    let x = self.sequence(chars, position)?;
+                    self.expect(chars, position, ',')?;
+                    let y = self.sequence(chars, position)?;
+                    self.expect(chars, position, ']')?;
+                    [invert(&x), invert(&y), x, y].concat()
+                }
+                letter => {
+                    *position += 1;
+                    let g = self.generators.iter()
+                        .position(|&name| name == letter.to_ascii_lowercase())
+                        .ok_or_else(|| TopologyError::malformed("presentation", format!("'{}' is not a generator", letter)))?;
+                    vec![2 * g + usize::from(letter.is_ascii_uppercase())]
+                }
+            };
+            let power = self.exponent(chars, position)?;
+            let base = if power < 0 { invert(&factor) } else { factor };
+            let length = usize::try_from(power.unsigned_abs()).ok()
+                .and_then(|p| p.checked_mul(base.len()))
+                .and_then(|n| n.checked_add(word.len()));
+            if !matches!(length, Some(n) if n <= MAX_WORD_LENGTH) {
+                return Err(TopologyError::malformed("presentation", format!("word expands past {} letters", MAX_WORD_LENGTH)));
+            }
+            for _ in 0..power.unsigned_abs() {
+                word.extend_from_slice(&base);
+            }
+        }
+        Ok(word)
+    }
+
+    /// An optional "^n"
+    fn exponent(&self, chars: &[char], position: &mut usize) -> Result<i64, TopologyError> {
+        if chars.get(*position) != Some(&'^') {
+            return Ok(1);
+        }
+        *position += 1;
+        let start = *position;
+        if chars.get(*position) == Some(&'-') {
+            *position += 1;
+        }
+        while chars.get(*position).is_some_and(|c| c.is_ascii_digit()) {
+            *position += 1;
+        }
+        let digits: String = chars[start..*position].iter().collect();
+        digits.parse().map_err(|_| TopologyError::malformed("presentation", format!("bad exponent \"{}\"", digits)))
+    }
+
+    fn expect(&self, chars: &[char], position: &mut usize, close: char) -> Result<(), TopologyError> {
+        if chars.get(*position) == Some(&close) {
+            *position += 1;
+            Ok(())
+        } else {
+            Err(TopologyError::malformed("presentation", format!("expected '{}' at position {}", close, position)))
+        }
+    }
+
+    /// Enumerate the cosets of the subgroup generated by some words
+    ///
+    /// Returns the permutation of the cosets made by each generator; coset 0
+    /// is the subgroup itself.
+    pub fn coset_action(&self, subgroup: &[&str]) -> Result<Vec<Permutation>, TopologyError> {
+        let subgroup = subgroup.iter().map(|w| self.word(w)).collect::<Result<Vec<_>, _>>()?;
+        let mut table = CosetTable::new(2 * self.generators.len());
+
+        for word in &subgroup {
+            table.scan_and_fill(0, word)?;
+        }
+        let mut coset = 0;
+        while coset < table.rows.len() {
+            for relator in &self.relators {
+                if !table.is_live(coset) {
+                    break;
+                }
+                table.scan_and_fill(coset, relator)?;
+            }
+            if table.is_live(coset) {
+                for x in 0..table.columns {
+                    if table.rows[coset][x] == UNDEFINED {
+                        table.define(coset, x)?;
+                    }
+                }
+            }
+            coset += 1;
+        }
+        table.permutations()
+    }
+
+    /// The group as permutations of its own elements (the regular representation)
+    pub fn group(&self) -> Result<PermutationGroup, TopologyError> {
+        let generators = self.coset_action(&[])?;
+        let degree = generators.first().map_or(1, |g| g.degree());
+        PermutationGroup::new(degree, generators)
+    }
+
+    /// A kernel acting on the cosets of a subgroup (empty for the regular action)
+    pub fn kernel(&self, name: impl Into<String>, subgroup: &[&str]) -> Result<PermutationKernel, TopologyError> {
+        let generators = self.coset_action(subgroup)?;
+        let degree = generators.first().map_or(1, |g| g.degree());
+        PermutationKernel::new(name, PermutationGroup::new(degree, generators)?)
+    }
+
+    /// ⟨a, b | a², b³, (ab)⁷, [a,b]⁴⟩, a presentation of PSL(2,7)
+    pub fn psl27() -> Self {
+        Self::parse("ab", &["a^2", "b^3", "(ab)^7", "[a,b]^4"]).expect("the PSL(2,7) presentation is well formed")
+    }
+}
+
+/// Cancel adjacent inverse letters
+fn reduce(word: Word) -> Word {
+    let mut reduced = Word::new();
+    for x in word {
+        if reduced.last() == Some(&(x ^ 1)) {
+            reduced.pop();
+        } else {
+            reduced.push(x);
+        }
+    }
+    reduced
+}
+
+/// A partial coset table with coincidence handling
+struct CosetTable {
+    columns: usize,
+    rows: Vec<Vec<usize>>,
+    /// Union–find forest; a coset is live when it is its own parent
+    parent: Vec<usize>,
+}
+
+impl CosetTable {
+    fn new(columns: usize) -> Self {
+        Self { columns, rows: vec![vec![UNDEFINED; columns]], parent: vec![0] }
+    }
+
+    fn is_live(&self, coset: usize) -> bool {
+        self.parent[coset] == coset
+    }
+
+    fn define(&mut self, coset: usize, x: usize) -> Result<(), TopologyError> {
+        if self.rows.len() >= MAX_COSETS {
+            return Err(TopologyError::group_structure(format!("coset enumeration exceeded {} cosets", MAX_COSETS)));
+        }
+        let new = self.rows.len();
+        self.rows.push(vec![UNDEFINED; self.columns]);
+        self.parent.push(new);
+        self.rows[coset][x] = new;
+        self.rows[new][x ^ 1] = coset;
+        Ok(())
+    }
+
+    fn representative(&mut self, coset: usize) -> usize {
+        let mut root = coset;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = coset;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    fn merge(&mut self, a: usize, b: usize, queue: &mut Vec<usize>) {
+        let (a, b) = (self.representative(a), self.representative(b));
+        if a != b {
+            let (keep, lose) = (a.min(b), a.max(b));
+            self.parent[lose] = keep;
+            queue.push(lose);
+        }
+    }
+
+    /// Identify two cosets and everything that follows from it
+    fn coincidence(&mut self, a: usize, b: usize) {
+        let mut queue = Vec::new();
+        self.merge(a, b, &mut queue);
+        let mut i = 0;
+        while i < queue.len() {
+            let dead = queue[i];
+            i += 1;
+            for x in 0..self.columns {
+                let f = self.rows[dead][x];
+                if f == UNDEFINED {
+                    continue;
+                }
+                self.rows[f][x ^ 1] = UNDEFINED;
+                let (e1, f1) = (self.representative(dead), self.representative(f));
+                if self.rows[e1][x] != UNDEFINED {
+                    let target = self.rows[e1][x];
+                    self.merge(f1, target, &mut queue);
+                } else if self.rows[f1][x ^ 1] != UNDEFINED {
+                    let target = self.rows[f1][x ^ 1];
+                    self.merge(e1, target, &mut queue);
+                } else {
+                    self.rows[e1][x] = f1;
+                    self.rows[f1][x ^ 1] = e1;
+                }
+            }
+        }
+    }
+
+    /// Trace a word from a coset both ways, defining cosets to close the gap
+    fn scan_and_fill(&mut self, coset: usize, word: &[usize]) -> Result<(), TopologyError> {
+        if word.is_empty() {
+            return Ok(());
+        }
+        let (mut f, mut b) = (coset, coset);
+        let (mut i, mut j) = (0, word.len());
+        loop {
+            while i < j && self.rows[f][word[i]] != UNDEFINED {
+                f = self.rows[f][word[i]];
+                i += 1;
+            }
+            if i == j {
+                if f != b {
+                    self.coincidence(f, b);
+                }
+                return Ok(());
+            }
+            while j > i && self.rows[b][word[j - 1] ^ 1] != UNDEFINED {
+                b = self.rows[b][word[j - 1] ^ 1];
+                j -= 1;
+            }
+            if j == i {
+                self.coincidence(f, b);
+                return Ok(());
+            }
+            if j == i + 1 {
+                // A deduction closes the cycle
+                self.rows[f][word[i]] = b;
+                self.rows[b][word[i] ^ 1] = f;
+                return Ok(());
+            }
+            self.define(f, word[i])?;
+        }
+    }
+
+    /// The live cosets, renumbered, permuted by each generator
+    fn permutations(&mut self) -> Result<Vec<Permutation>, TopologyError> {
+        let live: Vec<usize> = (0..self.rows.len()).filter(|&c| self.is_live(c)).collect();
+        let mut number = vec![UNDEFINED; self.rows.len()];
+        for (n, &c) in live.iter().enumerate() {
+            number[c] = n;
+        }
+        (0..self.columns / 2)
+            .map(|g| {
+                let images = live.iter()
+                    .map(|&c| {
+                        let image = self.rows[c][2 * g];
+                        if image == UNDEFINED {
+                            return UNDEFINED;
+                        }
+                        number[self.representative(image)]
+                    })
+                    .collect();
+                Permutation::new(images).map_err(|_| TopologyError::group_structure("coset table did not close"))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_words() {
+        let p = Presentation::parse("ab", &[]).unwrap();
+        assert_eq!(p.word("ab").unwrap(), vec![0, 2]);
+        assert_eq!(p.word("a^-2").unwrap(), vec![1, 1]);
+        assert_eq!(p.word("[a,b]").unwrap(), vec![1, 3, 0, 2]);
+        assert_eq!(p.word("(aB)^2 bA").unwrap(), vec![0, 3]);
+        assert!(p.word("abc").is_err());
+        assert!(p.word("(ab").is_err());
+        assert!(Presentation::parse("aA", &[]).is_err());
+        assert!(matches!(p.word("(ab)^4000000000"), Err(TopologyError::MalformedInput { .. })));
+        assert!(p.word("((ab)^1000)^1000").is_err());
+    }
+
+    #[test]
+    fn test_small_groups() {
+        let cyclic = Presentation::parse("a", &["a^6"]).unwrap();
+        assert_eq!(cyclic.group().unwrap().order(), 6);
+
+        let a5 = Presentation::parse("ab", &["a^2", "b^3", "(ab)^5"]).unwrap();
+        assert_eq!(a5.group().unwrap().order(), 60);
+
+        let quaternion = Presentation::parse("ij", &["i^4", "i^2 J^2", "j i J i"]).unwrap();
+        assert_eq!(quaternion.group().unwrap().order(), 8);
+
+        // Z2 * Z3 is infinite: the enumeration gives up
+        let modular = Presentation::parse("ab", &["a^2", "b^3"]).unwrap();
+        assert!(matches!(modular.group(), Err(TopologyError::GroupStructure { .. })));
+    }
+
+    #[test]
+    fn test_psl27_from_relations() {
+        let presentation = Presentation::psl27();
+        let kernel = presentation.kernel("PSL(2,7)", &[]).unwrap();
+        assert_eq!(kernel.order(), 168);
+        assert_eq!(kernel.group.degree(), 168);
+
+        // The 7 cosets of ⟨aba, Bab⟩ (an S₄) are the points of the Fano plane
+        let fano = presentation.coset_action(&["aba", "Bab"]).unwrap();
+        assert_eq!(fano[0].degree(), 7);
+        let group = PermutationGroup::new(7, fano).unwrap();
+        assert_eq!(group.order(), 168);
+        assert!(group.is_transitive());
+
+        // Triples split into the 7 lines and the 28 triangles
+        let mut sizes: Vec<usize> = [vec![0, 1, 2], vec![0, 1, 3], vec![0, 1, 4], vec![0, 1, 5], vec![0, 1, 6]]
+            .iter()
+            .map(|t| group.orbit_of_set(t).len())
+            .collect();
+        sizes.sort_unstable();
+        sizes.dedup();
+        assert_eq!(sizes, vec![7, 28]);
+
+        let census = |elements: Vec<Permutation>| {
+            let mut orders: Vec<usize> = elements.iter().map(|e| e.order()).collect();
+            orders.sort_unstable();
+            orders
+        };
+        assert_eq!(census(group.elements().unwrap()), census(PermutationKernel::psl27().elements));
+    }
+}
@@ -0,0 +1,291 @@
+//! Quaternions - unit-quaternion rotations and the binary groups.
+//!
+//! Every rotation is ±q for a unit quaternion q. Taking both signs of every
+//! kernel element gives its double cover:
+//!
+//! - A₅ (60) lifts to the binary icosahedral group 2I (120)
+//! - S₄ lifts to 2O, A₄ to 2T, Dₙ to 2Dₙ and Zₙ to Z₂ₙ
+//!
+//! Quaternions compose exactly, compare cheaply, and slerp along great
+//! circles, which gives timbral morphs a constant angular speed.
+
+use crate::group::FiniteGroup;
+use crate::kernel::{GenerativeKernel, GroupElement};
+use crate::types::*;
+use nalgebra::{Quaternion, Rotation3, UnitQuaternion, Vector3};
+
+/// Tolerance for comparing quaternions
+const QUATERNION_TOLERANCE: f64 = 1e-9;
+
+/// The sign of ±q with w > 0, or the first nonzero of i, j, k positive
+fn canonical(q: UnitQuaternion<f64>) -> UnitQuaternion<f64> {
+    let c = q.coords;
+    let leading = [c.w, c.x, c.y, c.z].into_iter().find(|v| v.abs() > QUATERNION_TOLERANCE).unwrap_or(1.0);
+    if leading < 0.0 {
+        UnitQuaternion::new_unchecked(-q.into_inner())
+    } else {
+        q
+    }
+}
+
+fn same(a: &UnitQuaternion<f64>, b: &UnitQuaternion<f64>) -> bool {
+    (a.coords - b.coords).norm() < QUATERNION_TOLERANCE
+}
+
+impl GroupElement {
+    /// The unit quaternion of this rotation, with canonical sign
+    pub fn quaternion(&self) -> UnitQuaternion<f64> {
+        canonical(UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix(&self.matrix)))
+    }
+
+    /// An element from a unit quaternion (either sign)
+    pub fn from_quaternion(q: &UnitQuaternion<f64>, name: impl Into<String>) -> Self {
+        let matrix = q.to_rotation_matrix().into_inner();
+        Self { matrix, name: name.into() }
+    }
+
+    /// The rotation a fraction t of the way from this element to another
+    ///
+    /// Follows the shorter great circle on the 3-sphere.
+    pub fn slerp(&self, other: &GroupElement, t: f64) -> UnitQuaternion<f64> {
+        let (a, mut b) = (self.quaternion(), other.quaternion());
+        if a.coords.dot(&b.coords) < 0.0 {
+            b = UnitQuaternion::new_unchecked(-b.into_inner());
+        }
+        a.try_slerp(&b, t, QUATERNION_TOLERANCE).unwrap_or(a)
+    }
+}
+
+/// The double cover of a rotation group in the unit quaternions
+///
+/// Element 2i is the canonical lift of rotation i and 2i + 1 its negative,
+/// so element 0 is 1 and element 1 is −1.
+#[derive(Debug, Clone)]
+pub struct BinaryGroup {
+    /// Name, e.g. "2I"
+    pub name: String,
+    /// The unit quaternions
+    pub quaternions: Vec<UnitQuaternion<f64>>,
+    products: Vec<Vec<usize>>,
+}
+
+impl BinaryGroup {
+    /// Lift a rotation group
+    pub fn double_cover(group: &FiniteGroup) -> Self {
+        let lifts: Vec<UnitQuaternion<f64>> = group.elements.iter().map(|g| g.quaternion()).collect();
+        let quaternions: Vec<UnitQuaternion<f64>> = lifts.iter()
+            .flat_map(|&q| [q, UnitQuaternion::new_unchecked(-q.into_inner())])
+            .collect();
+
+        // The product lies over the product of rotations; only its sign is unknown
+        let products = (0..quaternions.len())
+            .map(|a| {
+                (0..quaternions.len())
+                    .map(|b| {
+                        let below = group.multiply(a / 2, b / 2);
+                        let product = quaternions[a] * quaternions[b];
+                        2 * below + usize::from(!same(&product, &lifts[below]))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let base = group.isomorphism_type(&(0..group.order()).collect::<Vec<_>>());
+        let name = match base.as_str() {
+            "1" => "Z2".to_string(),
+            "V4" => "Q8".to_string(),
+            "A4" => "2T".to_string(),
+            "S4" => "2O".to_string(),
+            "A5" => "2I".to_string(),
+            other => match (other.strip_prefix('Z'), other.strip_prefix('D')) {
+                (Some(n), _) => format!("Z{}", 2 * n.parse::<usize>().unwrap_or(0)),
+                (_, Some(n)) => format!("2D{}", n),
+                _ => format!("2{}", other),
+            },
+        };
+        Self { name, quaternions, products }
+    }
+
+    /// Number of elements
+    pub fn order(&self) -> usize {
+        self.quaternions.len()
+    }
+
+    /// Index of a unit quaternion, if present
+    pub fn index_of(&self, q: &UnitQuaternion<f64>) -> Option<usize> {
+        self.quaternions.iter().position(|p| same(p, q))
+    }
+
+    /// Index of a · b
+    pub fn multiply(&self, a: usize, b: usize) -> usize {
+        self.products[a][b]
+    }
+
+    /// Index of a⁻¹
+    pub fn inverse(&self, a: usize) -> usize {
+        (0..self.order()).find(|&b| self.multiply(a, b) == 0).unwrap_or(0)
+    }
+
+    /// Smallest n with aⁿ = 1
+    pub fn element_order(&self, a: usize) -> usize {
+        let mut current = a;
+        let mut n = 1;
+        while current != 0 {
+            current = self.multiply(current, a);
+            n += 1;
+        }
+        n
+    }
+
+    /// The rotation an element covers
+    pub fn projection(&self, a: usize) -> usize {
+        a / 2
+    }
+
+    /// Elements commuting with everything
+    pub fn center(&self) -> Vec<usize> {
+        (0..self.order())
+            .filter(|&a| (0..self.order()).all(|b| self.multiply(a, b) == self.multiply(b, a)))
+            .collect()
+    }
+}
+
+impl GenerativeKernel {
+    /// The kernel's own element for a rotation, with its canonical name
+    pub fn reduce(&self, element: &GroupElement) -> Option<&GroupElement> {
+        let q = element.quaternion();
+        self.elements.iter().find(|g| same(&g.quaternion(), &q))
+    }
+
+    /// Compose two elements and reduce to the canonical element
+    pub fn compose(&self, a: &GroupElement, b: &GroupElement) -> Result<GroupElement, TopologyError> {
+        let product = a.compose(b);
+        self.reduce(&product).cloned()
+            .ok_or_else(|| TopologyError::group_structure(format!("{} is not an element of {:?}", product.name, self.group)))
+    }
+
+    /// The double cover of the kernel's group (2I for the icosahedral kernel)
+    pub fn binary_group(&self) -> Result<BinaryGroup, TopologyError> {
+        Ok(BinaryGroup::double_cover(&self.group_structure()?))
+    }
+
+    /// A point carried along the slerp from one element to another
+    ///
+    /// Returns `steps + 1` points from a·point to b·point with equal angular
+    /// spacing.
+    pub fn slerp_path(&self, a: &GroupElement, b: &GroupElement, point: &Vector3<f64>, steps: usize) -> Vec<Vector3<f64>> {
+        let steps = steps.max(1);
+        (0..=steps)
+            .map(|i| a.slerp(b, i as f64 / steps as f64) * point)
+            .collect()
+    }
+}
+
+/// A quaternion from its components w + xi + yj + zk, normalized
+pub fn unit_quaternion(w: f64, x: f64, y: f64, z: f64) -> UnitQuaternion<f64> {
+    UnitQuaternion::from_quaternion(Quaternion::new(w, x, y, z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_quaternions_match_matrices() {
+        let kernel = GenerativeKernel::icosahedral();
+        for a in &kernel.elements {
+            let q = a.quaternion();
+            assert!(q.w > -1e-9);
+            let back = GroupElement::from_quaternion(&q, "back");
+            assert!((back.matrix - a.matrix).norm() < 1e-9);
+            assert_eq!(back.order().unwrap(), a.order().unwrap());
+            for b in kernel.elements.iter().take(12) {
+                let product = canonical(q * b.quaternion());
+                assert!(same(&product, &a.compose(b).quaternion()));
+            }
+        }
+        assert!(same(&unit_quaternion(2.0, 0.0, 0.0, 0.0), &GroupElement::identity().quaternion()));
+    }
+
+    #[test]
+    fn test_binary_icosahedral_group() {
+        let binary = GenerativeKernel::icosahedral().binary_group().unwrap();
+        assert_eq!(binary.name, "2I");
+        assert_eq!(binary.order(), 120);
+        assert_eq!(binary.center(), vec![0, 1]);
+
+        let mut orders: BTreeMap<usize, usize> = BTreeMap::new();
+        for a in 0..binary.order() {
+            *orders.entry(binary.element_order(a)).or_insert(0) += 1;
+            assert_eq!(binary.multiply(a, binary.inverse(a)), 0);
+        }
+        // −1 is the only involution
+        let expected = BTreeMap::from([(1, 1), (2, 1), (3, 20), (4, 30), (5, 24), (6, 20), (10, 24)]);
+        assert_eq!(orders, expected);
+
+        // Projection is a homomorphism onto A₅
+        let group = GenerativeKernel::icosahedral().group_structure().unwrap();
+        for a in (0..120).step_by(7) {
+            for b in (0..120).step_by(5) {
+                let ab = binary.multiply(a, b);
+                assert_eq!(binary.projection(ab), group.multiply(binary.projection(a), binary.projection(b)));
+                assert!(same(&binary.quaternions[ab], &(binary.quaternions[a] * binary.quaternions[b])));
+            }
+        }
+    }
+
+    #[test]
+    fn test_other_double_covers() {
+        let names: Vec<String> = [
+            GenerativeKernel::trivial(),
+            GenerativeKernel::cyclic(3).unwrap(),
+            GenerativeKernel::dihedral(2).unwrap(),
+            GenerativeKernel::dihedral(5).unwrap(),
+            GenerativeKernel::tetrahedral(),
+            GenerativeKernel::octahedral(),
+        ]
+        .iter()
+        .map(|k| k.binary_group().unwrap())
+        .map(|b| format!("{}/{}", b.name, b.order()))
+        .collect();
+        assert_eq!(names, vec!["Z2/2", "Z6/6", "Q8/8", "2D5/20", "2T/24", "2O/48"]);
+    }
+
+    #[test]
+    fn test_slerp_moves_at_constant_speed() {
+        let kernel = GenerativeKernel::icosahedral();
+        let (a, b) = (&kernel.elements[0], &kernel.elements[30]);
+        assert!(same(&a.slerp(b, 0.0), &a.quaternion()));
+        assert!(same(&canonical(a.slerp(b, 1.0)), &b.quaternion()));
+
+        let point = kernel.geometry.vertices[0];
+        let path = kernel.slerp_path(a, b, &point, 8);
+        assert_eq!(path.len(), 9);
+        assert!((path[8] - b.apply(&point)).norm() < 1e-9);
+        let steps: Vec<f64> = (0..8).map(|i| a.slerp(b, i as f64 / 8.0).angle_to(&a.slerp(b, (i + 1) as f64 / 8.0))).collect();
+        assert!(steps.iter().all(|s| (s - steps[0]).abs() < 1e-9));
+        assert!(path.iter().all(|p| (p.norm() - 1.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_canonical_names_after_reduction() {
+        let kernel = GenerativeKernel::icosahedral();
+        let (a, b) = (&kernel.elements[13], &kernel.elements[40]);
+        let raw = a.compose(b).compose(a).compose(b);
+        assert!(raw.name.contains('∘'));
+        let reduced = kernel.reduce(&raw).unwrap();
+        assert!(!reduced.name.contains('∘'));
+        assert_eq!(reduced.order().unwrap(), raw.order().unwrap());
+        assert_eq!(kernel.compose(a, b).unwrap().name, kernel.reduce(&a.compose(b)).unwrap().name);
+
+        assert_eq!(a.compose(&a.inverse()).name, "e");
+        assert_eq!(a.inverse().inverse().name, a.name);
+        assert_eq!(a.compose(&GroupElement::identity()).name, a.name);
+
+        // Orders come from the angle, beyond the icosahedral 60
+        let turn = GroupElement::rotation(Vector3::z(), 2.0 * std::f64::consts::PI * 3.0 / 97.0, "r");
+        assert_eq!(turn.order().unwrap(), 97);
+        assert!(kernel.reduce(&turn).is_none());
+    }
+}
@@ -0,0 +1,657 @@
+//! Cellular Sheaves - belief data glued over the Concept Complex.
+//!
+//! Each Idea holds a vector space of belief values (its stalk), each
+//! relation a space of shared claims, and restriction maps say how an
+//! idea's beliefs project onto what the relation asserts.
+//!
+//! - Global sections (H⁰) are belief assignments consistent everywhere.
+//! - H¹ = ker δ¹ / im δ⁰ collects obstructions to gluing. Filled triangles
+//!   whose restrictions commute carry stalks of their own, so the loop
+//!   around them is a boundary and not an obstruction.
+//! - A twisted loop trades its H¹ class for a lost global section (the
+//!   Euler characteristic depends only on the stalks), so comparing with a
+//!   flat reference sheaf isolates paradoxes: beliefs that agree along every
+//!   relation yet cannot be reconciled around a loop.
+//!
+//! The sheaf lives on the 2-skeleton (ideas, pairwise relations and
+//! filled triangles).
+
+use crate::simplex::{cycle_basis, ConceptComplex, HomologicalHole};
+use crate::types::*;
+use nalgebra::{DMatrix, DVector};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Numerical tolerance for ranks and kernels
+const EPSILON: f64 = 1e-9;
+
+/// A relation with its stalk and restriction maps
+#[derive(Debug, Clone)]
+pub struct SheafEdge {
+    /// Endpoints (u, v) with u < v; coboundary orientation is u → v
+    pub vertices: (usize, usize),
+    /// Dimension of the edge stalk
+    pub dimension: usize,
+    /// Restriction from u's stalk (dimension × dim u)
+    pub from_source: DMatrix<f64>,
+    /// Restriction from v's stalk (dimension × dim v)
+    pub from_target: DMatrix<f64>,
+}
+
+/// A filled triangle of relations with its stalk and restriction maps
+#[derive(Debug, Clone)]
+pub struct SheafTriangle {
+    /// Corners (a, b, c) with a < b < c; δ¹ orientation is [b,c] − [a,c] + [a,b]
+    pub vertices: (usize, usize, usize),
+    /// Dimension of the triangle stalk
+    pub dimension: usize,
+    /// Restriction from the stalk of edge (a, b)
+    pub from_ab: DMatrix<f64>,
+    /// Restriction from the stalk of edge (b, c)
+    pub from_bc: DMatrix<f64>,
+    /// Restriction from the stalk of edge (a, c)
+    pub from_ac: DMatrix<f64>,
+}
+
+/// Dimensions of the sheaf cohomology groups
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SheafCohomology {
+    /// Global sections: consistent belief assignments
+    pub h0: usize,
+    /// Obstructions to gluing, modulo the boundaries of filled triangles
+    pub h1: usize,
+    /// Triangle data no edge assignment reaches
+    pub h2: usize,
+}
+
+/// A cellular sheaf of belief values on a concept complex
+#[derive(Debug, Clone, Default)]
+pub struct CellularSheaf {
+    /// Stalk dimension of each idea
+    pub stalks: BTreeMap<usize, usize>,
+    /// Relations with restriction maps
+    pub edges: Vec<SheafEdge>,
+    /// Filled triangles with restriction maps
+    pub triangles: Vec<SheafTriangle>,
+}
+
+impl CellularSheaf {
+    /// An empty sheaf
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constant sheaf: every stalk is ℝ^dimension, every restriction the identity
+    ///
+    /// Edges and triangles are the 2-skeleton of the complex, including the
+    /// faces of higher relations.
+    pub fn constant(complex: &ConceptComplex, dimension: usize) -> Self {
+        let mut sheaf = Self::new();
+        for &id in complex.vertices.keys() {
+            sheaf.add_vertex(id, dimension);
+        }
+
+        let mut pairs: BTreeSet<(usize, usize)> = BTreeSet::new();
+        let mut triples: BTreeSet<(usize, usize, usize)> = BTreeSet::new();
+        for relation in complex.faces.iter().filter(|f| f.dimension >= 1) {
+            // Relation vertices are sorted
+            let corners = &relation.vertices;
+            for (i, &a) in corners.iter().enumerate() {
+                for (j, &b) in corners.iter().enumerate().skip(i + 1) {
+                    pairs.insert((a, b));
+                    for &c in &corners[j + 1..] {
+                        triples.insert((a, b, c));
+                    }
+                }
+            }
+        }
+        let identity = DMatrix::identity(dimension, dimension);
+        for (a, b) in pairs {
+            // Dimensions match by construction
+            let _ = sheaf.add_edge(a, b, identity.clone(), identity.clone());
+        }
+        for (a, b, c) in triples {
+            // Identities commute
+            let _ = sheaf.add_triangle(a, b, c, identity.clone(), identity.clone(), identity.clone());
+        }
+        sheaf
+    }
+
+    /// Add an idea with a stalk of the given dimension
+    pub fn add_vertex(&mut self, id: usize, dimension: usize) -> &mut Self {
+        self.stalks.insert(id, dimension);
+        self
+    }
+
+    /// Add (or replace) a relation between `a` and `b` with its restriction maps
+    ///
+    /// `from_a` maps a's stalk into the edge stalk, `from_b` maps b's.
+    /// Replacing a relation unfills the triangles on it; add them again
+    /// with `add_triangle` if the new maps still commute.
+    pub fn add_edge(
+        &mut self,
+        a: usize,
+        b: usize,
+        from_a: DMatrix<f64>,
+        from_b: DMatrix<f64>,
+    ) -> Result<(), TopologyError> {
+        let dim_a = *self.stalks.get(&a).ok_or_else(|| TopologyError::not_found(format!("idea {}", a)))?;
+        let dim_b = *self.stalks.get(&b).ok_or_else(|| TopologyError::not_found(format!("idea {}", b)))?;
+
+        if from_a.ncols() != dim_a {
+            return Err(TopologyError::DimensionMismatch { expected: dim_a, found: from_a.ncols() });
+        }
+        if from_b.ncols() != dim_b {
+            return Err(TopologyError::DimensionMismatch { expected: dim_b, found: from_b.ncols() });
+        }
+        if from_a.nrows() != from_b.nrows() {
+            return Err(TopologyError::DimensionMismatch { expected: from_a.nrows(), found: from_b.nrows() });
+        }
+
+        let (vertices, from_source, from_target) = if a < b {
+            ((a, b), from_a, from_b)
+        } else {
+            ((b, a), from_b, from_a)
+        };
+        let edge = SheafEdge {
+            vertices,
+            dimension: from_source.nrows(),
+            from_source,
+            from_target,
+        };
+
+        match self.edges.iter_mut().find(|e| e.vertices == vertices) {
+            Some(existing) => {
+                *existing = edge;
+                self.triangles.retain(|t| !triangle_edges(t.vertices).contains(&vertices));
+            }
+            None => self.edges.push(edge),
+        }
+        Ok(())
+    }
+
+    /// Fill the triangle a, b, c with restriction maps from its three edges
+    ///
+    /// The edges must exist, and the maps must commute with theirs: going
+    /// from a corner to the triangle through either adjacent edge gives the
+    /// same map (so that δ¹δ⁰ = 0).
+    pub fn add_triangle(
+        &mut self,
+        a: usize,
+        b: usize,
+        c: usize,
+        from_ab: DMatrix<f64>,
+        from_bc: DMatrix<f64>,
+        from_ac: DMatrix<f64>,
+    ) -> Result<(), TopologyError> {
+        let mut corners = [a, b, c];
+        corners.sort_unstable();
+        if corners[0] == corners[1] || corners[1] == corners[2] {
+            return Err(TopologyError::invalid_structure(format!("triangle ({}, {}, {}) repeats a corner", a, b, c)));
+        }
+        let vertices = (corners[0], corners[1], corners[2]);
+
+        // Re-key the caller's maps by the sorted edges (ab, ac, bc)
+        let mut given = vec![((a.min(b), a.max(b)), from_ab), ((b.min(c), b.max(c)), from_bc), ((a.min(c), a.max(c)), from_ac)];
+        let mut edges = Vec::with_capacity(3);
+        let mut maps = Vec::with_capacity(3);
+        for key in triangle_edges(vertices) {
+            let edge = self.edge(key).ok_or_else(|| TopologyError::not_found(format!("relation {:?}", key)))?;
+            let at = given.iter().position(|(k, _)| *k == key).expect("a triangle has three distinct edges");
+            let map = given.swap_remove(at).1;
+            if map.ncols() != edge.dimension {
+                return Err(TopologyError::DimensionMismatch { expected: edge.dimension, found: map.ncols() });
+            }
+            edges.push(edge);
+            maps.push(map);
+        }
+        let dimension = maps[0].nrows();
+        if let Some(map) = maps.iter().find(|m| m.nrows() != dimension) {
+            return Err(TopologyError::DimensionMismatch { expected: dimension, found: map.nrows() });
+        }
+
+        // Each corner reaches the triangle through its two edges
+        let (ab, ac, bc) = (edges[0], edges[1], edges[2]);
+        let routes = [
+            (&maps[0] * &ab.from_source, &maps[1] * &ac.from_source),
+            (&maps[0] * &ab.from_target, &maps[2] * &bc.from_source),
+            (&maps[1] * &ac.from_target, &maps[2] * &bc.from_target),
+        ];
+        for (left, right) in &routes {
+            if (left - right).norm() > EPSILON * (1.0 + left.norm().max(right.norm())) {
+                return Err(TopologyError::invalid_structure(format!(
+                    "restrictions around triangle {:?} do not commute",
+                    vertices
+                )));
+            }
+        }
+
+        let [from_ab, from_ac, from_bc]: [DMatrix<f64>; 3] = maps.try_into().expect("three edges");
+        let triangle = SheafTriangle { vertices, dimension, from_ab, from_bc, from_ac };
+        match self.triangles.iter_mut().find(|t| t.vertices == vertices) {
+            Some(existing) => *existing = triangle,
+            None => self.triangles.push(triangle),
+        }
+        Ok(())
+    }
+
+    fn edge(&self, vertices: (usize, usize)) -> Option<&SheafEdge> {
+        self.edges.iter().find(|e| e.vertices == vertices)
+    }
+
+    /// Replace the restriction maps of an existing relation
+    pub fn set_restriction(
+        &mut self,
+        a: usize,
+        b: usize,
+        from_a: DMatrix<f64>,
+        from_b: DMatrix<f64>,
+    ) -> Result<(), TopologyError> {
+        let key = (a.min(b), a.max(b));
+        if !self.edges.iter().any(|e| e.vertices == key) {
+            return Err(TopologyError::not_found(format!("relation {:?}", key)));
+        }
+        self.add_edge(a, b, from_a, from_b)
+    }
+
+    /// Offset of each idea's block in a 0-cochain
+    fn vertex_offsets(&self) -> BTreeMap<usize, usize> {
+        let mut offsets = BTreeMap::new();
+        let mut offset = 0;
+        for (&id, &dim) in &self.stalks {
+            offsets.insert(id, offset);
+            offset += dim;
+        }
+        offsets
+    }
+
+    /// Total dimension of 0-cochains (Σ vertex stalks)
+    pub fn c0_dimension(&self) -> usize {
+        self.stalks.values().sum()
+    }
+
+    /// Total dimension of 1-cochains (Σ edge stalks)
+    pub fn c1_dimension(&self) -> usize {
+        self.edges.iter().map(|e| e.dimension).sum()
+    }
+
+    /// Total dimension of 2-cochains (Σ triangle stalks)
+    pub fn c2_dimension(&self) -> usize {
+        self.triangles.iter().map(|t| t.dimension).sum()
+    }
+
+    /// Coboundary δ: C⁰ → C¹, (δx)_e = F_v x_v - F_u x_u
+    pub fn coboundary(&self) -> DMatrix<f64> {
+        let offsets = self.vertex_offsets();
+        let mut delta = DMatrix::zeros(self.c1_dimension(), self.c0_dimension());
+
+        let mut row = 0;
+        for edge in &self.edges {
+            let (u, v) = edge.vertices;
+            let (cu, cv) = (offsets[&u], offsets[&v]);
+            for r in 0..edge.dimension {
+                for c in 0..edge.from_source.ncols() {
+                    delta[(row + r, cu + c)] -= edge.from_source[(r, c)];
+                }
+                for c in 0..edge.from_target.ncols() {
+                    delta[(row + r, cv + c)] += edge.from_target[(r, c)];
+                }
+            }
+            row += edge.dimension;
+        }
+        delta
+    }
+
+    /// Coboundary δ¹: C¹ → C², (δ¹y)_t = F_bc y_bc − F_ac y_ac + F_ab y_ab
+    pub fn coboundary_1(&self) -> DMatrix<f64> {
+        let mut offsets = BTreeMap::new();
+        let mut offset = 0;
+        for edge in &self.edges {
+            offsets.insert(edge.vertices, offset);
+            offset += edge.dimension;
+        }
+        let mut delta = DMatrix::zeros(self.c2_dimension(), self.c1_dimension());
+
+        let mut row = 0;
+        for triangle in &self.triangles {
+            let (a, b, c) = triangle.vertices;
+            for (key, map, sign) in [((b, c), &triangle.from_bc, 1.0), ((a, c), &triangle.from_ac, -1.0), ((a, b), &triangle.from_ab, 1.0)] {
+                let column = offsets[&key];
+                for r in 0..triangle.dimension {
+                    for k in 0..map.ncols() {
+                        delta[(row + r, column + k)] += sign * map[(r, k)];
+                    }
+                }
+            }
+            row += triangle.dimension;
+        }
+        delta
+    }
+
+    /// Sheaf Laplacian L = δᵀδ
+    pub fn laplacian(&self) -> DMatrix<f64> {
+        let delta = self.coboundary();
+        delta.transpose() * delta
+    }
+
+    /// Kernel of δ (columns) and its rank, read off one SVD
+    ///
+    /// Singular values up to `EPSILON` count as zero, so global sections
+    /// and cohomology always agree.
+    fn coboundary_kernel(&self) -> (DMatrix<f64>, usize) {
+        let n = self.c0_dimension();
+        if n == 0 {
+            return (DMatrix::zeros(0, 0), 0);
+        }
+        // Zero rows pad δ to at least n × n so V spans all of C⁰
+        let delta = self.coboundary();
+        let padded = DMatrix::from_fn(delta.nrows().max(n), n, |i, j| {
+            if i < delta.nrows() { delta[(i, j)] } else { 0.0 }
+        });
+        let svd = padded.svd(false, true);
+        let v_t = svd.v_t.expect("V was requested");
+        let kernel: Vec<DVector<f64>> = (0..n)
+            .filter(|&i| svd.singular_values[i] <= EPSILON)
+            .map(|i| v_t.row(i).transpose())
+            .collect();
+        let rank = n - kernel.len();
+        if kernel.is_empty() {
+            (DMatrix::zeros(n, 0), rank)
+        } else {
+            (DMatrix::from_columns(&kernel), rank)
+        }
+    }
+
+    /// Basis of global sections (columns), the kernel of δ (and of the Laplacian)
+    pub fn global_sections(&self) -> DMatrix<f64> {
+        self.coboundary_kernel().0
+    }
+
+    /// Dimensions of H⁰, H¹ and H²
+    pub fn cohomology(&self) -> SheafCohomology {
+        let rank_0 = self.coboundary_kernel().1;
+        let delta_1 = self.coboundary_1();
+        let rank_1 = if delta_1.is_empty() { 0 } else { delta_1.svd(false, false).rank(EPSILON) };
+        SheafCohomology {
+            // Numerical ranks of a near-non-complex may overshoot; never wrap
+            h0: self.c0_dimension().saturating_sub(rank_0),
+            h1: self.c1_dimension().saturating_sub(rank_0 + rank_1),
+            h2: self.c2_dimension().saturating_sub(rank_1),
+        }
+    }
+
+    /// The flat reference sheaf: same stalks, untwisted (identity-like) restrictions
+    ///
+    /// Triangles are kept where identity-like maps still commute.
+    pub fn reference(&self) -> Self {
+        let mut flat = Self { stalks: self.stalks.clone(), ..Self::default() };
+        for edge in &self.edges {
+            let (u, v) = edge.vertices;
+            flat.edges.push(SheafEdge {
+                vertices: edge.vertices,
+                dimension: edge.dimension,
+                from_source: DMatrix::identity(edge.dimension, self.stalks[&u]),
+                from_target: DMatrix::identity(edge.dimension, self.stalks[&v]),
+            });
+        }
+        for triangle in &self.triangles {
+            let (a, b, c) = triangle.vertices;
+            let from = |key| DMatrix::identity(triangle.dimension, flat.edge(key).map_or(0, |e: &SheafEdge| e.dimension));
+            let (from_ab, from_bc, from_ac) = (from((a, b)), from((b, c)), from((a, c)));
+            // Mixed stalk dimensions may not commute; such triangles stay open
+            let _ = flat.add_triangle(a, b, c, from_ab, from_bc, from_ac);
+        }
+        flat
+    }
+
+    /// Global sections lost to twisting, relative to the flat reference
+    ///
+    /// Equals the number of loop classes of the reference's H¹ that the
+    /// twist turns into obstructions.
+    pub fn paradox_dimension(&self) -> usize {
+        self.reference().cohomology().h0.saturating_sub(self.cohomology().h0)
+    }
+
+    /// Loops of a cycle basis around which beliefs cannot be reconciled
+    pub fn paradoxes(&self) -> Vec<HomologicalHole> {
+        self.cycle_basis()
+            .into_iter()
+            .filter(|cycle| self.restrict_to(cycle).paradox_dimension() > 0)
+            .map(|cycle| {
+                let mut hole = HomologicalHole::new(1, vec![cycle]);
+                hole.gap_description =
+                    "Locally consistent beliefs that cannot be reconciled around this loop".to_string();
+                hole
+            })
+            .collect()
+    }
+
+    /// Fail with a non-contractible loop if any paradox exists
+    pub fn check_consistency(&self) -> Result<(), TopologyError> {
+        match self.paradox_dimension() {
+            0 => Ok(()),
+            depth => Err(TopologyError::NonContractibleLoop { depth }),
+        }
+    }
+
+    /// Per-relation disagreement ‖F_v x_v - F_u x_u‖ of a belief assignment
+    pub fn disagreement(&self, beliefs: &BTreeMap<usize, DVector<f64>>) -> Vec<((usize, usize), f64)> {
+        self.edges.iter()
+            .filter_map(|edge| {
+                let (u, v) = edge.vertices;
+                let (xu, xv) = (beliefs.get(&u)?, beliefs.get(&v)?);
+                if xu.len() != edge.from_source.ncols() || xv.len() != edge.from_target.ncols() {
+                    return None;
+                }
+                let residual = &edge.from_target * xv - &edge.from_source * xu;
+                Some((edge.vertices, residual.norm()))
+            })
+            .collect()
+    }
+
+    /// Closest globally consistent assignment (orthogonal projection onto H⁰)
+    pub fn nearest_global_section(
+        &self,
+        beliefs: &BTreeMap<usize, DVector<f64>>,
+    ) -> Result<BTreeMap<usize, DVector<f64>>, TopologyError> {
+        let x = self.flatten(beliefs)?;
+        let basis = self.global_sections();
+        let projected = &basis * (basis.transpose() * x);
+        Ok(self.unflatten(&projected))
+    }
+
+    /// Heat flow x ← x - α·Lx, which relaxes beliefs toward consistency
+    pub fn diffuse(
+        &self,
+        beliefs: &BTreeMap<usize, DVector<f64>>,
+        alpha: f64,
+        steps: usize,
+    ) -> Result<BTreeMap<usize, DVector<f64>>, TopologyError> {
+        let laplacian = self.laplacian();
+        let mut x = self.flatten(beliefs)?;
+        for _ in 0..steps {
+            x = &x - alpha * (&laplacian * &x);
+        }
+        Ok(self.unflatten(&x))
+    }
+
+    fn flatten(&self, beliefs: &BTreeMap<usize, DVector<f64>>) -> Result<DVector<f64>, TopologyError> {
+        let mut x = DVector::zeros(self.c0_dimension());
+        let mut offset = 0;
+        for (id, &dim) in &self.stalks {
+            let value = beliefs.get(id).ok_or_else(|| TopologyError::not_found(format!("belief for idea {}", id)))?;
+            if value.len() != dim {
+                return Err(TopologyError::DimensionMismatch { expected: dim, found: value.len() });
+            }
+            x.rows_mut(offset, dim).copy_from(value);
+            offset += dim;
+        }
+        Ok(x)
+    }
+
+    fn unflatten(&self, x: &DVector<f64>) -> BTreeMap<usize, DVector<f64>> {
+        let mut beliefs = BTreeMap::new();
+        let mut offset = 0;
+        for (&id, &dim) in &self.stalks {
+            beliefs.insert(id, x.rows(offset, dim).into_owned());
+            offset += dim;
+        }
+        beliefs
+    }
+
+    /// The sub-sheaf on a closed walk of ideas
+    fn restrict_to(&self, cycle: &[usize]) -> Self {
+        let on_cycle: BTreeSet<(usize, usize)> = (0..cycle.len())
+            .map(|i| {
+                let (a, b) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+                (a.min(b), a.max(b))
+            })
+            .collect();
+        Self {
+            stalks: cycle.iter().map(|v| (*v, self.stalks[v])).collect(),
+            edges: self.edges.iter().filter(|e| on_cycle.contains(&e.vertices)).cloned().collect(),
+            triangles: self.triangles.iter()
+                .filter(|t| triangle_edges(t.vertices).iter().all(|e| on_cycle.contains(e)))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Fundamental cycles of a BFS spanning forest
+    fn cycle_basis(&self) -> Vec<Vec<usize>> {
+        let vertices: Vec<usize> = self.stalks.keys().copied().collect();
+        let edges: Vec<(usize, usize)> = self.edges.iter().map(|e| e.vertices).collect();
+        cycle_basis(&vertices, &edges)
+    }
+}
+
+/// Edges of a sorted triangle: (a, b), (a, c), (b, c)
+fn triangle_edges((a, b, c): (usize, usize, usize)) -> [(usize, usize); 3] {
+    [(a, b), (a, c), (b, c)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex::ComplexBuilder;
+
+    fn triangle() -> ConceptComplex {
+        let mut builder = ComplexBuilder::new();
+        let a = builder.add_idea("Thesis");
+        let b = builder.add_idea("Antithesis");
+        let c = builder.add_idea("Synthesis");
+        builder.connect(a, b).connect(b, c).connect(c, a);
+        builder.build()
+    }
+
+    fn scalar(x: f64) -> DMatrix<f64> {
+        DMatrix::from_element(1, 1, x)
+    }
+
+    #[test]
+    fn test_constant_sheaf_cohomology() {
+        let sheaf = CellularSheaf::constant(&triangle(), 2);
+        assert_eq!(sheaf.cohomology(), SheafCohomology { h0: 2, h1: 2, h2: 0 });
+        assert_eq!(sheaf.paradox_dimension(), 0);
+        assert!(sheaf.check_consistency().is_ok());
+        assert!(sheaf.paradoxes().is_empty());
+    }
+
+    #[test]
+    fn test_filled_triangle_has_no_h1() {
+        let mut builder = ComplexBuilder::new();
+        let a = builder.add_idea("Thesis");
+        let b = builder.add_idea("Antithesis");
+        let c = builder.add_idea("Synthesis");
+        builder.add_triangle(a, b, c);
+        let sheaf = CellularSheaf::constant(&builder.build(), 2);
+        assert_eq!(sheaf.triangles.len(), 1);
+        assert_eq!(sheaf.cohomology(), SheafCohomology { h0: 2, h1: 0, h2: 0 });
+
+        // Twisting an edge unfills the triangle, and the loop becomes a paradox
+        let mut twisted = CellularSheaf::constant(&triangle(), 1);
+        twisted.add_triangle(0, 1, 2, scalar(1.0), scalar(1.0), scalar(1.0)).unwrap();
+        twisted.set_restriction(2, 0, scalar(-1.0), scalar(1.0)).unwrap();
+        assert!(twisted.triangles.is_empty());
+        assert_eq!(twisted.paradox_dimension(), 1);
+    }
+
+    #[test]
+    fn test_commuting_triangle_is_a_boundary() {
+        // B holds twice A's belief, C three times B's, and C six times A's
+        let mut sheaf = CellularSheaf::new();
+        for id in 0..3 {
+            sheaf.add_vertex(id, 1);
+        }
+        sheaf.add_edge(0, 1, scalar(2.0), scalar(1.0)).unwrap();
+        sheaf.add_edge(1, 2, scalar(3.0), scalar(1.0)).unwrap();
+        sheaf.add_edge(0, 2, scalar(6.0), scalar(1.0)).unwrap();
+        assert_eq!(sheaf.cohomology(), SheafCohomology { h0: 1, h1: 1, h2: 0 });
+        assert!(sheaf.paradoxes().is_empty());
+
+        sheaf.add_triangle(0, 1, 2, scalar(3.0), scalar(1.0), scalar(1.0)).unwrap();
+        assert_eq!(sheaf.cohomology(), SheafCohomology { h0: 1, h1: 0, h2: 0 });
+        assert!((sheaf.coboundary_1() * sheaf.coboundary()).norm() < 1e-12);
+
+        // Maps that do not commute cannot fill the triangle
+        let result = sheaf.add_triangle(0, 1, 2, scalar(1.0), scalar(1.0), scalar(1.0));
+        assert!(matches!(result, Err(TopologyError::InvalidStructure { .. })));
+    }
+
+    #[test]
+    fn test_twisted_loop_is_a_paradox() {
+        // A agrees with B, B agrees with C, but C asserts the opposite of A
+        let mut sheaf = CellularSheaf::constant(&triangle(), 1);
+        sheaf.set_restriction(2, 0, scalar(-1.0), scalar(1.0)).unwrap();
+
+        // No consistent assignment survives, and the loop class is spent on it
+        assert_eq!(sheaf.cohomology(), SheafCohomology { h0: 0, h1: 0, h2: 0 });
+        assert_eq!(sheaf.reference().cohomology(), SheafCohomology { h0: 1, h1: 1, h2: 0 });
+        assert_eq!(sheaf.paradox_dimension(), 1);
+        assert!(matches!(
+            sheaf.check_consistency(),
+            Err(TopologyError::NonContractibleLoop { depth: 1 })
+        ));
+
+        let paradoxes = sheaf.paradoxes();
+        assert_eq!(paradoxes.len(), 1);
+        assert_eq!(paradoxes[0].representatives[0].len(), 3);
+    }
+
+    #[test]
+    fn test_nearest_global_section_averages() {
+        let sheaf = CellularSheaf::constant(&triangle(), 1);
+        let beliefs: BTreeMap<usize, DVector<f64>> = [(0, 1.0), (1, 2.0), (2, 6.0)]
+            .into_iter()
+            .map(|(id, x)| (id, DVector::from_vec(vec![x])))
+            .collect();
+
+        let consistent = sheaf.nearest_global_section(&beliefs).unwrap();
+        for value in consistent.values() {
+            assert!((value[0] - 3.0).abs() < 1e-9);
+        }
+        assert!(sheaf.disagreement(&consistent).iter().all(|(_, d)| *d < 1e-9));
+
+        let relaxed = sheaf.diffuse(&beliefs, 0.1, 500).unwrap();
+        assert!((relaxed[&0][0] - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sections_match_h0_near_a_twist() {
+        // A twist a hair away from flat is still a twist for both computations
+        for twist in [1.0, 1.0 - 1e-6, -1.0] {
+            let mut sheaf = CellularSheaf::constant(&triangle(), 1);
+            sheaf.set_restriction(2, 0, scalar(twist), scalar(1.0)).unwrap();
+            assert_eq!(sheaf.global_sections().ncols(), sheaf.cohomology().h0, "twist {}", twist);
+        }
+        let empty = CellularSheaf::new();
+        assert_eq!(empty.cohomology(), SheafCohomology { h0: 0, h1: 0, h2: 0 });
+    }
+
+    #[test]
+    fn test_restriction_dimension_mismatch() {
+        let mut sheaf = CellularSheaf::constant(&triangle(), 2);
+        let result = sheaf.set_restriction(0, 1, DMatrix::identity(2, 3), DMatrix::identity(2, 2));
+        assert!(matches!(result, Err(TopologyError::DimensionMismatch { expected: 2, found: 3 })));
+        assert!(matches!(sheaf.set_restriction(0, 7, DMatrix::identity(2, 2), DMatrix::identity(2, 2)), Err(TopologyError::NotFound { .. })));
+    }
+}
@@ -5,8 +5,9 @@
 //!
 //! The "Unthought" is a missing face - a homological hole.
 
+use crate::morse::{MorseReduction, MORSE_THRESHOLD};
 use crate::types::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
 /// A single idea - vertex in the concept complex
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -14,6 +15,8 @@ pub struct Idea {
     pub id: usize,
     pub name: String,
     pub embedding: Vec<i64>, // Discretized position for hashing
+    /// Data points this idea stands for (e.g. a Mapper cluster)
+    pub members: Vec<usize>,
 }
 
 impl Idea {
@@ -22,6 +25,7 @@ impl Idea {
             id,
             name: name.into(),
             embedding: vec![id as i64],
+            members: Vec::new(),
         }
     }
 
@@ -29,6 +33,12 @@ impl Idea {
         self.embedding = embedding;
         self
     }
+
+    /// Attach the data points this idea summarizes
+    pub fn with_members(mut self, members: Vec<usize>) -> Self {
+        self.members = members;
+        self
+    }
 }
 
 /// A relation between ideas - a face (simplex) in the complex
@@ -53,6 +63,12 @@ impl Relation {
         }
     }
 
+    /// Set the strength of the relation
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
     /// Create an edge (1-simplex) between two ideas
     pub fn edge(a: usize, b: usize) -> Self {
         Self::new(vec![a, b])
@@ -166,100 +182,27 @@ impl ConceptComplex {
     }
 
     /// Compute homology groups and identify holes
+    ///
+    /// Exact over GF(2). Large complexes are first collapsed to their Morse
+    /// complex, which has the same homology with far fewer cells.
     pub fn calculate_homology(&mut self) -> &Vec<usize> {
         if !self.betti_numbers.is_empty() {
             return &self.betti_numbers;
         }
 
-        // Build chain complexes for each dimension
-        let mut betti = Vec::new();
-
-        // β₀: Number of connected components
-        betti.push(self.count_connected_components());
-
-        // β₁: Number of independent 1-cycles (loops that don't bound)
-        betti.push(self.count_one_cycles());
-
-        // β₂: Number of independent 2-cycles (voids)
-        if self.max_dimension >= 2 {
-            betti.push(self.count_two_cycles());
-        }
+        let closure = self.chain_complex(self.max_dimension);
+        let mut betti = if closure.len() > MORSE_THRESHOLD {
+            MorseReduction::of(&closure).betti_numbers()
+        } else {
+            closure.betti_numbers()
+        };
 
+        // β₀ and β₁ are always reported, higher ones up to the top dimension
+        betti.resize(self.max_dimension.max(1) + 1, 0);
         self.betti_numbers = betti;
         &self.betti_numbers
     }
 
-    /// Count connected components (β₀)
-    fn count_connected_components(&self) -> usize {
-        if self.vertices.is_empty() {
-            return 0;
-        }
-
-        let mut visited: HashSet<usize> = HashSet::new();
-        let mut components = 0;
-
-        for &id in self.vertices.keys() {
-            if !visited.contains(&id) {
-                components += 1;
-                self.dfs_visit(id, &mut visited);
-            }
-        }
-
-        components
-    }
-
-    /// DFS to find all connected vertices
-    fn dfs_visit(&self, start: usize, visited: &mut HashSet<usize>) {
-        let mut stack = vec![start];
-
-        while let Some(current) = stack.pop() {
-            if visited.contains(&current) {
-                continue;
-            }
-            visited.insert(current);
-
-            // Find neighbors via edges
-            for face in &self.faces {
-                if face.dimension == 1 && face.vertices.contains(&current) {
-                    for &v in &face.vertices {
-                        if !visited.contains(&v) {
-                            stack.push(v);
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    /// Count independent 1-cycles (β₁)
-    fn count_one_cycles(&self) -> usize {
-        let v = self.vertices.len();
-        let e = self.faces.iter().filter(|f| f.dimension == 1).count();
-        let c = self.count_connected_components();
-
-        // Euler characteristic for 1-skeleton: χ = V - E
-        // β₁ = E - V + C (for connected graph: β₁ = E - V + 1)
-        if e >= v {
-            e - v + c
-        } else {
-            0
-        }
-    }
-
-    /// Count independent 2-cycles (β₂)
-    fn count_two_cycles(&self) -> usize {
-        let e = self.faces.iter().filter(|f| f.dimension == 1).count();
-        let t = self.faces.iter().filter(|f| f.dimension == 2).count();
-
-        // Simplified: if we have triangles but they don't fill all possible voids
-        // This is a simplification - proper computation requires boundary matrices
-        if t > 0 && e > 3 * t {
-            1 // At least one void exists
-        } else {
-            0
-        }
-    }
-
     /// Identify the "gap" - a homological hole that invites bridging
     pub fn identify_gap(&self) -> Option<HomologicalHole> {
         // Look for H₁ holes first (most common cognitive gaps)
@@ -280,25 +223,26 @@ impl ConceptComplex {
         None
     }
 
-    /// Find representatives of 1-cycles
+    /// Find a representative 1-cycle that bounds nothing, as a closed walk
     fn find_cycle_representatives(&self) -> Vec<Vec<usize>> {
-        // Simplified: find a cycle in the edge graph
-        let edges: Vec<_> = self.faces.iter()
-            .filter(|f| f.dimension == 1)
-            .collect();
+        let closure = self.chain_complex(2);
+        let cells_of = |dimension: usize| closure.cells.iter().filter(move |c| c.dimension() == dimension);
+        let vertices: Vec<usize> = cells_of(0).map(|c| c.vertices[0]).collect();
+        let edges: Vec<(usize, usize)> = cells_of(1).map(|c| (c.vertices[0], c.vertices[1])).collect();
 
-        if edges.len() < 3 {
-            return vec![];
-        }
-
-        // Try to find a simple cycle using the first few edges
-        // Full implementation would use cycle basis algorithm
-        vec![edges.iter()
-            .take(3)
-            .flat_map(|e| e.vertices.clone())
-            .collect::<HashSet<_>>()
+        cycle_basis(&vertices, &edges)
+            .into_iter()
+            .find(|walk| {
+                let chain: Vec<usize> = (0..walk.len())
+                    .filter_map(|i| {
+                        let (a, b) = (walk[i], walk[(i + 1) % walk.len()]);
+                        closure.find(&[a.min(b), a.max(b)])
+                    })
+                    .collect();
+                !closure.is_boundary(&chain)
+            })
             .into_iter()
-            .collect()]
+            .collect()
     }
 
     /// Bridge a gap by adding a new relation
@@ -342,6 +286,56 @@ impl Default for ConceptComplex {
     }
 }
 
+/// Fundamental cycles of a BFS spanning forest, as closed vertex walks
+/// (first vertex not repeated)
+pub(crate) fn cycle_basis(vertices: &[usize], edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut adjacency: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for &(u, v) in edges {
+        adjacency.entry(u).or_default().push(v);
+        adjacency.entry(v).or_default().push(u);
+    }
+
+    let mut parent: BTreeMap<usize, usize> = BTreeMap::new();
+    let mut depth: BTreeMap<usize, usize> = BTreeMap::new();
+    for &root in vertices {
+        if depth.contains_key(&root) {
+            continue;
+        }
+        depth.insert(root, 0);
+        let mut queue = VecDeque::from([root]);
+        while let Some(u) = queue.pop_front() {
+            for &w in adjacency.get(&u).into_iter().flatten() {
+                if !depth.contains_key(&w) {
+                    depth.insert(w, depth[&u] + 1);
+                    parent.insert(w, u);
+                    queue.push_back(w);
+                }
+            }
+        }
+    }
+
+    let is_tree_edge = |a: usize, b: usize| parent.get(&a) == Some(&b) || parent.get(&b) == Some(&a);
+    edges.iter()
+        .filter(|&&(a, b)| !is_tree_edge(a, b))
+        .map(|&(mut a, mut b)| {
+            // Walk both ends up to their common ancestor
+            let (mut left, mut right) = (vec![a], vec![b]);
+            while a != b {
+                if depth[&a] >= depth[&b] {
+                    a = parent[&a];
+                    left.push(a);
+                } else {
+                    b = parent[&b];
+                    right.push(b);
+                }
+            }
+            right.pop();
+            left.extend(right.into_iter().rev());
+            left
+        })
+        .collect()
+}
+
 /// Builder for creating concept complexes
 pub struct ComplexBuilder {
     complex: ConceptComplex,
@@ -450,9 +444,9 @@ mod tests {
         builder.add_triangle(a, b, c);
 
         let complex = builder.build();
-        // Triangle should "fill" the hole, but our simplified computation
-        // may not capture this perfectly
+        // The triangle fills the hole
         assert_eq!(complex.betti_numbers[0], 1);
+        assert_eq!(complex.betti_numbers[1], 0);
     }
 
     #[test]
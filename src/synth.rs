@@ -0,0 +1,454 @@
+//! Offline Synthesis - rendering the transformation space to audio.
+//!
+//! The sequencer's steps are rendered offline by a small additive/FM voice:
+//!
+//! - timbre: a golden partial series (`HarmonicSignature::overtones`),
+//!   frequency-modulated at the golden ratio
+//! - morph: the step's `TimbralMorph::interpolation_path` moves the timbre
+//!   over time. Height darkens or brightens the partials, and the dip
+//!   through the sphere's interior deepens the modulation.
+//! - envelope: each cell's pulse sets the note's accent and decay
+//!
+//! Rendering is deterministic and offline. The same kernel, walk and
+//! settings always give the same WAV bytes, so renders are checked by
+//! checksum.
+
+use crate::gravity::HarmonicSignature;
+use crate::kernel::{GenerativeKernel, PHI};
+use crate::midi::{Sequencer, Walk};
+use crate::tuning::{Tuning, MIDDLE_C};
+use crate::types::*;
+use nalgebra::Vector3;
+use std::f64::consts::PI;
+use std::path::Path as FilePath;
+
+/// Default sample rate (Hz)
+pub const SAMPLE_RATE: u32 = 44_100;
+
+/// Attack time of every note (s)
+const ATTACK: f64 = 0.005;
+
+/// Fade at the end of every note, against clicks (s)
+const RELEASE: f64 = 0.005;
+
+/// PCM sample width
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitDepth {
+    /// 16-bit signed
+    Sixteen,
+    /// 24-bit signed
+    TwentyFour,
+}
+
+impl BitDepth {
+    /// Bits per sample
+    pub fn bits(&self) -> u16 {
+        match self {
+            Self::Sixteen => 16,
+            Self::TwentyFour => 24,
+        }
+    }
+
+    fn full_scale(&self) -> f64 {
+        match self {
+            Self::Sixteen => 32_767.0,
+            Self::TwentyFour => 8_388_607.0,
+        }
+    }
+}
+
+/// 64-bit FNV-1a hash
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// A mono PCM recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct WavFile {
+    /// Samples per second
+    pub sample_rate: u32,
+    /// Sample width when written
+    pub bit_depth: BitDepth,
+    /// Samples in [-1, 1]
+    pub samples: Vec<f64>,
+}
+
+impl WavFile {
+    /// A recording from samples (clamped to [-1, 1] when written)
+    pub fn new(sample_rate: u32, bit_depth: BitDepth, samples: Vec<f64>) -> Self {
+        Self { sample_rate: sample_rate.max(1), bit_depth, samples }
+    }
+
+    /// Length in seconds
+    pub fn duration_seconds(&self) -> f64 {
+        self.samples.len() as f64 / self.sample_rate as f64
+    }
+
+    /// Largest absolute sample
+    pub fn peak(&self) -> f64 {
+        self.samples.iter().fold(0.0, |m, s| m.max(s.abs()))
+    }
+
+    /// Encode as a RIFF/WAVE file
+    ///
+    /// Fails if the data is too long for the 32-bit RIFF size fields.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, TopologyError> {
+        let width = (self.bit_depth.bits() / 8) as u32;
+        let data_length = u32::try_from(self.samples.len())
+            .ok()
+            .and_then(|n| n.checked_mul(width))
+            .filter(|&n| n <= u32::MAX - 36)
+            .ok_or_else(|| {
+                TopologyError::malformed("wav", format!("{} samples overflow the RIFF size", self.samples.len()))
+            })?;
+
+        let mut out = Vec::with_capacity(44 + data_length as usize);
+        out.extend(b"RIFF");
+        out.extend((36 + data_length).to_le_bytes());
+        out.extend(b"WAVE");
+        out.extend(b"fmt ");
+        out.extend(16u32.to_le_bytes());
+        out.extend(1u16.to_le_bytes()); // PCM
+        out.extend(1u16.to_le_bytes()); // mono
+        out.extend(self.sample_rate.to_le_bytes());
+        out.extend((self.sample_rate * width).to_le_bytes());
+        out.extend((width as u16).to_le_bytes());
+        out.extend(self.bit_depth.bits().to_le_bytes());
+        out.extend(b"data");
+        out.extend(data_length.to_le_bytes());
+
+        let scale = self.bit_depth.full_scale();
+        for sample in &self.samples {
+            let value = (sample.clamp(-1.0, 1.0) * scale).round() as i32;
+            out.extend(&value.to_le_bytes()[..width as usize]);
+        }
+        Ok(out)
+    }
+
+    /// Write a `.wav` file
+    pub fn save(&self, path: impl AsRef<FilePath>) -> std::io::Result<()> {
+        let bytes = self.to_bytes().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        std::fs::write(path, bytes)
+    }
+
+    /// 64-bit FNV-1a hash of the encoded file
+    pub fn checksum(&self) -> Result<u64, TopologyError> {
+        Ok(fnv1a(&self.to_bytes()?))
+    }
+
+    /// Decode a mono 16- or 24-bit PCM file
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TopologyError> {
+        if bytes.len() < 12 || &bytes[..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err(TopologyError::malformed("wav", "missing RIFF/WAVE header"));
+        }
+
+        let mut format = None;
+        let mut data = None;
+        let mut at = 12;
+        while at + 8 <= bytes.len() {
+            let id = &bytes[at..at + 4];
+            let length = u32::from_le_bytes([bytes[at + 4], bytes[at + 5], bytes[at + 6], bytes[at + 7]]) as usize;
+            let body = bytes.get(at + 8..at + 8 + length).ok_or_else(|| TopologyError::malformed("wav", "truncated chunk"))?;
+            match id {
+                b"fmt " if length >= 16 => format = Some(body),
+                b"data" => data = Some(body),
+                _ => {}
+            }
+            at += 8 + length + length % 2;
+        }
+
+        let format = format.ok_or_else(|| TopologyError::malformed("wav", "no fmt chunk"))?;
+        let data = data.ok_or_else(|| TopologyError::malformed("wav", "no data chunk"))?;
+        let field = |i: usize| u16::from_le_bytes([format[i], format[i + 1]]);
+        if field(0) != 1 || field(2) != 1 {
+            return Err(TopologyError::malformed("wav", "only mono PCM is supported"));
+        }
+        let sample_rate = u32::from_le_bytes([format[4], format[5], format[6], format[7]]);
+        let bit_depth = match field(14) {
+            16 => BitDepth::Sixteen,
+            24 => BitDepth::TwentyFour,
+            bits => return Err(TopologyError::malformed("wav", format!("{}-bit samples are not supported", bits))),
+        };
+
+        let width = (bit_depth.bits() / 8) as usize;
+        let scale = bit_depth.full_scale();
+        let samples = data.chunks_exact(width)
+            .map(|chunk| {
+                // Sign-extend from the top byte
+                let mut word = [if chunk[width - 1] & 0x80 != 0 { 0xFF } else { 0 }; 4];
+                word[..width].copy_from_slice(chunk);
+                i32::from_le_bytes(word) as f64 / scale
+            })
+            .collect();
+        Ok(Self::new(sample_rate, bit_depth, samples))
+    }
+}
+
+/// An additive voice with golden-ratio FM
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timbre {
+    /// Amplitude of harmonic k+1
+    pub partials: Vec<f64>,
+    /// FM depth (radians of phase deviation)
+    pub modulation_index: f64,
+    /// Modulator frequency over carrier frequency
+    pub modulation_ratio: f64,
+}
+
+impl Timbre {
+    /// The golden series 1, φ⁻¹, φ⁻², … over seven partials, lightly modulated
+    pub fn golden() -> Self {
+        Self::from_signature(&HarmonicSignature::golden())
+    }
+
+    /// Partials from a signature's overtones
+    pub fn from_signature(signature: &HarmonicSignature) -> Self {
+        Self { partials: signature.overtones.clone(), modulation_index: 0.5, modulation_ratio: PHI }
+    }
+
+    /// The timbre at a point of a morph path
+    ///
+    /// Height z ∈ [-1, 1] sets brightness: at the top the partials are as
+    /// given, at the bottom each is cubed. Points inside the sphere
+    /// deepen the modulation by up to 5× at the centre.
+    pub fn at(&self, point: &Vector3<f64>) -> Self {
+        let brightness = (point.z.clamp(-1.0, 1.0) + 1.0) / 2.0;
+        let exponent = 1.0 + 2.0 * (1.0 - brightness);
+        let depth = 1.0 + 4.0 * (1.0 - point.norm().min(1.0));
+        Self {
+            partials: self.partials.iter().map(|a| a.abs().powf(exponent)).collect(),
+            modulation_index: self.modulation_index * depth,
+            modulation_ratio: self.modulation_ratio,
+        }
+    }
+
+    /// One sample of a note at `frequency`, `t` seconds after its onset
+    ///
+    /// Normalized so the partial amplitudes sum to 1.
+    pub fn sample(&self, frequency: f64, t: f64) -> f64 {
+        let total: f64 = self.partials.iter().map(|a| a.abs()).sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+        let modulator = self.modulation_index * (2.0 * PI * self.modulation_ratio * frequency * t).sin();
+        let sum: f64 = self.partials.iter().enumerate()
+            .map(|(k, a)| a * (2.0 * PI * (k + 1) as f64 * frequency * t + modulator).sin())
+            .sum();
+        sum / total
+    }
+}
+
+/// Point along a path at fraction `u` ∈ [0, 1], piecewise linear
+fn along_path(path: &[Vector3<f64>], u: f64) -> Vector3<f64> {
+    match path.len() {
+        0 => Vector3::z(),
+        1 => path[0],
+        n => {
+            let position = u.clamp(0.0, 1.0) * (n - 1) as f64;
+            let k = (position.floor() as usize).min(n - 2);
+            path[k].lerp(&path[k + 1], position - k as f64)
+        }
+    }
+}
+
+/// Envelope level `t` seconds into a note of length `duration`
+///
+/// The pulse is the accent (peak level). Decay is exponential, with a time
+/// constant of half the note times the pulse, so accented notes ring longer.
+pub fn envelope(pulse: f64, t: f64, duration: f64) -> f64 {
+    if t < 0.0 || t >= duration {
+        return 0.0;
+    }
+    let attack = (t / ATTACK).min(1.0);
+    let release = ((duration - t) / RELEASE).min(1.0);
+    let decay = (-t / (0.5 * duration * pulse.max(1e-3))).exp();
+    pulse * attack * release * decay
+}
+
+/// Renders kernel walks to audio
+#[derive(Debug, Clone)]
+pub struct Synthesizer<'a> {
+    /// Supplies the steps (pitches, pulses, morphs) and tempo
+    pub sequencer: Sequencer<'a>,
+    /// Base timbre, reshaped along each morph
+    pub timbre: Timbre,
+    /// Samples per second
+    pub sample_rate: u32,
+    /// Output sample width
+    pub bit_depth: BitDepth,
+    /// Output gain
+    pub gain: f64,
+    /// Tuning of the MIDI keys
+    pub tuning: Tuning,
+    /// Frequency of middle C (key 60)
+    pub reference: f64,
+}
+
+impl<'a> Synthesizer<'a> {
+    /// A golden voice at 44.1 kHz, 16-bit
+    pub fn new(kernel: &'a GenerativeKernel) -> Self {
+        Self {
+            sequencer: Sequencer::new(kernel),
+            timbre: Timbre::golden(),
+            sample_rate: SAMPLE_RATE,
+            bit_depth: BitDepth::Sixteen,
+            gain: 0.8,
+            tuning: Tuning::twelve_tet(),
+            reference: MIDDLE_C,
+        }
+    }
+
+    /// Set the sample rate
+    pub fn with_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate.max(1);
+        self
+    }
+
+    /// Set the output sample width
+    pub fn with_bit_depth(mut self, bit_depth: BitDepth) -> Self {
+        self.bit_depth = bit_depth;
+        self
+    }
+
+    /// Set the base timbre
+    pub fn with_timbre(mut self, timbre: Timbre) -> Self {
+        self.timbre = timbre;
+        self
+    }
+
+    /// Set the tuning and the frequency of middle C
+    pub fn with_tuning(mut self, tuning: Tuning, reference: f64) -> Self {
+        self.tuning = tuning;
+        self.reference = reference;
+        self
+    }
+
+    /// Set the sequencer (register, tempo, …)
+    pub fn with_sequencer(mut self, sequencer: Sequencer<'a>) -> Self {
+        self.sequencer = sequencer;
+        self
+    }
+
+    /// Render a walk to samples
+    pub fn render_samples(&self, walk: &Walk) -> Result<Vec<f64>, TopologyError> {
+        let seconds_per_beat = 60.0 / self.sequencer.tempo_bpm;
+        let rate = self.sample_rate as f64;
+        let mut samples = Vec::new();
+
+        for step in self.sequencer.steps(walk)? {
+            let step_length = step.beats() * seconds_per_beat;
+            let mut onset = 0.0;
+            for (&key, &pulse) in step.keys.iter().zip(&step.pulses) {
+                let duration = pulse * seconds_per_beat;
+                let frequency = self.tuning.key_frequency(key, self.reference);
+                let count = (duration * rate).round() as usize;
+                for n in 0..count {
+                    let t = n as f64 / rate;
+                    let point = along_path(&step.morph.interpolation_path, (onset + t) / step_length);
+                    let voice = self.timbre.at(&point).sample(frequency, t);
+                    samples.push(self.gain * envelope(pulse, t, duration) * voice);
+                }
+                onset += duration;
+            }
+        }
+        Ok(samples)
+    }
+
+    /// Render a walk to a WAV recording
+    pub fn render(&self, walk: &Walk) -> Result<WavFile, TopologyError> {
+        Ok(WavFile::new(self.sample_rate, self.bit_depth, self.render_samples(walk)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_synth(kernel: &GenerativeKernel) -> Synthesizer<'_> {
+        Synthesizer::new(kernel).with_sample_rate(8_000)
+    }
+
+    /// Hash of the samples rounded to 12 bits, coarse enough to ignore libm's last bits
+    fn quantized_checksum(wav: &WavFile) -> u64 {
+        let bytes: Vec<u8> = wav.samples.iter().flat_map(|s| ((s * 2048.0).round() as i16).to_le_bytes()).collect();
+        fnv1a(&bytes)
+    }
+
+    #[test]
+    fn test_wav_encoding_is_fixed() {
+        let wav = WavFile::new(8_000, BitDepth::Sixteen, vec![0.0, 0.5, -0.5, 1.0, -1.0, 2.0]);
+        let bytes = wav.to_bytes().unwrap();
+        assert_eq!(bytes.len(), 44 + 12);
+        assert_eq!(&bytes[44..], &[0, 0, 0x00, 0x40, 0x00, 0xC0, 0xFF, 0x7F, 0x01, 0x80, 0xFF, 0x7F]);
+        assert_eq!(wav.checksum().unwrap(), 0x4785_afea_5b68_b04c);
+    }
+
+    #[test]
+    fn test_round_trip_both_depths() {
+        let samples: Vec<f64> = (0..100).map(|n| (n as f64 * 0.37).sin() * 0.9).collect();
+        for (depth, tolerance) in [(BitDepth::Sixteen, 1.0 / 32_767.0), (BitDepth::TwentyFour, 1.0 / 8_388_607.0)] {
+            let wav = WavFile::new(22_050, depth, samples.clone());
+            let decoded = WavFile::from_bytes(&wav.to_bytes().unwrap()).unwrap();
+            assert_eq!(decoded.sample_rate, 22_050);
+            assert_eq!(decoded.bit_depth, depth);
+            assert!(decoded.samples.iter().zip(&samples).all(|(a, b)| (a - b).abs() <= tolerance));
+        }
+        assert!(matches!(WavFile::from_bytes(b"RIFF0000AVI "), Err(TopologyError::MalformedInput { .. })));
+    }
+
+    #[test]
+    fn test_render_is_reproducible() {
+        let kernel = GenerativeKernel::icosahedral();
+        let synth = test_synth(&kernel);
+        let a = synth.render(&Walk::random(3, 11)).unwrap();
+        let b = synth.render(&Walk::random(3, 11)).unwrap();
+        let c = synth.render(&Walk::random(3, 12)).unwrap();
+        assert_eq!(quantized_checksum(&a), 0x273d_7e52_95da_70c6);
+        assert_eq!(a.checksum().unwrap(), b.checksum().unwrap());
+        assert_ne!(a.checksum().unwrap(), c.checksum().unwrap());
+        // Notes open from silence and release back into it
+        assert_eq!(a.samples[0], 0.0);
+        assert!(a.samples.last().unwrap().abs() <= 0.8 / (8_000.0 * RELEASE));
+
+        // 3 steps of 1 + 2φ⁻¹ beats at 120 bpm
+        let expected = 3.0 * (1.0 + 2.0 * crate::kernel::PHI_INV) * 0.5;
+        assert!((a.duration_seconds() - expected).abs() < 3.0 / 8_000.0);
+        assert!(a.peak() > 0.1 && a.peak() <= 0.8);
+
+        let just = test_synth(&kernel).with_tuning(Tuning::just(), MIDDLE_C).render(&Walk::random(3, 11)).unwrap();
+        assert_eq!(just.samples.len(), a.samples.len());
+        assert_ne!(just.checksum().unwrap(), a.checksum().unwrap());
+
+        let deep = test_synth(&kernel).with_bit_depth(BitDepth::TwentyFour).render(&Walk::random(3, 11)).unwrap();
+        assert_eq!(deep.to_bytes().unwrap().len() - 44, 3 * a.samples.len());
+    }
+
+    #[test]
+    fn test_morph_reshapes_timbre() {
+        let timbre = Timbre::golden();
+        let top = timbre.at(&Vector3::z());
+        let bottom = timbre.at(&-Vector3::z());
+        let inside = timbre.at(&(Vector3::z() * 0.5));
+        assert!((top.partials[1] - timbre.partials[1]).abs() < 1e-12);
+        assert!((bottom.partials[1] - timbre.partials[1].powi(3)).abs() < 1e-12);
+        assert!((inside.modulation_index - 3.0 * timbre.modulation_index).abs() < 1e-12);
+
+        let path = [Vector3::x(), Vector3::y()];
+        assert!((along_path(&path, 0.5) - Vector3::new(0.5, 0.5, 0.0)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_envelope_follows_pulse() {
+        let duration = 0.5;
+        assert_eq!(envelope(1.0, 0.0, duration), 0.0);
+        assert_eq!(envelope(1.0, duration, duration), 0.0);
+        let strong = envelope(1.0, 0.1, duration);
+        let weak = envelope(crate::kernel::PHI_INV, 0.1, duration);
+        assert!(strong > weak && weak > 0.0);
+        let tet = Tuning::twelve_tet();
+        assert!((tet.key_frequency(69, MIDDLE_C) - 440.0).abs() < 1e-9);
+        assert!((tet.key_frequency(57, MIDDLE_C) - 220.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,476 @@
+//! Music Theory - pitch-class sets and neo-Riemannian moves on kernel material.
+//!
+//! Pitch classes form sets with the usual invariants: normal form, prime
+//! form (Rahn's packing), interval vector, and transposition and
+//! inversion. Every rhythmic cell's three vertices form a trichord.
+//!
+//! Major and minor triads move by the neo-Riemannian P, L and R:
+//!
+//! - P (parallel) flips the third: C ↔ c
+//! - L (leading-tone) moves the root down a semitone: C ↔ e
+//! - R (relative) moves the fifth up a tone: C ↔ a
+//!
+//! The moves are laid out on a Tonnetz, with fifths along one axis and
+//! major thirds along the other. Because the kernel's rotations permute
+//! pitch classes, some of them carry a triad onto its P, L or R neighbour.
+//! `plr_realizations` reports which.
+
+use crate::kernel::{GenerativeKernel, RhythmicCell, TransformationSpace};
+use crate::types::*;
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+
+/// Reduce an integer to a pitch class
+fn pc(value: i64) -> usize {
+    value.rem_euclid(12) as usize
+}
+
+/// A set of pitch classes (0 = C … 11 = B)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PitchClassSet {
+    classes: BTreeSet<usize>,
+}
+
+impl PitchClassSet {
+    /// A set from pitch classes (reduced mod 12)
+    pub fn new(classes: impl IntoIterator<Item = usize>) -> Self {
+        Self { classes: classes.into_iter().map(|c| c % 12).collect() }
+    }
+
+    /// Pitch classes in ascending order
+    pub fn classes(&self) -> Vec<usize> {
+        self.classes.iter().copied().collect()
+    }
+
+    /// Number of pitch classes
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    /// Check if the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    /// Check membership
+    pub fn contains(&self, class: usize) -> bool {
+        self.classes.contains(&(class % 12))
+    }
+
+    /// T_n: add n to every class
+    pub fn transpose(&self, n: i64) -> Self {
+        Self { classes: self.classes.iter().map(|&c| pc(c as i64 + n)).collect() }
+    }
+
+    /// T_nI: invert about 0, then transpose by n
+    pub fn invert(&self, n: i64) -> Self {
+        Self { classes: self.classes.iter().map(|&c| pc(n - c as i64)).collect() }
+    }
+
+    /// The most compact rotation (Rahn)
+    ///
+    /// Rotations are compared by the span from the first class to the
+    /// last, then to the second-last, and so on; then by the first class.
+    pub fn normal_form(&self) -> Vec<usize> {
+        let classes = self.classes();
+        let n = classes.len();
+        (0..n)
+            .map(|i| (0..n).map(|k| classes[(i + k) % n]).collect::<Vec<_>>())
+            .min_by_key(|rotation| (packing(rotation), rotation[0]))
+            .unwrap_or_default()
+    }
+
+    /// The more compact of the normal forms of the set and its inversion, starting at 0
+    pub fn prime_form(&self) -> Vec<usize> {
+        let zeroed = |form: Vec<usize>| -> Vec<usize> {
+            let first = form.first().copied().unwrap_or(0) as i64;
+            form.iter().map(|&c| pc(c as i64 - first)).collect()
+        };
+        let original = zeroed(self.normal_form());
+        let inverted = zeroed(self.invert(0).normal_form());
+        std::cmp::min_by_key(original, inverted, |form| (packing(form), form.clone()))
+    }
+
+    /// Counts of interval classes 1 through 6
+    pub fn interval_vector(&self) -> [usize; 6] {
+        let classes = self.classes();
+        let mut vector = [0; 6];
+        for (i, &a) in classes.iter().enumerate() {
+            for &b in &classes[i + 1..] {
+                let interval = (b - a).min(12 - (b - a));
+                vector[interval - 1] += 1;
+            }
+        }
+        vector
+    }
+
+    /// The major or minor triad these classes spell, if any
+    pub fn triad(&self) -> Option<Triad> {
+        if self.len() != 3 {
+            return None;
+        }
+        self.classes.iter().find_map(|&root| {
+            [Quality::Major, Quality::Minor].into_iter()
+                .map(|quality| Triad { root, quality })
+                .find(|triad| &triad.pitch_classes() == self)
+        })
+    }
+}
+
+/// Spans from the first class to each later one, last first
+fn packing(form: &[usize]) -> Vec<usize> {
+    let first = form.first().copied().unwrap_or(0) as i64;
+    form.iter().skip(1).rev().map(|&c| pc(c as i64 - first)).collect()
+}
+
+/// Major or minor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Quality {
+    /// Root, major third, fifth
+    Major,
+    /// Root, minor third, fifth
+    Minor,
+}
+
+/// A major or minor triad
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Triad {
+    /// Root pitch class
+    pub root: usize,
+    /// Major or minor
+    pub quality: Quality,
+}
+
+/// A neo-Riemannian move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PlrMove {
+    /// Parallel: C ↔ c
+    P,
+    /// Leading-tone exchange: C ↔ e
+    L,
+    /// Relative: C ↔ a
+    R,
+}
+
+impl PlrMove {
+    /// All three moves
+    pub const ALL: [PlrMove; 3] = [PlrMove::P, PlrMove::L, PlrMove::R];
+
+    /// Parse a word such as "PLR" (spaces ignored)
+    pub fn parse(word: &str) -> Result<Vec<PlrMove>, TopologyError> {
+        word.chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c.to_ascii_uppercase() {
+                'P' => Ok(PlrMove::P),
+                'L' => Ok(PlrMove::L),
+                'R' => Ok(PlrMove::R),
+                other => Err(TopologyError::malformed("plr", format!("'{}' is not P, L or R", other))),
+            })
+            .collect()
+    }
+}
+
+impl Triad {
+    /// A major triad
+    pub fn major(root: usize) -> Self {
+        Self { root: root % 12, quality: Quality::Major }
+    }
+
+    /// A minor triad
+    pub fn minor(root: usize) -> Self {
+        Self { root: root % 12, quality: Quality::Minor }
+    }
+
+    /// All 24 major and minor triads
+    pub fn all() -> Vec<Triad> {
+        (0..12).flat_map(|r| [Self::major(r), Self::minor(r)]).collect()
+    }
+
+    /// Root, third and fifth
+    pub fn notes(&self) -> [usize; 3] {
+        let third = match self.quality {
+            Quality::Major => 4,
+            Quality::Minor => 3,
+        };
+        [self.root, (self.root + third) % 12, (self.root + 7) % 12]
+    }
+
+    /// The triad as a pitch-class set
+    pub fn pitch_classes(&self) -> PitchClassSet {
+        PitchClassSet::new(self.notes())
+    }
+
+    /// Apply one move
+    pub fn apply(&self, step: PlrMove) -> Triad {
+        let r = self.root;
+        match (step, self.quality) {
+            (PlrMove::P, Quality::Major) => Self::minor(r),
+            (PlrMove::P, Quality::Minor) => Self::major(r),
+            (PlrMove::L, Quality::Major) => Self::minor(r + 4),
+            (PlrMove::L, Quality::Minor) => Self::major(r + 8),
+            (PlrMove::R, Quality::Major) => Self::minor(r + 9),
+            (PlrMove::R, Quality::Minor) => Self::major(r + 3),
+        }
+    }
+
+    /// Apply moves left to right
+    pub fn apply_all(&self, moves: &[PlrMove]) -> Triad {
+        moves.iter().fold(*self, |triad, &step| triad.apply(step))
+    }
+
+    /// A shortest sequence of moves to another triad
+    pub fn plr_path(&self, to: &Triad) -> Vec<PlrMove> {
+        let mut previous: Vec<(Triad, Option<(Triad, PlrMove)>)> = vec![(*self, None)];
+        let mut queue = VecDeque::from([*self]);
+        while let Some(current) = queue.pop_front() {
+            if current == *to {
+                break;
+            }
+            for step in PlrMove::ALL {
+                let next = current.apply(step);
+                if previous.iter().all(|(t, _)| *t != next) {
+                    previous.push((next, Some((current, step))));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut current = *to;
+        while let Some(&(_, Some((from, step)))) = previous.iter().find(|(t, _)| *t == current) {
+            path.push(step);
+            current = from;
+        }
+        path.reverse();
+        path
+    }
+}
+
+impl fmt::Display for Triad {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = crate::tuning::NOTE_NAMES[self.root];
+        match self.quality {
+            Quality::Major => write!(f, "{}", name),
+            Quality::Minor => write!(f, "{}", name.to_lowercase()),
+        }
+    }
+}
+
+/// One triangle of the Tonnetz
+#[derive(Debug, Clone, PartialEq)]
+pub struct TonnetzTriangle {
+    /// The triad it spells
+    pub triad: Triad,
+    /// Lattice corners as (fifths, major thirds)
+    pub corners: [(i32, i32); 3],
+}
+
+/// A window of the Tonnetz: fifths run along columns, major thirds along rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tonnetz {
+    /// Number of columns (steps of a fifth)
+    pub columns: i32,
+    /// Number of rows (steps of a major third)
+    pub rows: i32,
+}
+
+impl Tonnetz {
+    /// A window with the given size
+    pub fn new(columns: i32, rows: i32) -> Self {
+        Self { columns: columns.max(1), rows: rows.max(1) }
+    }
+
+    /// Pitch class at a lattice point
+    pub fn pitch_at(fifths: i32, thirds: i32) -> usize {
+        pc(7 * fifths as i64 + 4 * thirds as i64)
+    }
+
+    /// The lattice point of a pitch class within 4 fifths × 3 thirds
+    pub fn position(class: usize) -> (i32, i32) {
+        (0..4)
+            .flat_map(|a| (0..3).map(move |b| (a, b)))
+            .find(|&(a, b)| Self::pitch_at(a, b) == class % 12)
+            .unwrap_or((0, 0))
+    }
+
+    /// Triangles in the window: major pointing up, minor pointing down
+    pub fn triangles(&self) -> Vec<TonnetzTriangle> {
+        let mut triangles = Vec::new();
+        for b in 0..self.rows {
+            for a in 0..self.columns {
+                let up = [(a, b), (a + 1, b), (a, b + 1)];
+                let down = [(a + 1, b), (a, b + 1), (a + 1, b + 1)];
+                for corners in [up, down] {
+                    let set = PitchClassSet::new(corners.iter().map(|&(x, y)| Self::pitch_at(x, y)));
+                    if let Some(triad) = set.triad() {
+                        triangles.push(TonnetzTriangle { triad, corners });
+                    }
+                }
+            }
+        }
+        triangles
+    }
+}
+
+/// Kernel elements that carry a triad onto its P, L or R neighbour
+#[derive(Debug, Clone)]
+pub struct PlrRealization {
+    /// The move
+    pub step: PlrMove,
+    /// The triad moved
+    pub triad: Triad,
+    /// Indices of the elements g with g·triad = step(triad)
+    pub elements: Vec<usize>,
+}
+
+impl TransformationSpace {
+    /// The pitch classes of some vertices
+    ///
+    /// Vertices are matched by `PitchClass::index`; those without a pitch
+    /// class are skipped.
+    pub fn pitch_set(&self, vertices: &[usize]) -> PitchClassSet {
+        PitchClassSet::new(vertices.iter()
+            .filter_map(|&v| self.pitch_classes.iter().find(|p| p.index == v))
+            .map(|p| p.pitch))
+    }
+
+    /// A rhythmic cell's three vertices, read as a trichord
+    pub fn trichord(&self, cell: &RhythmicCell) -> PitchClassSet {
+        self.pitch_set(&cell.vertices)
+    }
+}
+
+impl GenerativeKernel {
+    /// For every triad and move, the elements realizing it
+    ///
+    /// An element realizes a move on a triad when its vertex permutation
+    /// maps the triad's three vertices onto the vertices of the moved triad.
+    /// Triads using pitches absent from the kernel are skipped.
+    pub fn plr_realizations(&self) -> Vec<PlrRealization> {
+        let space = self.generate_space();
+        let vertex_of = |class: usize| space.pitch_classes.iter().position(|p| p.pitch == class);
+        let vertices_of = |triad: &Triad| -> Option<Vec<usize>> {
+            let mut vertices: Vec<usize> = triad.notes().iter().map(|&c| vertex_of(c)).collect::<Option<_>>()?;
+            vertices.sort_unstable();
+            Some(vertices)
+        };
+        let permutations: Vec<Option<Vec<usize>>> = self.elements.iter().map(|g| self.vertex_permutation(g)).collect();
+
+        let mut report = Vec::new();
+        for triad in Triad::all() {
+            for step in PlrMove::ALL {
+                let (Some(from), Some(to)) = (vertices_of(&triad), vertices_of(&triad.apply(step))) else {
+                    continue;
+                };
+                let elements = permutations.iter().enumerate()
+                    .filter_map(|(i, p)| p.as_ref().map(|p| (i, p)))
+                    .filter(|(_, p)| {
+                        let mut image: Vec<usize> = from.iter().map(|&v| p[v]).collect();
+                        image.sort_unstable();
+                        image == to
+                    })
+                    .map(|(i, _)| i)
+                    .collect();
+                report.push(PlrRealization { step, triad, elements });
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_invariants() {
+        let a_minor = PitchClassSet::new([9, 0, 4]);
+        assert_eq!(a_minor.normal_form(), vec![9, 0, 4]);
+        assert_eq!(a_minor.prime_form(), vec![0, 3, 7]);
+        assert_eq!(PitchClassSet::new([0, 4, 7]).prime_form(), vec![0, 3, 7]);
+        assert_eq!(a_minor.interval_vector(), [0, 0, 1, 1, 1, 0]);
+
+        let diatonic = PitchClassSet::new([0, 2, 4, 5, 7, 9, 11]);
+        assert_eq!(diatonic.prime_form(), vec![0, 1, 3, 5, 6, 8, 10]);
+        assert_eq!(diatonic.interval_vector(), [2, 5, 4, 3, 6, 1]);
+
+        // Rahn and Forte disagree on 5-20; this is Rahn's form
+        assert_eq!(PitchClassSet::new([0, 1, 5, 6, 8]).prime_form(), vec![0, 1, 5, 6, 8]);
+
+        assert_eq!(a_minor.transpose(3), PitchClassSet::new([0, 3, 7]));
+        assert_eq!(PitchClassSet::new([0, 4, 7]).invert(7), PitchClassSet::new([7, 3, 0]));
+    }
+
+    #[test]
+    fn test_plr_moves() {
+        let c = Triad::major(0);
+        assert_eq!(c.apply(PlrMove::P), Triad::minor(0));
+        assert_eq!(c.apply(PlrMove::L), Triad::minor(4));
+        assert_eq!(c.apply(PlrMove::R), Triad::minor(9));
+        for triad in Triad::all() {
+            for step in PlrMove::ALL {
+                // Involutions that keep two common tones
+                assert_eq!(triad.apply(step).apply(step), triad);
+                let common = triad.notes().iter().filter(|&&n| triad.apply(step).pitch_classes().contains(n)).count();
+                assert_eq!(common, 2);
+            }
+        }
+
+        // LR walks the major triads round the circle of fifths
+        let lr = PlrMove::parse("LR").unwrap();
+        let mut seen = BTreeSet::new();
+        let mut current = c;
+        for _ in 0..12 {
+            seen.insert(current);
+            current = current.apply_all(&lr);
+        }
+        assert_eq!(current, c);
+        assert_eq!(seen.len(), 12);
+        assert!(PlrMove::parse("PXL").is_err());
+
+        let path = c.plr_path(&Triad::minor(6));
+        assert_eq!(c.apply_all(&path), Triad::minor(6));
+        assert_eq!(path.len(), 3);
+        assert_eq!(format!("{} {}", c, Triad::minor(9)), "C a");
+    }
+
+    #[test]
+    fn test_tonnetz_layout() {
+        let tonnetz = Tonnetz::new(4, 3);
+        let triangles = tonnetz.triangles();
+        assert_eq!(triangles.len(), 24);
+        let triads: BTreeSet<Triad> = triangles.iter().map(|t| t.triad).collect();
+        assert_eq!(triads.len(), 24);
+
+        // Neighbouring triangles share an edge and differ by one move
+        let c = triangles.iter().find(|t| t.triad == Triad::major(0)).unwrap();
+        for step in PlrMove::ALL {
+            let neighbour = triangles.iter().find(|t| t.triad == Triad::major(0).apply(step)).unwrap();
+            let shared = c.corners.iter()
+                .filter(|&&(a, b)| neighbour.corners.iter().any(|&(x, y)| Tonnetz::pitch_at(a, b) == Tonnetz::pitch_at(x, y)))
+                .count();
+            assert_eq!(shared, 2);
+        }
+        assert_eq!(Tonnetz::pitch_at(Tonnetz::position(11).0, Tonnetz::position(11).1), 11);
+    }
+
+    #[test]
+    fn test_kernel_trichords_and_plr_report() {
+        let kernel = GenerativeKernel::icosahedral();
+        let space = kernel.generate_space();
+        assert!(space.rhythmic_cells.iter().all(|cell| space.trichord(cell).len() == 3));
+
+        let report = kernel.plr_realizations();
+        assert_eq!(report.len(), 72);
+        assert!(report.iter().any(|r| !r.elements.is_empty()));
+
+        // A realizing element for T → M(T) has an inverse realizing M(T) → T
+        let group = kernel.group_structure().unwrap();
+        for entry in report.iter().filter(|r| !r.elements.is_empty()) {
+            let back = report.iter().find(|r| r.step == entry.step && r.triad == entry.triad.apply(entry.step)).unwrap();
+            for &g in &entry.elements {
+                assert!(back.elements.contains(&group.inverse(g)));
+            }
+        }
+    }
+}
@@ -0,0 +1,363 @@
+//! Tuning Systems - from pitch classes to Hertz.
+//!
+//! A `Tuning` is a list of ratios within a period (usually the octave 2/1).
+//! The built-in tunings are:
+//!
+//! - 12-TET and any equal division
+//! - 5-limit just intonation
+//! - Pythagorean, from stacked 3/2 fifths
+//! - golden, from stacked φ reduced into the octave
+//!
+//! Scala `.scl` files can also be read. Frequencies are measured from a
+//! reference: the frequency of degree 0 (C) at MIDI key 60.
+//!
+//! Vertices are assigned pitches geometrically. On the icosahedron,
+//! antipodal vertices are a tritone apart. Vertex 0 is C, and its five
+//! neighbours, taken in turn around it, continue the circle of fifths
+//! G D A E B.
+
+use crate::kernel::{PitchClass, Polyhedron, PHI};
+use crate::types::*;
+use nalgebra::Vector3;
+use std::fmt::Write;
+
+/// C4 in 12-TET with A4 = 440 Hz
+pub const MIDDLE_C: f64 = 261.625_565_300_598_6;
+
+/// Note names of the twelve pitch classes, from C
+pub const NOTE_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+
+/// Size of an interval in cents
+pub fn cents(ratio: f64) -> f64 {
+    1200.0 * ratio.log2()
+}
+
+/// A scale of ratios repeating at a period
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tuning {
+    /// Description
+    pub name: String,
+    /// Ratio of each degree to degree 0, ascending from 1
+    pub ratios: Vec<f64>,
+    /// Ratio at which the scale repeats (2 for the octave)
+    pub period: f64,
+}
+
+/// Where a frequency falls in a tuning
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TuningMatch {
+    /// Nearest degree within the period
+    pub degree: usize,
+    /// Periods above (or below) the reference
+    pub octave: i32,
+    /// Deviation from that degree, in cents
+    pub cents: f64,
+}
+
+impl Tuning {
+    /// A tuning from ratios (sorted, reduced into [1, period), 1 added if missing)
+    pub fn new(name: impl Into<String>, ratios: Vec<f64>, period: f64) -> Self {
+        let period = if period > 1.0 { period } else { 2.0 };
+        let mut reduced: Vec<f64> = ratios.into_iter()
+            .filter(|r| r.is_finite() && *r > 0.0)
+            .map(|r| r / period.powf((r.ln() / period.ln()).floor()))
+            .collect();
+        reduced.push(1.0);
+        reduced.sort_by(f64::total_cmp);
+        reduced.dedup_by(|a, b| (cents(*a) - cents(*b)).abs() < 1e-6);
+        Self { name: name.into(), ratios: reduced, period }
+    }
+
+    /// n equal divisions of the octave
+    pub fn equal(divisions: usize) -> Self {
+        let n = divisions.max(1);
+        Self::new(format!("{}-TET", n), (0..n).map(|k| 2f64.powf(k as f64 / n as f64)).collect(), 2.0)
+    }
+
+    /// Standard 12-tone equal temperament
+    pub fn twelve_tet() -> Self {
+        Self::equal(12)
+    }
+
+    /// 5-limit just intonation
+    pub fn just() -> Self {
+        let ratios = [
+            1.0, 16.0 / 15.0, 9.0 / 8.0, 6.0 / 5.0, 5.0 / 4.0, 4.0 / 3.0,
+            45.0 / 32.0, 3.0 / 2.0, 8.0 / 5.0, 5.0 / 3.0, 9.0 / 5.0, 15.0 / 8.0,
+        ];
+        Self::new("5-limit just intonation", ratios.to_vec(), 2.0)
+    }
+
+    /// Pythagorean: fifths from D♭ (−5) to F♯ (+6)
+    pub fn pythagorean() -> Self {
+        Self::generated("Pythagorean", 1.5, -5, 6)
+    }
+
+    /// Golden: twelve stacked φ, each reduced into the octave
+    pub fn golden() -> Self {
+        Self::generated("golden (stacked φ)", PHI, 0, 11)
+    }
+
+    /// Powers `low..=high` of a generator, reduced into the octave
+    pub fn generated(name: impl Into<String>, generator: f64, low: i32, high: i32) -> Self {
+        Self::new(name, (low..=high).map(|k| generator.powi(k)).collect(), 2.0)
+    }
+
+    /// Number of degrees per period
+    pub fn len(&self) -> usize {
+        self.ratios.len()
+    }
+
+    /// Check if the tuning has no degrees (never, once constructed)
+    pub fn is_empty(&self) -> bool {
+        self.ratios.is_empty()
+    }
+
+    /// Ratio of any degree to degree 0, crossing periods as needed
+    pub fn ratio(&self, degree: i64) -> f64 {
+        let n = self.ratios.len() as i64;
+        self.ratios[degree.rem_euclid(n) as usize] * self.period.powi(degree.div_euclid(n) as i32)
+    }
+
+    /// Frequency of a degree, where degree 0 sounds at `reference`
+    pub fn frequency(&self, degree: i64, reference: f64) -> f64 {
+        reference * self.ratio(degree)
+    }
+
+    /// Frequency of a MIDI key, with key 60 at degree 0
+    pub fn key_frequency(&self, key: u8, reference: f64) -> f64 {
+        self.frequency(key as i64 - 60, reference)
+    }
+
+    /// The nearest degree to a frequency
+    pub fn nearest(&self, frequency: f64, reference: f64) -> TuningMatch {
+        let periods = (frequency / reference).ln() / self.period.ln();
+        let octave = periods.floor() as i32;
+        let within = frequency / reference / self.period.powi(octave);
+
+        // Candidates include the next period's degree 0
+        let mut best = TuningMatch { degree: 0, octave: octave + 1, cents: cents(within / self.period) };
+        for (degree, &ratio) in self.ratios.iter().enumerate() {
+            let deviation = cents(within / ratio);
+            if deviation.abs() < best.cents.abs() {
+                best = TuningMatch { degree, octave, cents: deviation };
+            }
+        }
+        best
+    }
+
+    /// Parse a Scala `.scl` file
+    ///
+    /// Lines starting with `!` are comments. Then come a description, the
+    /// number of notes, and one pitch per line: cents if it has a `.`,
+    /// otherwise a ratio `a/b` or an integer. The last pitch is the period.
+    pub fn from_scala(input: &str) -> Result<Self, TopologyError> {
+        let mut lines = input.lines().filter(|line| !line.trim_start().starts_with('!'));
+        let name = lines.next().ok_or_else(|| TopologyError::malformed("scala", "missing description line"))?.trim().to_string();
+        let count_line = lines.next().ok_or_else(|| TopologyError::malformed("scala", "missing note count"))?;
+        let count: usize = count_line.split_whitespace().next().unwrap_or("")
+            .parse()
+            .map_err(|_| TopologyError::malformed("scala", format!("bad note count '{}'", count_line.trim())))?;
+
+        let mut pitches = Vec::with_capacity(count);
+        for line in lines.take(count) {
+            let token = line.split_whitespace().next().ok_or_else(|| TopologyError::malformed("scala", "blank pitch line"))?;
+            pitches.push(parse_scala_pitch(token)?);
+        }
+        if pitches.len() != count {
+            return Err(TopologyError::malformed("scala", format!("expected {} pitches, found {}", count, pitches.len())));
+        }
+
+        let Some(&period) = pitches.last() else {
+            return Ok(Self::new(name, vec![1.0], 2.0));
+        };
+        if period <= 1.0 {
+            return Err(TopologyError::malformed("scala", format!("period {} is not above 1/1", period)));
+        }
+        pitches.pop();
+        Ok(Self::new(name, pitches, period))
+    }
+
+    /// Write as a Scala `.scl` file (pitches in cents)
+    pub fn to_scala(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "! {}.scl", self.name.replace(char::is_whitespace, "_"));
+        let _ = writeln!(out, "{}", self.name);
+        let _ = writeln!(out, " {}", self.ratios.len());
+        for ratio in self.ratios.iter().skip(1).chain(std::iter::once(&self.period)) {
+            let _ = writeln!(out, " {:.6}", cents(*ratio));
+        }
+        out
+    }
+}
+
+/// One Scala pitch: cents (with a '.') or a ratio
+fn parse_scala_pitch(token: &str) -> Result<f64, TopologyError> {
+    let bad = || TopologyError::malformed("scala", format!("bad pitch '{}'", token));
+    let ratio = if token.contains('.') {
+        2f64.powf(token.parse::<f64>().map_err(|_| bad())? / 1200.0)
+    } else if let Some((numerator, denominator)) = token.split_once('/') {
+        let numerator: f64 = numerator.parse::<u64>().map_err(|_| bad())? as f64;
+        let denominator: f64 = denominator.parse::<u64>().map_err(|_| bad())? as f64;
+        numerator / denominator
+    } else {
+        token.parse::<u64>().map_err(|_| bad())? as f64
+    };
+    if ratio.is_finite() && ratio > 0.0 { Ok(ratio) } else { Err(bad()) }
+}
+
+impl PitchClass {
+    /// Frequency in 12-TET, in the octave from `reference` (C4)
+    pub fn frequency(&self, reference: f64) -> f64 {
+        self.frequency_in(&Tuning::twelve_tet(), reference)
+    }
+
+    /// Frequency in a given tuning, in the period from `reference`
+    ///
+    /// A 12-degree tuning plays pitch class k on degree k. Any other tuning
+    /// plays the degree nearest in cents to the 12-TET pitch, which may be
+    /// degree 0 of the next period (B in 5-TET sounds as the next C).
+    pub fn frequency_in(&self, tuning: &Tuning, reference: f64) -> f64 {
+        let pitch = self.pitch % 12;
+        if tuning.len() == 12 {
+            return tuning.frequency(pitch as i64, reference);
+        }
+        let found = tuning.nearest(reference * 2f64.powf(pitch as f64 / 12.0), reference);
+        tuning.frequency(found.octave as i64 * tuning.len() as i64 + found.degree as i64, reference)
+    }
+}
+
+/// Pitch class (0 = C … 11 = B) of each vertex
+///
+/// If all 12 vertices come in antipodal pairs, antipodes are a tritone
+/// apart. Vertex 0 is C and its neighbours, in turn around it, are
+/// G D A E B. Any other shape spreads its vertices evenly over the octave.
+pub fn vertex_pitches(geometry: &Polyhedron) -> Vec<usize> {
+    let vertices = &geometry.vertices;
+    let n = vertices.len();
+    let antipode = |i: usize| vertices.iter().position(|w| (w + vertices[i]).norm() < 1e-9);
+    let even = || (0..n).map(|i| (12 * i + n / 2) / n.max(1) % 12).collect();
+    if n != 12 || (0..n).any(|i| antipode(i).is_none()) {
+        return even();
+    }
+
+    // Neighbours of vertex 0, in angular order around its axis
+    let axis = vertices[0];
+    let mut neighbours: Vec<usize> = geometry.edges.iter()
+        .filter_map(|&[a, b]| if a == 0 { Some(b) } else if b == 0 { Some(a) } else { None })
+        .collect();
+    let Some(&first) = neighbours.first() else { return even() };
+    let e1 = (vertices[first] - axis * axis.dot(&vertices[first])).normalize();
+    let e2: Vector3<f64> = axis.cross(&e1);
+    let angle = |v: &Vector3<f64>| v.dot(&e2).atan2(v.dot(&e1)).rem_euclid(std::f64::consts::TAU);
+    neighbours.sort_by(|&a, &b| angle(&vertices[a]).total_cmp(&angle(&vertices[b])));
+
+    let mut pitches = vec![usize::MAX; n];
+    pitches[0] = 0;
+    for (k, &v) in neighbours.iter().enumerate() {
+        pitches[v] = 7 * (k + 1) % 12;
+    }
+    for i in 0..n {
+        if pitches[i] < 12 {
+            if let Some(j) = antipode(i) {
+                pitches[j] = (pitches[i] + 6) % 12;
+            }
+        }
+    }
+    if pitches.iter().any(|&p| p >= 12) {
+        return even();
+    }
+    pitches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fhp::FUNDAMENTAL_FREQ;
+    use crate::kernel::GenerativeKernel;
+
+    #[test]
+    fn test_builtin_tunings() {
+        let tet = Tuning::twelve_tet();
+        assert_eq!(tet.len(), 12);
+        assert!((tet.key_frequency(69, MIDDLE_C) - 440.0).abs() < 1e-9);
+
+        let just = Tuning::just();
+        assert!((just.ratio(7) - 1.5).abs() < 1e-12);
+        assert!((just.ratio(-5) - 0.75).abs() < 1e-12);
+
+        // The Pythagorean fifth is pure; its major third is the ditone 81/64
+        let pythagorean = Tuning::pythagorean();
+        assert_eq!(pythagorean.len(), 12);
+        assert!((pythagorean.ratio(7) - 1.5).abs() < 1e-12);
+        assert!((pythagorean.ratio(4) - 81.0 / 64.0).abs() < 1e-12);
+
+        let golden = Tuning::golden();
+        assert_eq!(golden.len(), 12);
+        assert!(golden.ratios.iter().any(|r| (r - PHI).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_scala_parsing() {
+        let scl = "! meantone.scl\n!\nQuarter-comma meantone (excerpt)\n 4\n!\n 193.157\n 5/4 major third\n 696.578\n 2/1\n";
+        let tuning = Tuning::from_scala(scl).unwrap();
+        assert_eq!(tuning.name, "Quarter-comma meantone (excerpt)");
+        assert_eq!(tuning.len(), 4);
+        assert!((tuning.ratio(2) - 1.25).abs() < 1e-12);
+        assert!((cents(tuning.ratio(1)) - 193.157).abs() < 1e-9);
+        assert_eq!(tuning.period, 2.0);
+
+        let round_trip = Tuning::from_scala(&Tuning::just().to_scala()).unwrap();
+        assert!(round_trip.ratios.iter().zip(&Tuning::just().ratios).all(|(a, b)| (cents(a / b)).abs() < 1e-5));
+
+        for bad in ["name\n3\n100.0\n", "name\nmany\n", "name\n1\n3/x\n", "name\n1\n1/2\n"] {
+            assert!(matches!(Tuning::from_scala(bad), Err(TopologyError::MalformedInput { .. })), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_vertex_pitches_are_principled() {
+        let kernel = GenerativeKernel::icosahedral();
+        let space = kernel.generate_space();
+        let vertices = &kernel.geometry.vertices;
+
+        let mut pitches: Vec<usize> = space.pitch_classes.iter().map(|p| p.pitch).collect();
+        for (i, p) in space.pitch_classes.iter().enumerate() {
+            let j = vertices.iter().position(|w| (w + vertices[i]).norm() < 1e-9).unwrap();
+            assert_eq!((p.pitch + 6) % 12, space.pitch_classes[j].pitch);
+            assert_eq!(p.name, NOTE_NAMES[p.pitch]);
+        }
+        pitches.sort_unstable();
+        assert_eq!(pitches, (0..12).collect::<Vec<_>>());
+
+        // Every neighbour of C is a fifth-chain note
+        let neighbours: Vec<usize> = kernel.geometry.edges.iter()
+            .filter(|e| e.contains(&0))
+            .map(|e| space.pitch_classes[e[0].max(e[1])].pitch)
+            .collect();
+        assert!(neighbours.iter().all(|p| [7, 2, 9, 4, 11].contains(p)));
+    }
+
+    #[test]
+    fn test_frequencies_relate_to_fhp() {
+        let kernel = GenerativeKernel::icosahedral();
+        let space = kernel.generate_space();
+        let a = space.pitch_classes.iter().find(|p| p.name == "A").unwrap();
+        assert!((a.frequency(MIDDLE_C) - 440.0).abs() < 1e-9);
+        assert!((a.frequency_in(&Tuning::just(), 264.0) - 440.0).abs() < 1e-9);
+
+        // Other tunings take the nearest degree: A (900 cents) is degree 4 of 5-TET
+        let pentatonic = Tuning::equal(5);
+        assert!((a.frequency_in(&pentatonic, MIDDLE_C) - MIDDLE_C * 2f64.powf(4.0 / 5.0)).abs() < 1e-9);
+        let b = space.pitch_classes.iter().find(|p| p.name == "B").unwrap();
+        assert!((b.frequency_in(&pentatonic, MIDDLE_C) - 2.0 * MIDDLE_C).abs() < 1e-9);
+        assert!((cents(a.frequency_in(&Tuning::equal(19), MIDDLE_C) / a.frequency(MIDDLE_C))).abs() < 1200.0 / 38.0);
+
+        // The FHP fundamental, folded down by octaves, lands near a pitch class
+        let found = Tuning::twelve_tet().nearest(FUNDAMENTAL_FREQ, MIDDLE_C);
+        assert!(found.cents.abs() <= 50.0);
+        let folded = Tuning::twelve_tet().frequency(found.degree as i64, MIDDLE_C) * 2f64.powi(found.octave);
+        assert!((cents(FUNDAMENTAL_FREQ / folded) - found.cents).abs() < 1e-6);
+        assert!(found.octave > 20);
+    }
+}
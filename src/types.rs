@@ -83,6 +83,97 @@ pub enum TopologyError {
 
     #[error("Dimension mismatch: expected {expected}, found {found}")]
     DimensionMismatch { expected: usize, found: usize },
+
+    /// Imported data could not be parsed
+    #[error("Malformed {format} input: {message}")]
+    MalformedInput {
+        /// Interchange format being read
+        format: String,
+        /// What went wrong
+        message: String,
+    },
+
+    /// Imported data was written with an incompatible `SCHEMA_VERSION`
+    #[error("Schema version mismatch: expected {expected}, found {found}")]
+    SchemaMismatch {
+        /// Version this library writes
+        expected: String,
+        /// Version found in the input
+        found: String,
+    },
+
+    /// A group or kernel failed a structural check
+    #[error("Group structure violated: {message}")]
+    GroupStructure {
+        /// What went wrong
+        message: String,
+    },
+
+    /// A sheaf, algebra or other built structure breaks its own rules
+    #[error("Invalid structure: {message}")]
+    InvalidStructure {
+        /// What went wrong
+        message: String,
+    },
+
+    /// A referenced idea, edge or agent does not exist
+    #[error("Not found: {what}")]
+    NotFound {
+        /// What was looked up
+        what: String,
+    },
+
+    /// Too few observations to estimate anything
+    #[error("Insufficient data: {message}")]
+    InsufficientData {
+        /// What was missing
+        message: String,
+    },
+
+    /// A value lies outside the range its encoding allows
+    #[error("{what} {value} is out of range (at most {max})")]
+    OutOfRange {
+        /// What the value is
+        what: String,
+        /// The value given
+        value: u64,
+        /// Largest allowed value
+        max: u64,
+    },
+
+    /// A gauge frame has no inverse
+    #[error("Singular gauge: {dimension}×{dimension} frame is not invertible")]
+    SingularGauge {
+        /// Size of the frame
+        dimension: usize,
+    },
+}
+
+impl TopologyError {
+    /// Input in some interchange format could not be parsed
+    pub fn malformed(format: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::MalformedInput { format: format.into(), message: message.into() }
+    }
+
+    /// A group or kernel invariant does not hold
+    pub fn group_structure(message: impl Into<String>) -> Self {
+        Self::GroupStructure { message: message.into() }
+    }
+
+    /// A structure built in code is inconsistent
+    pub fn invalid_structure(message: impl Into<String>) -> Self {
+        Self::InvalidStructure { message: message.into() }
+    }
+
+    /// An estimate has nothing to work from
+    pub fn insufficient_data(message: impl Into<String>) -> Self {
+        Self::InsufficientData { message: message.into() }
+    }
+
+    /// A lookup by id or index found nothing
+    pub fn not_found(what: impl Into<String>) -> Self {
+        Self::NotFound { what: what.into() }
+    }
 }
 
 /// The attention tensor - defines distance in cognitive space
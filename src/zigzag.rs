@@ -0,0 +1,433 @@
+//! Zigzag Persistence - holes across sessions that learn and forget.
+//!
+//! A session-by-session sequence of Concept Complexes K₀, K₁, ... is joined
+//! through unions, giving inclusions in both directions
+//!
+//! ```text
+//! K₀ → K₀∪K₁ ← K₁ → K₁∪K₂ ← K₂ → ...
+//! ```
+//!
+//! Ordinary persistence only ever adds relations; the backward arrows let a
+//! hole die because its relations were forgotten. Bars are read off the
+//! generalized rank invariant (the rank of lim → colim over every window of
+//! the zigzag) by inclusion-exclusion. All linear algebra is over GF(2).
+
+use crate::homology::{add_mod2, combinations, ChainComplex};
+use crate::simplex::ConceptComplex;
+use std::collections::{BTreeSet, HashMap};
+
+/// A bar of the zigzag barcode
+///
+/// Positions index the zigzag: `2i` is session i, `2i + 1` the union of
+/// sessions i and i + 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZigzagInterval {
+    /// Homological dimension
+    pub dimension: usize,
+    /// First zigzag position where the hole exists
+    pub start: usize,
+    /// Last zigzag position where the hole exists
+    pub end: usize,
+}
+
+impl ZigzagInterval {
+    /// First session containing the hole (None if it lives only in a union)
+    pub fn birth_session(&self) -> Option<usize> {
+        let first = self.start.div_ceil(2);
+        (2 * first <= self.end).then_some(first)
+    }
+
+    /// Last session containing the hole
+    pub fn last_session(&self) -> Option<usize> {
+        self.birth_session().map(|_| self.end / 2)
+    }
+
+    /// Hole exists only where two sessions were combined
+    pub fn is_transient(&self) -> bool {
+        self.birth_session().is_none()
+    }
+
+    /// Human-readable account of the hole's life
+    pub fn describe(&self, sessions: usize) -> String {
+        match (self.birth_session(), self.last_session()) {
+            (Some(born), Some(last)) if last + 1 >= sessions => {
+                format!("H{} hole born in session {} and still open", self.dimension, born)
+            }
+            (Some(born), Some(last)) => format!(
+                "H{} hole born in session {}, forgotten by session {}",
+                self.dimension, born, last + 1
+            ),
+            _ => format!(
+                "H{} hole appearing only when sessions {} and {} are combined",
+                self.dimension, self.start / 2, self.start / 2 + 1
+            ),
+        }
+    }
+}
+
+/// A time-indexed sequence of Concept Complexes
+#[derive(Debug, Clone)]
+pub struct ZigzagSequence {
+    sessions: Vec<ConceptComplex>,
+    max_dimension: usize,
+}
+
+impl ZigzagSequence {
+    /// Empty sequence tracking holes up to dimension 2
+    pub fn new() -> Self {
+        Self { sessions: Vec::new(), max_dimension: 2 }
+    }
+
+    /// Highest homological dimension to track
+    pub fn with_max_dimension(mut self, dimension: usize) -> Self {
+        self.max_dimension = dimension;
+        self
+    }
+
+    /// Append the next session's complex
+    pub fn push(&mut self, complex: ConceptComplex) -> &mut Self {
+        self.sessions.push(complex);
+        self
+    }
+
+    /// Number of sessions
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Check if there are no sessions
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Betti numbers of each session
+    pub fn betti_numbers(&self) -> Vec<Vec<usize>> {
+        let zigzag = self.zigzag();
+        (0..self.sessions.len())
+            .map(|i| (0..=self.max_dimension).map(|p| zigzag.homology(2 * i, p).basis.len()).collect())
+            .collect()
+    }
+
+    /// The zigzag barcode, sorted by dimension then start
+    pub fn barcode(&self) -> Vec<ZigzagInterval> {
+        if self.sessions.is_empty() {
+            return Vec::new();
+        }
+        let zigzag = self.zigzag();
+        let positions = zigzag.nodes.len();
+
+        let mut bars = Vec::new();
+        for dimension in 0..=self.max_dimension {
+            let homology: Vec<NodeHomology> = (0..positions).map(|j| zigzag.homology(j, dimension)).collect();
+            let arrows: Vec<Vec<Vec<usize>>> = (1..positions)
+                .step_by(2)
+                .flat_map(|o| [(o - 1, o), (o + 1, o)])
+                .map(|(e, o)| homology[e].basis.iter().map(|b| homology[o].coordinates(b)).collect())
+                .collect();
+
+            let mut ranks: HashMap<(usize, usize), i64> = HashMap::new();
+            let mut rank = |s: isize, t: usize| -> i64 {
+                if s < 0 || t >= positions {
+                    return 0;
+                }
+                *ranks
+                    .entry((s as usize, t))
+                    .or_insert_with(|| window_rank(&homology, &arrows, s as usize, t) as i64)
+            };
+
+            for start in 0..positions {
+                for end in start..positions {
+                    let s = start as isize;
+                    let multiplicity = rank(s, end) - rank(s - 1, end) - rank(s, end + 1) + rank(s - 1, end + 1);
+                    for _ in 0..multiplicity.max(0) {
+                        bars.push(ZigzagInterval { dimension, start, end });
+                    }
+                }
+            }
+        }
+        bars
+    }
+
+    /// Holes that were forgotten before the last session
+    pub fn forgotten(&self) -> Vec<ZigzagInterval> {
+        let last = self.sessions.len().saturating_sub(1);
+        self.barcode()
+            .into_iter()
+            .filter(|bar| bar.last_session().is_some_and(|s| s < last))
+            .collect()
+    }
+
+    /// Simplices of every session inside one ambient chain complex
+    fn zigzag(&self) -> Zigzag {
+        let mut ambient = ChainComplex::new();
+        let sessions: Vec<BTreeSet<usize>> = self.sessions.iter()
+            .map(|complex| {
+                let mut cells = BTreeSet::new();
+                for &id in complex.vertices.keys() {
+                    cells.insert(ambient.add_simplex(vec![id], 0.0));
+                }
+                for relation in &complex.faces {
+                    let mut vertices = relation.vertices.clone();
+                    vertices.sort_unstable();
+                    vertices.dedup();
+                    let size = vertices.len().min(self.max_dimension + 2);
+                    for simplex in combinations(&vertices, size) {
+                        let top = ambient.add_simplex(simplex, 0.0);
+                        collect_faces(&ambient, top, &mut cells);
+                    }
+                }
+                cells
+            })
+            .collect();
+
+        let mut nodes = Vec::new();
+        for (i, cells) in sessions.iter().enumerate() {
+            if i > 0 {
+                nodes.push(sessions[i - 1].union(cells).copied().collect());
+            }
+            nodes.push(cells.clone());
+        }
+        Zigzag { ambient, nodes }
+    }
+}
+
+impl Default for ZigzagSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn collect_faces(complex: &ChainComplex, cell: usize, into: &mut BTreeSet<usize>) {
+    if into.insert(cell) {
+        for &face in &complex.cells[cell].boundary {
+            collect_faces(complex, face, into);
+        }
+    }
+}
+
+/// Rank of lim → colim for the window [s, t] of the zigzag
+fn window_rank(homology: &[NodeHomology], arrows: &[Vec<Vec<usize>>], s: usize, t: usize) -> usize {
+    let mut offsets = Vec::with_capacity(t - s + 2);
+    let mut total = 0;
+    for node in &homology[s..=t] {
+        offsets.push(total);
+        total += node.basis.len();
+    }
+    let offset = |j: usize| offsets[j - s];
+
+    // Arrows (source, target, matrix) inside the window; arrow 2k joins 2k → 2k+1
+    let inside: Vec<(usize, usize, &Vec<Vec<usize>>)> = arrows.iter()
+        .enumerate()
+        .map(|(a, matrix)| {
+            let o = 2 * (a / 2) + 1;
+            (if a % 2 == 0 { o - 1 } else { o + 1 }, o, matrix)
+        })
+        .filter(|&(e, o, _)| e.min(o) >= s && e.max(o) <= t)
+        .collect();
+
+    // lim: families (x_j) with M x_e = x_o on every arrow
+    let mut columns: Vec<Vec<usize>> = vec![Vec::new(); total];
+    let mut row = 0;
+    for &(e, o, matrix) in &inside {
+        for (i, image) in matrix.iter().enumerate() {
+            columns[offset(e) + i] = add_mod2(&columns[offset(e) + i], &image.iter().map(|r| row + r).collect::<Vec<_>>());
+        }
+        for r in 0..homology[o].basis.len() {
+            columns[offset(o) + r] = add_mod2(&columns[offset(o) + r], &[row + r]);
+        }
+        row += homology[o].basis.len();
+    }
+    let mut constraints = Echelon::default();
+    let limit: Vec<Vec<usize>> = columns.into_iter()
+        .enumerate()
+        .filter_map(|(k, column)| constraints.insert(column, vec![k]))
+        .collect();
+
+    // colim: ⊕ V_j modulo x_e ~ M x_e
+    let mut relations = Echelon::default();
+    for &(e, o, matrix) in &inside {
+        for (i, image) in matrix.iter().enumerate() {
+            let shifted: Vec<usize> = image.iter().map(|r| offset(o) + r).collect();
+            relations.insert(add_mod2(&[offset(e) + i], &shifted), Vec::new());
+        }
+    }
+
+    // Image of lim, read at the first node of the window
+    let first = offset(s)..offset(s) + homology[s].basis.len();
+    limit.into_iter()
+        .filter(|family| {
+            let component: Vec<usize> = family.iter().copied().filter(|k| first.contains(k)).collect();
+            relations.insert(component, Vec::new()).is_none()
+        })
+        .count()
+}
+
+/// Positions of the zigzag as cell sets of one ambient complex
+struct Zigzag {
+    ambient: ChainComplex,
+    nodes: Vec<BTreeSet<usize>>,
+}
+
+impl Zigzag {
+    /// H_p of one position with a basis of representative cycles
+    fn homology(&self, node: usize, p: usize) -> NodeHomology {
+        let cells = &self.nodes[node];
+        let boundary = |c: usize| {
+            let mut column = self.ambient.cells[c].boundary.clone();
+            column.sort_unstable();
+            column
+        };
+        let of_dimension = |d: usize| cells.iter().copied().filter(move |&c| self.ambient.cells[c].dimension() == d);
+
+        let mut chains = Echelon::default();
+        let cycles: Vec<Vec<usize>> = of_dimension(p)
+            .filter_map(|c| {
+                let column = if p == 0 { Vec::new() } else { boundary(c) };
+                chains.insert(column, vec![c])
+            })
+            .collect();
+
+        let mut quotient = Echelon::default();
+        for c in of_dimension(p + 1) {
+            quotient.insert(boundary(c), Vec::new());
+        }
+
+        let mut basis = Vec::new();
+        for cycle in cycles {
+            let (residual, tag) = quotient.reduce(cycle.clone(), Vec::new());
+            if let Some(&pivot) = residual.last() {
+                quotient.rows.insert(pivot, (residual, add_mod2(&tag, &[basis.len()])));
+                basis.push(cycle);
+            }
+        }
+        NodeHomology { basis, quotient }
+    }
+}
+
+/// A homology group with representatives and a way to take coordinates
+struct NodeHomology {
+    basis: Vec<Vec<usize>>,
+    quotient: Echelon,
+}
+
+impl NodeHomology {
+    /// Coordinates of a cycle in the basis (indices with coefficient 1)
+    fn coordinates(&self, cycle: &[usize]) -> Vec<usize> {
+        self.quotient.reduce(cycle.to_vec(), Vec::new()).1
+    }
+}
+
+/// Sparse GF(2) vectors in echelon form, keyed by their largest index
+///
+/// Each row carries a tag recording which inputs were combined into it.
+#[derive(Default)]
+struct Echelon {
+    rows: HashMap<usize, (Vec<usize>, Vec<usize>)>,
+}
+
+impl Echelon {
+    fn reduce(&self, mut vector: Vec<usize>, mut tag: Vec<usize>) -> (Vec<usize>, Vec<usize>) {
+        while let Some((row, row_tag)) = vector.last().and_then(|p| self.rows.get(p)) {
+            vector = add_mod2(&vector, row);
+            tag = add_mod2(&tag, row_tag);
+        }
+        (vector, tag)
+    }
+
+    /// Insert a vector; if it is dependent, return the tag of the vanishing combination
+    fn insert(&mut self, vector: Vec<usize>, tag: Vec<usize>) -> Option<Vec<usize>> {
+        let (vector, tag) = self.reduce(vector, tag);
+        match vector.last() {
+            Some(&pivot) => {
+                self.rows.insert(pivot, (vector, tag));
+                None
+            }
+            None => Some(tag),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simplex::{ComplexBuilder, Relation};
+
+    fn session(edges: &[(usize, usize)]) -> ConceptComplex {
+        let mut builder = ComplexBuilder::new();
+        for i in 0..3 {
+            builder.add_idea(format!("Idea {}", i));
+        }
+        for &(a, b) in edges {
+            builder.connect(a, b);
+        }
+        builder.build()
+    }
+
+    fn loops(bars: &[ZigzagInterval]) -> Vec<ZigzagInterval> {
+        bars.iter().copied().filter(|b| b.dimension == 1).collect()
+    }
+
+    #[test]
+    fn test_single_session() {
+        let mut sequence = ZigzagSequence::new().with_max_dimension(1);
+        sequence.push(session(&[(0, 1), (1, 2), (0, 2)]));
+
+        let bars = sequence.barcode();
+        assert_eq!(bars.len(), 2);
+        assert_eq!(loops(&bars), vec![ZigzagInterval { dimension: 1, start: 0, end: 0 }]);
+        assert_eq!(sequence.betti_numbers(), vec![vec![1, 1]]);
+    }
+
+    #[test]
+    fn test_forgetting_and_relearning() {
+        let mut sequence = ZigzagSequence::new();
+        sequence
+            .push(session(&[(0, 1), (1, 2), (0, 2)]))
+            .push(session(&[(0, 1), (1, 2)]))
+            .push(session(&[(0, 1), (1, 2), (0, 2)]));
+
+        let holes = loops(&sequence.barcode());
+        assert_eq!(holes.len(), 2);
+        assert_eq!((holes[0].birth_session(), holes[0].last_session()), (Some(0), Some(0)));
+        assert_eq!((holes[1].birth_session(), holes[1].last_session()), (Some(2), Some(2)));
+        assert_eq!(loops(&sequence.forgotten()), vec![holes[0]]);
+        assert!(holes[0].describe(3).contains("forgotten by session 1"));
+        assert!(holes[1].describe(3).contains("still open"));
+    }
+
+    #[test]
+    fn test_hole_only_in_union() {
+        let mut sequence = ZigzagSequence::new();
+        sequence.push(session(&[(0, 1), (1, 2)])).push(session(&[(0, 2)]));
+
+        let holes = loops(&sequence.barcode());
+        assert_eq!(holes, vec![ZigzagInterval { dimension: 1, start: 1, end: 1 }]);
+        assert!(holes[0].is_transient());
+    }
+
+    #[test]
+    fn test_components_merge() {
+        let mut sequence = ZigzagSequence::new().with_max_dimension(0);
+        sequence.push(session(&[(0, 1)])).push(session(&[(0, 1), (1, 2)]));
+
+        let mut bars = sequence.barcode();
+        bars.sort_by_key(|b| b.end);
+        assert_eq!(bars, vec![
+            ZigzagInterval { dimension: 0, start: 0, end: 0 },
+            ZigzagInterval { dimension: 0, start: 0, end: 2 },
+        ]);
+    }
+
+    #[test]
+    fn test_filled_triangle_has_no_loop() {
+        let mut filled = session(&[]);
+        filled.add_relation(Relation::new(vec![0, 1, 2]));
+
+        let mut sequence = ZigzagSequence::new();
+        sequence.push(session(&[(0, 1), (1, 2), (0, 2)])).push(filled);
+
+        let holes = loops(&sequence.barcode());
+        assert_eq!(holes, vec![ZigzagInterval { dimension: 1, start: 0, end: 0 }]);
+        assert_eq!(sequence.betti_numbers()[1], vec![1, 0, 0]);
+    }
+}